@@ -0,0 +1,294 @@
+use crate::error::{Result, SesClientError};
+use aws_sdk_sesv2::types::{Body, Content};
+
+/// The HTML and plain text parts of a simple email, at least one of them is set.
+#[derive(Clone)]
+pub struct SimpleBody {
+    pub html: Option<String>,
+    pub text: Option<String>,
+}
+
+impl SimpleBody {
+    /// A body that starts with a tag is the HTML part, anything else the text part.
+    ///
+    /// An HTML body without a separate `text` part gets one generated from the HTML,
+    /// so recipients with text-only clients still get a readable message. A text
+    /// body already is the text part, so a separate one is refused.
+    pub fn new(body: String, text: Option<String>) -> Result<Self> {
+        let text = text.filter(|text| !text.trim().is_empty());
+        match (is_html(&body), text) {
+            (true, text) => Ok(SimpleBody {
+                text: text.or_else(|| Some(html_to_text(&body))),
+                html: Some(body),
+            }),
+            (false, None) => Ok(SimpleBody {
+                html: None,
+                text: Some(body),
+            }),
+            (false, Some(_)) => Err(SesClientError::validation(
+                "The body isn't HTML, give it as the text part or pass an HTML body",
+            )),
+        }
+    }
+
+    pub fn to_body(&self) -> Body {
+        let content = |data: &String| Content::builder().data(data).charset("UTF-8").build();
+        Body::builder()
+            .set_html(self.html.as_ref().map(content))
+            .set_text(self.text.as_ref().map(content))
+            .build()
+    }
+}
+
+pub fn is_html(body: &str) -> bool {
+    body.trim_start().starts_with('<')
+}
+
+/// Converts an HTML body into plain text.
+///
+/// Tags are stripped, block elements start a new line, list items become `- `
+/// lines and links keep their target as `text (url)`. The content of `head`,
+/// `style` and `script` elements is dropped and common entities are decoded.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut link: Option<(String, usize)> = None;
+    let mut skipped: Option<String> = None;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if skipped.is_none() {
+            push_text(&mut text, &rest[..start]);
+        }
+        let after = &rest[start..];
+        if after.starts_with("<!--") {
+            rest = after
+                .find("-->")
+                .map(|end| &after[end + 3..])
+                .unwrap_or_default();
+            continue;
+        }
+        let Some(end) = after.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &after[1..end];
+        rest = &after[end + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|char: char| char.is_whitespace() || char == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(skipped_name) = &skipped {
+            if closing && *skipped_name == name {
+                skipped = None;
+            }
+            continue;
+        }
+        match (name.as_str(), closing) {
+            ("head" | "style" | "script" | "title", false) => skipped = Some(name),
+            ("br", _) => text.push('\n'),
+            ("li", false) => {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str("- ");
+            }
+            ("li", true) => {}
+            ("td" | "th", false) => text.push(' '),
+            (
+                "p" | "div" | "tr" | "table" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" | "h5"
+                | "h6" | "blockquote" | "hr" | "section" | "header" | "footer",
+                _,
+            ) => new_line(&mut text),
+            ("a", false) => {
+                link = attribute(tag, "href")
+                    .filter(|href| !href.starts_with('#'))
+                    .map(|href| (decode_entities(&href), text.len()))
+            }
+            ("a", true) => {
+                if let Some((href, label_start)) = link.take() {
+                    let label = text[label_start..].trim();
+                    let href_shown = href.trim_start_matches("mailto:");
+                    if label.is_empty() {
+                        text.push_str(&href);
+                    } else if label != href && label != href_shown {
+                        text.push_str(&format!(" ({href})"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if skipped.is_none() {
+        push_text(&mut text, rest);
+    }
+    tidy(&text)
+}
+
+/// Appends the text between tags with its whitespace collapsed the way a browser does.
+fn push_text(text: &mut String, raw: &str) {
+    for char in decode_entities(raw).chars() {
+        match char.is_whitespace() {
+            true => {
+                if !text.is_empty() && !text.ends_with([' ', '\n']) {
+                    text.push(' ');
+                }
+            }
+            false => text.push(char),
+        }
+    }
+}
+
+fn new_line(text: &mut String) {
+    if !text.is_empty() && !text.ends_with("\n\n") {
+        text.push('\n');
+    }
+}
+
+/// Trims every line and keeps at most one blank line between paragraphs.
+fn tidy(text: &str) -> String {
+    let mut tidied = String::with_capacity(text.len());
+    let mut blank = false;
+    for line in text.lines().map(str::trim) {
+        match line.is_empty() {
+            true => blank = !tidied.is_empty(),
+            false => {
+                if blank {
+                    tidied.push('\n');
+                    blank = false;
+                }
+                tidied.push_str(line);
+                tidied.push('\n');
+            }
+        }
+    }
+    tidied
+}
+
+/// The value of a quoted or bare attribute of a tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lowercase = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lowercase[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        if !lowercase[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = tag[search..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value.split(char::is_whitespace).next(),
+        };
+        return value.map(|value| value.trim().to_string());
+    }
+    None
+}
+
+fn decode_entities(raw: &str) -> String {
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let entity = after
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&after[1..end]).map(|char| (char, end)));
+        match entity {
+            Some((char, end)) => {
+                decoded.push(char);
+                rest = &after[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        "copy" => Some('©'),
+        "reg" => Some('®'),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "hellip" => Some('…'),
+        _ => match entity.strip_prefix('#') {
+            Some(code) => match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => code.parse().ok(),
+            }
+            .and_then(char::from_u32),
+            None => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_blocks_lists_and_links() {
+        let html = "<html><head><title>Offer</title><style>p { color: red; }</style></head>\
+            <body><h1>Hello</h1><p>Visit   <a href=\"https://example.com\">our site</a> &amp; more.</p>\
+            <ul><li>One</li><li>Two</li></ul></body></html>";
+        assert_eq!(
+            html_to_text(html),
+            "Hello\n\nVisit our site (https://example.com) & more.\n\n- One\n- Two\n"
+        );
+    }
+
+    #[test]
+    fn shows_each_link_target_once() {
+        assert_eq!(
+            html_to_text("<p><a href='mailto:a@example.com'>a@example.com</a></p>"),
+            "a@example.com\n"
+        );
+        assert_eq!(
+            html_to_text("<p><a href=https://example.com><img src=\"logo.png\"></a></p>"),
+            "https://example.com\n"
+        );
+        assert_eq!(html_to_text("<a href=\"#top\">Top</a>"), "Top\n");
+    }
+
+    #[test]
+    fn drops_comments_and_decodes_entities() {
+        assert_eq!(
+            html_to_text("<p>a<!-- hidden -->b &#x41;&#66; &lt;tag&gt; &unknown; x</p>"),
+            "ab AB <tag> &unknown; x\n"
+        );
+        assert_eq!(html_to_text("line<br>next<br/>last"), "line\nnext\nlast\n");
+    }
+
+    #[test]
+    fn generates_the_text_part_of_html_bodies_only() {
+        let html = SimpleBody::new("  <p>Hi</p>".into(), None).unwrap();
+        assert_eq!(html.html.as_deref(), Some("  <p>Hi</p>"));
+        assert_eq!(html.text.as_deref(), Some("Hi\n"));
+
+        let given = SimpleBody::new("<p>Hi</p>".into(), Some("Hello".into())).unwrap();
+        assert_eq!(given.text.as_deref(), Some("Hello"));
+
+        let text = SimpleBody::new("Hi there".into(), Some(" ".into())).unwrap();
+        assert_eq!(text.html, None);
+        assert_eq!(text.text.as_deref(), Some("Hi there"));
+
+        // The text part of a text body would silently replace the body
+        assert!(SimpleBody::new("Hi there".into(), Some("Hello".into())).is_err());
+    }
+}
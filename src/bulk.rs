@@ -0,0 +1,304 @@
+use crate::context::Context;
+use crate::envelope::Envelope;
+use crate::error::{Result, SesClientError};
+use crate::journal::Journal;
+use aws_sdk_sesv2::error::{ProvideErrorMetadata, SdkError};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::future::Future;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// How many times a throttled request is sent again before its recipients are marked failed.
+pub const THROTTLE_RETRIES: u32 = 5;
+
+/// The outcome of a single recipient, the message ID on success.
+pub struct Delivery {
+    pub recipient: String,
+    pub outcome: std::result::Result<String, String>,
+}
+
+/// A single request to SES, a `SendEmail` to one recipient or a `SendBulkEmail` to many.
+pub trait Job {
+    fn recipients(&self) -> Vec<String>;
+}
+
+impl Job for String {
+    fn recipients(&self) -> Vec<String> {
+        vec![self.clone()]
+    }
+}
+
+impl Job for Vec<(String, Value)> {
+    fn recipients(&self) -> Vec<String> {
+        self.iter().map(|(email, _)| email.clone()).collect()
+    }
+}
+
+/// Sends jobs concurrently while keeping under the account's maximum send rate.
+pub struct BulkSender {
+    bucket: TokenBucket,
+    concurrency: usize,
+    recipients_per_message: usize,
+    journal: Option<Journal>,
+}
+
+impl BulkSender {
+    /// Reads the send quota of the account and fails early when the remaining
+    /// 24-hour quota can't cover `messages` messages, each counted with its CC and
+    /// BCC recipients.
+    pub async fn for_account(ctx: &Context, messages: usize, envelope: &Envelope) -> Result<Self> {
+        let recipients_per_message = envelope.recipients_per_message();
+        let recipients = messages * recipients_per_message;
+        let output = ctx
+            .client
+            .get_account()
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("GetAccount", err))?;
+        let quota = output.send_quota().ok_or_else(|| {
+            SesClientError::validation("SES didn't return the send quota of the account")
+        })?;
+        let max_send_rate = quota.max_send_rate().max(1.0);
+        // A negative 24-hour maximum stands for an unlimited quota
+        let remaining = quota.max24_hour_send() - quota.sent_last24_hours();
+        if quota.max24_hour_send() >= 0.0 && recipients as f64 > remaining {
+            return Err(SesClientError::validation(format!(
+                "Sending {messages} emails to {recipients} recipients exceeds the remaining 24-hour quota of {} recipients ({} of {} are used)",
+                remaining.max(0.0),
+                quota.sent_last24_hours(),
+                quota.max24_hour_send()
+            )));
+        }
+        println!(
+            "Max send rate: {} emails per second, remaining 24-hour quota: {}\n",
+            max_send_rate.to_string().green().bold(),
+            match quota.max24_hour_send() >= 0.0 {
+                true => remaining.to_string(),
+                false => "unlimited".into(),
+            }
+            .green()
+            .bold()
+        );
+        Ok(BulkSender {
+            bucket: TokenBucket::new(max_send_rate),
+            concurrency: max_send_rate.ceil() as usize,
+            recipients_per_message,
+            journal: None,
+        })
+    }
+
+    /// Records the outcome of every recipient in the journal as soon as it is known.
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Runs every job paced by the token bucket, a job takes one token per
+    /// recipient of each of its messages. Throttled jobs are retried with an
+    /// exponential backoff, a live counter shows the progress.
+    pub async fn run<T, F, Fut, E, R>(&self, jobs: Vec<T>, send: F) -> Vec<Delivery>
+    where
+        T: Job,
+        F: Fn(&T) -> Fut,
+        Fut: Future<Output = std::result::Result<Vec<Delivery>, SdkError<E, R>>>,
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+        R: std::fmt::Debug,
+    {
+        let total = jobs.iter().map(|job| job.recipients().len()).sum::<usize>();
+        let (sent, failed) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let deliveries = stream::iter(jobs)
+            .map(|job| {
+                let (send, sent, failed) = (&send, &sent, &failed);
+                async move {
+                    let recipients = job.recipients();
+                    let mut attempt = 0;
+                    let deliveries = loop {
+                        self.bucket
+                            .acquire(recipients.len() * self.recipients_per_message)
+                            .await;
+                        match send(&job).await {
+                            Ok(deliveries) => break deliveries,
+                            Err(err) if attempt < THROTTLE_RETRIES && is_retryable(&err) => {
+                                tokio::time::sleep(backoff(attempt)).await;
+                                attempt += 1;
+                            }
+                            Err(err) => {
+                                let reason = SesClientError::ses("Send", err).to_string();
+                                break recipients
+                                    .into_iter()
+                                    .map(|recipient| Delivery {
+                                        recipient,
+                                        outcome: Err(reason.clone()),
+                                    })
+                                    .collect();
+                            }
+                        }
+                    };
+                    for delivery in &deliveries {
+                        if let Some(Err(err)) = self
+                            .journal
+                            .as_ref()
+                            .map(|journal| journal.record(delivery))
+                        {
+                            err.report();
+                        }
+                        match delivery.outcome {
+                            Ok(_) => sent.fetch_add(1, Ordering::Relaxed),
+                            Err(_) => failed.fetch_add(1, Ordering::Relaxed),
+                        };
+                    }
+                    print!(
+                        "\r    {} of {total} sent, {} failed",
+                        sent.load(Ordering::Relaxed).to_string().green().bold(),
+                        failed.load(Ordering::Relaxed).to_string().red().bold()
+                    );
+                    std::io::stdout().flush().ok();
+                    deliveries
+                }
+            })
+            .buffer_unordered(self.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        println!("\n");
+        deliveries.into_iter().flatten().collect()
+    }
+}
+
+/// Prints the failed recipients and a summary, an error is returned if any failed.
+pub fn report(deliveries: &[Delivery], what: &str) -> Result<()> {
+    let failed = deliveries
+        .iter()
+        .filter_map(|delivery| match &delivery.outcome {
+            Ok(_) => None,
+            Err(reason) => Some((&delivery.recipient, reason)),
+        })
+        .collect::<Vec<_>>();
+    for (recipient, reason) in &failed {
+        println!("    {} '{recipient}': {reason}", "Failed".red().bold());
+    }
+    println!(
+        "{} {what} sent to {} of {} recipients\n",
+        "Done:".green().bold(),
+        (deliveries.len() - failed.len()).to_string().green().bold(),
+        deliveries.len()
+    );
+    match failed.is_empty() {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "{} of the {} {what} couldn't be sent",
+            failed.len(),
+            deliveries.len()
+        ))),
+    }
+}
+
+/// Sends a single request, again with the bulk backoff while it is throttled.
+pub async fn with_retries<T, F, Fut, E, R>(request: F) -> std::result::Result<T, SdkError<E, R>>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = std::result::Result<T, SdkError<E, R>>>,
+    E: ProvideErrorMetadata,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Err(err) if attempt < THROTTLE_RETRIES && is_retryable(&err) => {
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(500 * 2u64.pow(attempt))
+}
+
+/// Throttling and transport errors are worth another attempt, anything else will fail again.
+pub fn is_retryable<E: ProvideErrorMetadata, R>(err: &SdkError<E, R>) -> bool {
+    match err {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) => true,
+        _ => matches!(err.code(), Some("TooManyRequestsException" | "Throttling")),
+    }
+}
+
+/// Refills `rate` tokens per second up to a burst of one second worth of tokens.
+struct TokenBucket {
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        TokenBucket {
+            rate,
+            state: Mutex::new((rate, Instant::now())),
+        }
+    }
+
+    /// Waits until `tokens` are available, a request larger than the burst waits
+    /// for a full bucket and leaves it in debt so the average rate still holds.
+    async fn acquire(&self, tokens: usize) {
+        let tokens = tokens as f64;
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let (available, last_refill) = &mut *state;
+                *available =
+                    (*available + last_refill.elapsed().as_secs_f64() * self.rate).min(self.rate);
+                *last_refill = Instant::now();
+                let needed = tokens.min(self.rate);
+                if *available >= needed {
+                    *available -= tokens;
+                    return;
+                }
+                Duration::from_secs_f64((needed - *available) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The clock is paused, a sleep advances it right away by exactly its duration
+
+    #[tokio::test(start_paused = true)]
+    async fn token_bucket_allows_a_burst_then_keeps_the_rate() {
+        let bucket = TokenBucket::new(100.0);
+        let start = Instant::now();
+        bucket.acquire(100).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // The bucket is empty, 20 tokens take 200 ms to refill
+        bucket.acquire(20).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200) && elapsed < Duration::from_millis(201));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn token_bucket_goes_into_debt_for_requests_above_the_burst() {
+        let bucket = TokenBucket::new(100.0);
+        let start = Instant::now();
+        // A full bucket lets a request of twice the burst through and owes 100 tokens
+        bucket.acquire(200).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        bucket.acquire(10).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(1100) && elapsed < Duration::from_millis(1101));
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        assert_eq!(backoff(0), Duration::from_millis(500));
+        assert_eq!(backoff(3), Duration::from_millis(4000));
+    }
+}
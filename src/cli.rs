@@ -0,0 +1,930 @@
+use crate::contact_filter::{parse_conditions, ContactFilter};
+use crate::contact_lists::parse_preferences;
+use crate::envelope::{parse_tags, Envelope};
+use crate::error::Result;
+use crate::mime::RawFiles;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+
+/// Every operation of the interactive SES menu, reachable without prompts.
+///
+/// Running the binary without a subcommand starts the interactive menu.
+#[derive(Parser)]
+#[command(name = "ses_client", version)]
+pub struct Cli {
+    /// Where the scripted commands load the AWS credentials from, defaults to the
+    /// profile's source or 'config'
+    #[arg(long, value_enum, global = true)]
+    pub credentials: Option<CredentialSource>,
+
+    /// Named profile of the configuration file, defaults to its 'default_profile'
+    #[arg(long, global = true, env = "SES_CLIENT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Path to the configuration file, defaults to ~/.config/ses_client/config.toml
+    #[arg(long, global = true, env = "SES_CLIENT_CONFIG")]
+    pub config: Option<String>,
+
+    /// Send every SES request to this endpoint instead of the AWS one, e.g. http://localhost:4566
+    #[arg(long, global = true, env = "SES_ENDPOINT_URL")]
+    pub endpoint_url: Option<String>,
+
+    /// Render every send into a report file instead of sending, and skip every mutating SES call
+    #[arg(long, global = true, env = "SES_DRY_RUN")]
+    pub dry_run: bool,
+
+    /// What a bulk send from an account in the SES sandbox does with recipients who
+    /// aren't verified, asked before the send when it isn't given
+    #[arg(long, value_enum, global = true, env = "SES_UNVERIFIED")]
+    pub unverified: Option<UnverifiedAction>,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialSource {
+    /// The shared AWS configuration files (~/.aws/config and ~/.aws/credentials)
+    Config,
+    /// AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY and AWS_DEFAULT_REGION, a .env file is honoured
+    Env,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// One row per contact, the columns match the ones 'contact-list import' reads
+    Csv,
+    /// An array of contact objects
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Contact list operations
+    #[command(subcommand)]
+    ContactList(ContactListCommand),
+    /// Email template operations
+    #[command(subcommand)]
+    Template(TemplateCommand),
+    /// Email identity operations
+    #[command(subcommand)]
+    Identity(IdentityCommand),
+    /// Configuration set and event destination operations
+    #[command(subcommand)]
+    ConfigurationSet(ConfigurationSetCommand),
+    /// Send a single simple email
+    SendSimple(SendSimpleArgs),
+    /// Send a simple email to every address in a contact list
+    SendBulkSimple(SendBulkSimpleArgs),
+    /// Send a single templated email
+    SendTemplated(SendTemplatedArgs),
+    /// Send a single email with attachments and inline images as a raw MIME message
+    SendRaw(SendRawArgs),
+    /// Send an email with attachments and inline images to every address in a contact list
+    SendBulkRaw(SendBulkRawArgs),
+    /// Send a template to every address in a contact list with per-recipient data
+    SendBulkTemplated(SendBulkTemplatedArgs),
+    /// Continue an interrupted bulk send, skipping the recipients its journal has as delivered
+    Resume {
+        /// Path to the campaign_<time>.jsonl journal the bulk send wrote
+        #[arg(long)]
+        journal: String,
+    },
+    /// Print the default values and where each one came from
+    Defaults,
+}
+
+#[derive(Subcommand)]
+pub enum ContactListCommand {
+    /// Create a contact list name
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        description: Option<String>,
+        /// A topic as 'name:display name:OPT_IN|OPT_OUT[:description]', can be repeated,
+        /// the status is the default preference of the contacts
+        #[arg(long = "topic")]
+        topics: Vec<String>,
+    },
+    /// Change the description and the topics of a contact list, keeping its contacts
+    Update {
+        #[arg(long)]
+        name: String,
+        /// Replaces the description, an empty one removes it
+        #[arg(long)]
+        description: Option<String>,
+        /// A topic as 'name:display name:OPT_IN|OPT_OUT[:description]', can be repeated,
+        /// replaces the topic with the same name
+        #[arg(long = "topic")]
+        topics: Vec<String>,
+        /// Name of a topic to remove, can be repeated
+        #[arg(long = "remove-topic")]
+        removed_topics: Vec<String>,
+    },
+    /// Clone a contact list with its topics and contacts into a new list, in another
+    /// region or, with '--delete-source', under a new name
+    Copy {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        from: Option<String>,
+        /// Name of the new list
+        #[arg(long)]
+        to: String,
+        /// Region of the new list, defaults to the current region
+        #[arg(long)]
+        region: Option<String>,
+        /// Delete the source list once every contact is copied, a copy within the region
+        /// needs it since SES allows one list per region, the contacts are backed up
+        /// to a JSON file first
+        #[arg(long)]
+        delete_source: bool,
+    },
+    /// Print the description and the topics of a contact list
+    Describe {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Add an email to a contact list
+    AddEmail {
+        #[arg(long)]
+        email: String,
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        /// Send a verification email as well
+        #[arg(long)]
+        verify: bool,
+        /// Topic preferences as topic=OPT_IN|OPT_OUT, topics without one use their default
+        #[arg(long = "preference", value_delimiter = ',')]
+        preferences: Vec<String>,
+        /// Unsubscribe the contact from every topic of the list
+        #[arg(long)]
+        unsubscribe_all: bool,
+    },
+    /// Print the attributes, topic preferences and unsubscribe-all flag of a contact
+    GetContact {
+        #[arg(long)]
+        email: String,
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Change the attributes, topic preferences or unsubscribe-all flag of a contact
+    UpdateContact {
+        #[arg(long)]
+        email: String,
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        /// JSON object added to the attributes, a null value removes the attribute
+        #[arg(long)]
+        attributes: Option<String>,
+        /// Replace every attribute with the given ones instead of adding them
+        #[arg(long, requires = "attributes")]
+        replace_attributes: bool,
+        /// Topic preferences as topic=OPT_IN|OPT_OUT, added to the current ones
+        #[arg(long = "preference", value_delimiter = ',')]
+        preferences: Vec<String>,
+        /// Unsubscribe the contact from every topic or subscribe it again
+        #[arg(long, action = ArgAction::Set)]
+        unsubscribe_all: Option<bool>,
+    },
+    /// Create the contacts of a CSV or JSON file in a contact list
+    ///
+    /// A CSV file needs an 'email' column and may have an 'unsubscribe_all' column,
+    /// 'topic:<name>' columns with OPT_IN or OPT_OUT and any other column becomes an
+    /// attribute. A JSON file is an array of objects with 'email' and the optional
+    /// 'attributes', 'topics' and 'unsubscribe_all' fields.
+    Import {
+        /// Path to a .csv or .json file
+        #[arg(long)]
+        file: String,
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        /// How many contacts are created at the same time
+        #[arg(long, default_value_t = 10)]
+        concurrency: usize,
+        /// How many times a throttled request is retried, at most 10
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(0..=10))]
+        retries: u32,
+    },
+    /// Export every contact of a list with its attributes, topic preferences,
+    /// unsubscribe-all status and last-updated timestamp
+    Export {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// File to write, the export is streamed to stdout when omitted or '-'
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Write the emails of a contact list as text and PDF files
+    Emails {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Delete a single contact from a contact list
+    DeleteContact {
+        #[arg(long)]
+        email: String,
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Delete every contact in a contact list
+    DeleteContacts {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        /// Only delete the contacts that match
+        #[command(flatten)]
+        filter: ContactFilterArgs,
+    },
+    /// Print the emails of the contacts that match a filter, a page at a time
+    Search {
+        /// Defaults to the 'LIST_NAME' value
+        #[arg(long)]
+        list: Option<String>,
+        #[command(flatten)]
+        filter: ContactFilterArgs,
+        /// Page to print, numbered from 1
+        #[arg(long, default_value_t = 1)]
+        page: usize,
+        #[arg(long, default_value_t = 100)]
+        page_size: usize,
+        /// Write every matching email to this file, one per line
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Delete a contact list name along with its contacts
+    Delete {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateCommand {
+    /// Create an email template from local subject, HTML and text files
+    Create(TemplateFiles),
+    /// Replace the subject, HTML and text parts of an existing template
+    Update(TemplateFiles),
+    /// Download the subject, HTML and text parts of a template
+    Get {
+        #[arg(long)]
+        name: String,
+    },
+    /// Print the template variables of the subject and HTML parts
+    Variables {
+        #[arg(long)]
+        name: String,
+    },
+    /// Compare JSON template data against the variables of a template
+    Match {
+        /// Defaults to the 'TEMPLATE_NAME' value
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        data: String,
+    },
+    /// Render a template with JSON template data locally, without sending anything
+    Preview {
+        /// Template stored in SES, defaults to the 'TEMPLATE_NAME' value
+        #[arg(long, conflicts_with = "dir")]
+        name: Option<String>,
+        /// Directory with subject.html, template.html and an optional text_body.txt instead
+        #[arg(long)]
+        dir: Option<String>,
+        /// Path to the JSON template data
+        #[arg(long)]
+        data: String,
+        /// Where the rendered HTML is written
+        #[arg(long, default_value = "template_preview.html")]
+        output: String,
+    },
+    /// Download a template and then delete it
+    Delete {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Args)]
+pub struct TemplateFiles {
+    #[arg(long)]
+    pub name: String,
+    /// Path to the subject part in HTML format
+    #[arg(long)]
+    pub subject: String,
+    /// Path to the HTML body
+    #[arg(long)]
+    pub html: String,
+    /// Path to the text body for recipients who do not support HTML
+    #[arg(long)]
+    pub text: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum IdentityCommand {
+    /// Send a verification email to the given address
+    Create {
+        #[arg(long)]
+        email: String,
+    },
+    /// Check the verification status of an email identity
+    Verify {
+        #[arg(long)]
+        email: String,
+    },
+    /// Write the details of every email identity as text and PDF files
+    List,
+    /// Create a domain identity and print the DNS records that verify it
+    CreateDomain {
+        #[arg(long)]
+        domain: String,
+        /// Subdomain used as the MAIL FROM domain, e.g. mail.example.com
+        #[arg(long)]
+        mail_from: Option<String>,
+        /// Reject messages while the MAIL FROM MX record is missing instead of using amazonses.com
+        #[arg(long, requires = "mail_from")]
+        reject_on_mx_failure: bool,
+        /// Selector of a Bring Your Own DKIM key, Easy DKIM is used without it
+        #[arg(long, requires = "byodkim_private_key")]
+        byodkim_selector: Option<String>,
+        /// Path to the PEM private key of the BYODKIM pair
+        #[arg(long, requires = "byodkim_selector")]
+        byodkim_private_key: Option<String>,
+        /// Path to the PEM public key, used to print its TXT record
+        #[arg(long, requires = "byodkim_selector")]
+        byodkim_public_key: Option<String>,
+        /// Write the records as a zone-file snippet to this path
+        #[arg(long)]
+        zone_file: Option<String>,
+    },
+    /// Set or remove the custom MAIL FROM domain of a domain identity
+    MailFrom {
+        #[arg(long)]
+        domain: String,
+        /// Subdomain used as the MAIL FROM domain, leave it out to remove it
+        #[arg(long)]
+        mail_from: Option<String>,
+        #[arg(long, requires = "mail_from")]
+        reject_on_mx_failure: bool,
+    },
+    /// Print the DNS records of a domain identity as a zone-file snippet
+    DnsRecords {
+        #[arg(long)]
+        domain: String,
+        /// Write the snippet to this path instead of printing it
+        #[arg(long)]
+        zone_file: Option<String>,
+    },
+    /// Check which DNS records of a domain identity are published
+    CheckDns {
+        #[arg(long)]
+        domain: String,
+        /// Address of the DNS server to ask, e.g. 127.0.0.1:5353, the system resolver is used without it
+        #[arg(long, env = "DNS_RESOLVER")]
+        resolver: Option<String>,
+    },
+    /// Delete an email address or domain identity along with its policies
+    Delete {
+        #[arg(long)]
+        identity: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Attach a sending authorization policy from a JSON file, replacing one of the same name
+    PutPolicy {
+        #[arg(long)]
+        identity: String,
+        #[arg(long)]
+        name: String,
+        /// Path to the policy document, e.g. all_policy.json
+        #[arg(long)]
+        policy: String,
+        /// Replace an existing policy without asking
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Print the sending authorization policies of an identity
+    GetPolicies {
+        #[arg(long)]
+        identity: String,
+        /// Only print this policy
+        #[arg(long)]
+        name: Option<String>,
+        /// Also write the policies to this path
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Delete a sending authorization policy of an identity
+    DeletePolicy {
+        #[arg(long)]
+        identity: String,
+        #[arg(long)]
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Print the type, verification, DKIM, sending and MAIL FROM status of every identity
+    Status {
+        /// Column the identities are sorted by
+        #[arg(long, value_enum, default_value_t = IdentityColumn::Identity)]
+        sort: IdentityColumn,
+        #[arg(long)]
+        descending: bool,
+        /// Also write the table as CSV to this path
+        #[arg(long)]
+        csv: Option<String>,
+        /// Send the verification email again to every email address that is still pending,
+        /// by deleting and creating its identity again
+        #[arg(long)]
+        resend_pending: bool,
+        /// Resend without asking for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Turn the forwarding of bounces and complaints by email on or off
+    FeedbackForwarding {
+        #[arg(long)]
+        identity: String,
+        #[arg(long, action = ArgAction::Set)]
+        enabled: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigurationSetCommand {
+    /// Create a configuration set
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Collect the bounce and complaint rates of the messages sent with the set
+        #[arg(long)]
+        reputation_metrics: bool,
+        /// Create the set with sending paused
+        #[arg(long)]
+        sending_disabled: bool,
+        #[arg(long, value_enum)]
+        tls_policy: Option<TlsRequirement>,
+        /// Custom domain of the open and click tracking links
+        #[arg(long)]
+        tracking_domain: Option<String>,
+    },
+    /// List the configuration sets of the account
+    List,
+    /// Print the options and the event destinations of a configuration set
+    Describe {
+        #[arg(long)]
+        name: String,
+    },
+    /// Delete a configuration set along with its event destinations
+    Delete {
+        #[arg(long)]
+        name: String,
+    },
+    /// Turn the reputation metrics of a configuration set on or off
+    ReputationMetrics {
+        #[arg(long)]
+        name: String,
+        #[arg(long, action = ArgAction::Set)]
+        enabled: bool,
+    },
+    /// Pause or resume sending the messages of a configuration set
+    Sending {
+        #[arg(long)]
+        name: String,
+        #[arg(long, action = ArgAction::Set)]
+        enabled: bool,
+    },
+    /// Deliver the messages of a configuration set only over TLS or whenever possible
+    TlsPolicy {
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_enum)]
+        policy: TlsRequirement,
+    },
+    /// Set the custom domain of the open and click tracking links
+    TrackingDomain {
+        #[arg(long)]
+        name: String,
+        /// Omit it to go back to the domain SES provides
+        #[arg(long)]
+        domain: Option<String>,
+    },
+    /// Send the events of a configuration set to SNS, CloudWatch, Firehose or EventBridge
+    AddEventDestination(EventDestinationArgs),
+    /// Delete an event destination of a configuration set
+    DeleteEventDestination {
+        /// The configuration set
+        #[arg(long)]
+        set: String,
+        /// Name of the event destination
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum UnverifiedAction {
+    /// Send to the verified recipients only
+    Skip,
+    /// Send the unverified recipients verification emails and skip them
+    Verify,
+    /// Send nothing
+    Abort,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum IdentityColumn {
+    Identity,
+    Type,
+    Verification,
+    Dkim,
+    Sending,
+    MailFrom,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TlsRequirement {
+    /// Messages are only delivered over TLS
+    Require,
+    /// TLS is used when the receiving server supports it
+    Optional,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum EventKind {
+    Send,
+    Reject,
+    Bounce,
+    Complaint,
+    Delivery,
+    Open,
+    Click,
+    RenderingFailure,
+    DeliveryDelay,
+    Subscription,
+}
+
+#[derive(Args)]
+pub struct EventDestinationArgs {
+    /// The configuration set
+    #[arg(long)]
+    pub set: String,
+    /// Name of the event destination
+    #[arg(long)]
+    pub name: String,
+    /// The events sent to the destination
+    #[arg(long, value_enum, value_delimiter = ',', required = true)]
+    pub events: Vec<EventKind>,
+    /// Create the destination without sending it any event yet
+    #[arg(long)]
+    pub disabled: bool,
+    #[command(subcommand)]
+    pub target: EventTarget,
+}
+
+#[derive(Subcommand)]
+pub enum EventTarget {
+    /// An SNS topic
+    Sns {
+        #[arg(long)]
+        topic_arn: String,
+    },
+    /// CloudWatch metrics
+    CloudWatch {
+        /// A dimension as name:source:default, the source is message-tag,
+        /// email-header or link-tag
+        #[arg(long = "dimension", required = true)]
+        dimensions: Vec<String>,
+    },
+    /// A Kinesis Data Firehose delivery stream
+    Firehose {
+        #[arg(long)]
+        delivery_stream_arn: String,
+        /// Role SES assumes to write to the stream
+        #[arg(long)]
+        iam_role_arn: String,
+    },
+    /// An EventBridge event bus
+    EventBridge {
+        #[arg(long)]
+        event_bus_arn: String,
+    },
+}
+
+/// The body of a simple email and its separate text part.
+#[derive(Args)]
+pub struct BodySource {
+    #[command(flatten)]
+    pub origin: BodyOrigin,
+    /// Local file with the plain text part sent along an HTML body, generated
+    /// from the HTML when it's left out
+    #[arg(long)]
+    pub text_file: Option<String>,
+}
+
+/// Where the body is read from. Clap puts every field of a struct with a group
+/// in the group, so the text part lives in `BodySource`.
+#[derive(Args)]
+#[group(id = "body", required = true, multiple = false)]
+pub struct BodyOrigin {
+    /// Local file with the text or HTML body
+    #[arg(long)]
+    pub body_file: Option<String>,
+    /// Publicly accessible S3 object URL with the text or HTML body
+    #[arg(long)]
+    pub body_url: Option<String>,
+}
+
+/// The CC, BCC and Reply-To addresses, the configuration set and the message tags,
+/// the list options take a comma separated list or can be repeated.
+#[derive(Args)]
+pub struct EnvelopeArgs {
+    #[arg(long, value_delimiter = ',')]
+    pub cc: Vec<String>,
+    #[arg(long, value_delimiter = ',')]
+    pub bcc: Vec<String>,
+    #[arg(long, value_delimiter = ',')]
+    pub reply_to: Vec<String>,
+    /// Defaults to the 'CONFIGURATION_SET' value
+    #[arg(long)]
+    pub configuration_set: Option<String>,
+    /// Message tags as name=value, added to the 'EMAIL_TAGS' defaults
+    #[arg(long = "tag", value_delimiter = ',')]
+    pub tags: Vec<String>,
+}
+
+impl EnvelopeArgs {
+    pub fn envelope(self) -> Result<Envelope> {
+        Ok(Envelope {
+            cc: self.cc,
+            bcc: self.bcc,
+            reply_to: self.reply_to,
+            configuration_set: self.configuration_set,
+            tags: parse_tags(&self.tags.join(","))?,
+            topic: None,
+            selection: ContactFilter::default(),
+        })
+    }
+}
+
+/// Conditions that select contacts of a list, every given one must match,
+/// the list options take a comma separated list or can be repeated.
+#[derive(Args)]
+pub struct ContactFilterArgs {
+    /// Topic statuses as topic=OPT_IN|OPT_OUT, contacts without a preference use the topic default
+    #[arg(long = "topic-status", value_delimiter = ',')]
+    pub topic_statuses: Vec<String>,
+    /// Only the contacts that unsubscribed from every topic, or only the others
+    #[arg(long = "unsubscribed", action = ArgAction::Set)]
+    pub unsubscribe_all: Option<bool>,
+    /// Attribute values as name=value
+    #[arg(long = "attribute", value_delimiter = ',')]
+    pub attributes: Vec<String>,
+    /// Domain of the email address
+    #[arg(long)]
+    pub domain: Option<String>,
+    /// Regular expression the email address must match
+    #[arg(long)]
+    pub email_pattern: Option<String>,
+}
+
+impl ContactFilterArgs {
+    pub fn filter(self) -> Result<ContactFilter> {
+        let filter = ContactFilter {
+            topics: parse_preferences(&self.topic_statuses.join(","))?,
+            unsubscribe_all: self.unsubscribe_all,
+            attributes: parse_conditions(&self.attributes.join(","))?,
+            domain: self.domain,
+            pattern: self.email_pattern,
+        };
+        filter.validate()?;
+        Ok(filter)
+    }
+}
+
+#[derive(Args)]
+pub struct SendSimpleArgs {
+    /// Verified email addresses, at most 50 together with the CC and BCC addresses
+    #[arg(long, required = true, value_delimiter = ',')]
+    pub to: Vec<String>,
+    #[arg(long)]
+    pub subject: String,
+    #[command(flatten)]
+    pub body: BodySource,
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SendBulkSimpleArgs {
+    #[arg(long)]
+    pub subject: String,
+    #[command(flatten)]
+    pub body: BodySource,
+    /// Added to the message of every contact
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+    /// Defaults to the 'LIST_NAME' value
+    #[arg(long)]
+    pub list: Option<String>,
+    /// Only send to the contacts subscribed to this topic of the list
+    #[arg(long)]
+    pub topic: Option<String>,
+    /// Only send to the contacts that match
+    #[command(flatten)]
+    pub filter: ContactFilterArgs,
+}
+
+#[derive(Args)]
+pub struct SendTemplatedArgs {
+    /// Verified email addresses, at most 50 together with the CC and BCC addresses
+    #[arg(long, required = true, value_delimiter = ',')]
+    pub to: Vec<String>,
+    /// Defaults to the 'TEMPLATE_NAME' value
+    #[arg(long)]
+    pub template: Option<String>,
+    /// Path to the template data in JSON format
+    #[arg(long, required_unless_present = "contact_list")]
+    pub data: Option<String>,
+    /// Use the attributes of the To contact in this list as template data,
+    /// overridden by the '--data' values
+    #[arg(long)]
+    pub contact_list: Option<String>,
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SendBulkTemplatedArgs {
+    /// Defaults to the 'TEMPLATE_NAME' value
+    #[arg(long)]
+    pub template: Option<String>,
+    /// Defaults to the 'LIST_NAME' value
+    #[arg(long)]
+    pub list: Option<String>,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+    /// CSV or JSON file with the template data of each recipient keyed by email,
+    /// recipients missing from it use the attributes of their contact
+    #[arg(long)]
+    pub data: Option<String>,
+    /// JSON template data shared by every recipient, overridden by the per-recipient data
+    #[arg(long)]
+    pub default_data: Option<String>,
+    /// Added to the message of every contact
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Only send to the contacts subscribed to this topic of the list
+    #[arg(long)]
+    pub topic: Option<String>,
+    /// Only send to the contacts that match
+    #[command(flatten)]
+    pub filter: ContactFilterArgs,
+}
+
+#[derive(Args)]
+pub struct RawParts {
+    #[arg(long)]
+    pub subject: String,
+    #[command(flatten)]
+    pub body: RawBody,
+    /// File to attach, repeat it for several files
+    #[arg(long)]
+    pub attach: Vec<String>,
+    /// Image referenced from the HTML body as 'cid:<file name>', repeat it for several images
+    #[arg(long)]
+    pub inline: Vec<String>,
+}
+
+/// The bodies of a raw message, at least one of them is required.
+#[derive(Args)]
+#[group(id = "raw_body", required = true, multiple = true)]
+pub struct RawBody {
+    /// Local file with the HTML body
+    #[arg(long)]
+    pub html: Option<String>,
+    /// Local file with the text body, shown by clients without HTML support
+    #[arg(long)]
+    pub text: Option<String>,
+}
+
+impl RawParts {
+    pub fn files(self) -> RawFiles {
+        RawFiles {
+            subject: self.subject,
+            html: self.body.html,
+            text: self.body.text,
+            attachments: self.attach,
+            inline: self.inline,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct SendRawArgs {
+    /// A verified email address
+    #[arg(long)]
+    pub to: String,
+    #[command(flatten)]
+    pub parts: RawParts,
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SendBulkRawArgs {
+    /// Defaults to the 'LIST_NAME' value
+    #[arg(long)]
+    pub list: Option<String>,
+    #[command(flatten)]
+    pub parts: RawParts,
+    /// Only send to the contacts subscribed to this topic of the list
+    #[arg(long)]
+    pub topic: Option<String>,
+    /// Only send to the contacts that match
+    #[command(flatten)]
+    pub filter: ContactFilterArgs,
+    /// Added to the message of every contact
+    #[command(flatten)]
+    pub envelope: EnvelopeArgs,
+    /// Defaults to the 'FROM_ADDRESS' value
+    #[arg(long)]
+    pub from: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> std::result::Result<Cli, clap::Error> {
+        Cli::try_parse_from(["ses_client"].iter().chain(args))
+    }
+
+    #[test]
+    fn takes_a_text_part_along_the_body() {
+        let cli = parse(&[
+            "send-simple",
+            "--to",
+            "a@example.com",
+            "--subject",
+            "News",
+            "--body-file",
+            "news.html",
+            "--text-file",
+            "news.txt",
+        ])
+        .unwrap();
+        let Some(Commands::SendSimple(args)) = cli.command else {
+            panic!("send-simple parses into another command");
+        };
+        assert_eq!(args.body.origin.body_file.as_deref(), Some("news.html"));
+        assert_eq!(args.body.text_file.as_deref(), Some("news.txt"));
+
+        let bulk = ["send-bulk-simple", "--subject", "News"];
+        let both = [
+            "--body-file",
+            "news.html",
+            "--body-url",
+            "https://example.com",
+        ];
+        assert!(parse(&[&bulk[..], &both].concat()).is_err());
+        assert!(parse(&[&bulk[..], &["--text-file", "news.txt"]].concat()).is_err());
+    }
+
+    #[test]
+    fn requires_a_body_for_raw_sends() {
+        let raw = ["send-raw", "--to", "a@example.com", "--subject", "Prices"];
+        assert!(parse(&raw).is_err());
+        assert!(parse(&[&raw[..], &["--attach", "prices.pdf"]].concat()).is_err());
+        assert!(parse(&[&raw[..], &["--text", "prices.txt"]].concat()).is_ok());
+        assert!(
+            parse(&[&raw[..], &["--html", "prices.html", "--text", "prices.txt"]].concat()).is_ok()
+        );
+    }
+}
@@ -0,0 +1,711 @@
+use crate::body::SimpleBody;
+use crate::cli::{
+    BodySource, Commands, ConfigurationSetCommand, ContactFilterArgs, ContactListCommand,
+    CredentialSource, EnvelopeArgs, IdentityCommand, SendBulkSimpleArgs, SendBulkTemplatedArgs,
+    SendSimpleArgs, SendTemplatedArgs, TemplateCommand, TemplateFiles,
+};
+use crate::config::{with_overrides, Defaults};
+use crate::configuration_sets::{self, NewConfigurationSet};
+use crate::contact_filter::{matching_contacts, print_page, write_emails};
+use crate::contact_lists::{
+    self, existing_topic, parse_attributes, parse_preferences, parse_topic, ContactUpdate,
+    ListCopy, ListUpdate,
+};
+use crate::content::{fetch_body, read_file};
+use crate::context::Context;
+use crate::dns_check::{self, parse_resolver};
+use crate::domains::{self, Byodkim, DomainSetup};
+use crate::envelope::Envelope;
+use crate::error::{Result, SesClientError};
+use crate::export::export_contacts;
+use crate::identities;
+use crate::identity_status;
+use crate::import::import_contacts;
+use crate::preview::{preview, template_parts_from_dir};
+use crate::recipients::contact_template_data;
+use crate::sandbox::unreachable_recipients;
+use crate::send;
+use crate::templates;
+use aws_apis::{load_credential_from_env, CredentInitialize, SesOps};
+use colored::Colorize;
+use dotenv::dotenv;
+use inquire::Confirm;
+use std::env::var;
+
+/// Loads the credentials from one of the two sources the 'Verify the Credential' option offers.
+pub async fn update_credentials(
+    credential: &mut CredentInitialize,
+    source: CredentialSource,
+) -> Result<()> {
+    match source {
+        CredentialSource::Config => {
+            let (credentials, region) = load_credential_from_env().await;
+            credential.update(
+                credentials.access_key_id(),
+                credentials.secret_access_key(),
+                region.as_deref(),
+            );
+        }
+        CredentialSource::Env => {
+            dotenv().ok();
+            let env_var = |name: &str| {
+                var(name).map_err(|_| {
+                    SesClientError::Config(format!(
+                        "Ensure that the '{name}' environment variable is set, and its value is provided by AWS"
+                    ))
+                })
+            };
+            let access_key = env_var("AWS_ACCESS_KEY_ID")?;
+            let secret_key = env_var("AWS_SECRET_ACCESS_KEY")?;
+            let region = env_var("AWS_DEFAULT_REGION")?;
+            credential.update(&access_key, &secret_key, Some(&region));
+        }
+    }
+    Ok(())
+}
+
+/// Loads the credentials without prompting and builds the SES operations on top of them.
+pub async fn build_ses_ops(
+    credential: &mut CredentInitialize,
+    source: CredentialSource,
+    defaults: Defaults,
+    dry_run: bool,
+) -> Result<(SesOps, Context)> {
+    update_credentials(credential, source).await?;
+    defaults.apply_to_env();
+    let sdk_config = with_overrides(credential.build(), &defaults);
+    Ok((
+        SesOps::build(&sdk_config),
+        Context::new(&sdk_config, dry_run, defaults),
+    ))
+}
+
+/// Executes a single subcommand, mirroring the matching entry of the SES menu.
+pub async fn run(command: Commands, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    match command {
+        Commands::ContactList(command) => run_contact_list(command, ses_ops, ctx).await,
+        Commands::Template(command) => run_template(command, ses_ops, ctx).await,
+        Commands::ConfigurationSet(command) => run_configuration_set(command, ctx).await,
+        Commands::Identity(command) => run_identity(command, ses_ops, ctx).await,
+        Commands::SendSimple(args) => send_simple(args, ses_ops, ctx).await,
+        Commands::SendBulkSimple(args) => send_bulk_simple(args, ses_ops, ctx).await,
+        Commands::SendTemplated(args) => send_templated(args, ses_ops, ctx).await,
+        Commands::SendRaw(args) => {
+            let envelope = args.envelope.envelope()?;
+            verified_recipients([&args.to].into_iter().chain(envelope.copies()), ctx).await?;
+            let files = args.parts.files();
+            send::raw(
+                ses_ops,
+                ctx,
+                &args.to,
+                &files,
+                &envelope,
+                args.from.as_deref(),
+            )
+            .await
+        }
+        Commands::SendBulkRaw(args) => {
+            let list = existing_contact_list(args.list.as_deref(), ses_ops, ctx).await?;
+            let envelope =
+                bulk_envelope(args.envelope, args.topic, args.filter, list, ses_ops, ctx).await?;
+            let files = args.parts.files();
+            send::bulk_raw(ses_ops, ctx, list, &files, &envelope, args.from.as_deref()).await
+        }
+        Commands::SendBulkTemplated(args) => send_bulk_templated(args, ses_ops, ctx).await,
+        Commands::Resume { journal } => send::resume(ses_ops, ctx, &journal).await,
+        Commands::Defaults => {
+            ctx.defaults.print();
+            Ok(())
+        }
+    }
+}
+
+async fn run_contact_list(
+    command: ContactListCommand,
+    ses_ops: &SesOps,
+    ctx: &Context,
+) -> Result<()> {
+    match command {
+        ContactListCommand::Create {
+            name,
+            description,
+            topics,
+        } => {
+            let topics = topics
+                .iter()
+                .map(|topic| parse_topic(topic))
+                .collect::<Result<Vec<_>>>()?;
+            contact_lists::create(ctx, &name, description.as_deref(), topics).await?;
+        }
+        ContactListCommand::Update {
+            name,
+            description,
+            topics,
+            removed_topics,
+        } => {
+            existing_contact_list(Some(&name), ses_ops, ctx).await?;
+            let update = ListUpdate {
+                description,
+                topics: topics
+                    .iter()
+                    .map(|topic| parse_topic(topic))
+                    .collect::<Result<Vec<_>>>()?,
+                removed_topics,
+            };
+            contact_lists::update(ctx, &name, update).await?;
+        }
+        ContactListCommand::Copy {
+            from,
+            to,
+            region,
+            delete_source,
+        } => {
+            let from = existing_contact_list(from.as_deref(), ses_ops, ctx).await?;
+            let copy = ListCopy {
+                from: from.unwrap_or(ses_ops.get_list_name()),
+                to: &to,
+                region: region.as_deref(),
+                delete_source,
+            };
+            contact_lists::copy(ctx, &copy).await?;
+        }
+        ContactListCommand::Describe { name } => {
+            let name = existing_contact_list(name.as_deref(), ses_ops, ctx).await?;
+            let name = name.unwrap_or(ses_ops.get_list_name());
+            contact_lists::describe(ctx, name).await?;
+        }
+        ContactListCommand::AddEmail {
+            email,
+            list,
+            verify,
+            preferences,
+            unsubscribe_all,
+        } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            let preferences = parse_preferences(&preferences.join(","))?;
+            contact_lists::add_contact(ctx, list, &email, &preferences, unsubscribe_all).await?;
+            if verify {
+                identities::create(ctx, &email).await?;
+            }
+        }
+        ContactListCommand::GetContact { email, list } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            contact_lists::show_contact(ctx, list, &email).await?;
+        }
+        ContactListCommand::UpdateContact {
+            email,
+            list,
+            attributes,
+            replace_attributes,
+            preferences,
+            unsubscribe_all,
+        } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            let update = ContactUpdate {
+                attributes: attributes
+                    .as_deref()
+                    .map(parse_attributes)
+                    .transpose()?
+                    .unwrap_or_default(),
+                replace_attributes,
+                preferences: parse_preferences(&preferences.join(","))?,
+                unsubscribe_all,
+            };
+            contact_lists::update_contact(ctx, list, &email, &update).await?;
+        }
+        ContactListCommand::Import {
+            file,
+            list,
+            concurrency,
+            retries,
+        } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            import_contacts(ctx, list, &file, concurrency, retries).await?;
+        }
+        ContactListCommand::Export {
+            list,
+            format,
+            output,
+        } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            export_contacts(ctx, list, format, output.as_deref()).await?;
+        }
+        ContactListCommand::Emails { list } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            contact_lists::write_emails_as_text_pdf(ses_ops, ctx, list).await?;
+        }
+        ContactListCommand::DeleteContact { email, list } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            contact_lists::delete_contact(ctx, list, &email).await?;
+        }
+        ContactListCommand::DeleteContacts { list, filter } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            let filter = filter.filter()?;
+            match filter.is_empty() {
+                true => contact_lists::delete_all_contacts(ctx, list).await?,
+                false => {
+                    let emails = matching_contacts(ctx, list, &filter).await?;
+                    contact_lists::delete_contacts(ctx, list, emails).await?;
+                }
+            }
+        }
+        ContactListCommand::Search {
+            list,
+            filter,
+            page,
+            page_size,
+            output,
+        } => {
+            let list = existing_contact_list(list.as_deref(), ses_ops, ctx).await?;
+            let list = list.unwrap_or(ses_ops.get_list_name());
+            let emails = matching_contacts(ctx, list, &filter.filter()?).await?;
+            print_page(&emails, page, page_size);
+            if let Some(path) = output {
+                write_emails(&path, &emails)?;
+            }
+        }
+        ContactListCommand::Delete { name } => {
+            existing_contact_list(Some(&name), ses_ops, ctx).await?;
+            contact_lists::delete(ctx, &name).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_template(command: TemplateCommand, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    match command {
+        TemplateCommand::Create(files) => {
+            if templates::exists(ctx, &files.name).await? {
+                return Err(SesClientError::validation(format!(
+                    "Template '{}' already exists",
+                    files.name
+                )));
+            }
+            let (subject, html, text) = read_template_files(&files)?;
+            templates::create(ctx, &files.name, &subject, &html, text).await?;
+        }
+        TemplateCommand::Update(files) => {
+            existing_template(&files.name, ctx).await?;
+            let (subject, html, text) = read_template_files(&files)?;
+            templates::update(ctx, &files.name, &subject, &html, text).await?;
+        }
+        TemplateCommand::Get { name } => {
+            existing_template(&name, ctx).await?;
+            templates::download(ctx, &name).await?;
+        }
+        TemplateCommand::Variables { name } => {
+            existing_template(&name, ctx).await?;
+            templates::print_variables(ctx, &name).await?;
+        }
+        TemplateCommand::Match { name, data } => {
+            let template_name = name.unwrap_or_else(|| ses_ops.get_template_name().to_string());
+            existing_template(&template_name, ctx).await?;
+            templates::match_data(ctx, &template_name, &data).await?;
+        }
+        TemplateCommand::Preview {
+            name,
+            dir,
+            data,
+            output,
+        } => {
+            let parts = match dir {
+                Some(dir) => template_parts_from_dir(&dir)?,
+                None => {
+                    let name = name.unwrap_or_else(|| ses_ops.get_template_name().to_string());
+                    existing_template(&name, ctx).await?;
+                    templates::parts(ctx, &name).await?
+                }
+            };
+            let data = send::parse_template_data(&read_file(&data, "Template Data")?)?;
+            preview(&parts, &data, &output)?;
+        }
+        TemplateCommand::Delete { name } => {
+            existing_template(&name, ctx).await?;
+            if ctx.skipped("DeleteEmailTemplate", &name) {
+                return Ok(());
+            }
+            templates::download(ctx, &name).await?;
+            templates::delete(ctx, &name).await?;
+            println!(
+                "The template has been downloaded to your current directory in case you need it"
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn run_configuration_set(command: ConfigurationSetCommand, ctx: &Context) -> Result<()> {
+    match command {
+        ConfigurationSetCommand::Create {
+            name,
+            reputation_metrics,
+            sending_disabled,
+            tls_policy,
+            tracking_domain,
+        } => {
+            let set = NewConfigurationSet {
+                name: &name,
+                reputation_metrics,
+                sending_enabled: !sending_disabled,
+                tls_policy,
+                tracking_domain: tracking_domain.as_deref(),
+            };
+            configuration_sets::create(ctx, &set).await
+        }
+        ConfigurationSetCommand::List => {
+            for name in configuration_sets::list(ctx).await? {
+                println!("    {}", name.green().bold());
+            }
+            Ok(())
+        }
+        ConfigurationSetCommand::Describe { name } => {
+            configuration_sets::describe(ctx, &name).await
+        }
+        ConfigurationSetCommand::Delete { name } => configuration_sets::delete(ctx, &name).await,
+        ConfigurationSetCommand::ReputationMetrics { name, enabled } => {
+            configuration_sets::set_reputation_metrics(ctx, &name, enabled).await
+        }
+        ConfigurationSetCommand::Sending { name, enabled } => {
+            configuration_sets::set_sending(ctx, &name, enabled).await
+        }
+        ConfigurationSetCommand::TlsPolicy { name, policy } => {
+            configuration_sets::set_tls_policy(ctx, &name, policy).await
+        }
+        ConfigurationSetCommand::TrackingDomain { name, domain } => {
+            configuration_sets::set_tracking_domain(ctx, &name, domain.as_deref()).await
+        }
+        ConfigurationSetCommand::AddEventDestination(args) => {
+            configuration_sets::add_event_destination(ctx, &args).await
+        }
+        ConfigurationSetCommand::DeleteEventDestination { set, name } => {
+            configuration_sets::delete_event_destination(ctx, &set, &name).await
+        }
+    }
+}
+
+async fn run_identity(command: IdentityCommand, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    match command {
+        IdentityCommand::Create { email } => identities::create(ctx, &email).await?,
+        IdentityCommand::Verify { email } => {
+            if !identities::identity_names(ctx).await?.contains(&email) {
+                return Err(SesClientError::validation(format!(
+                    "No identity was found for the email '{email}', run 'identity create' first"
+                )));
+            }
+            match identities::is_verified(ctx, &email).await? {
+                true => println!("The email address {} has been verified", email.green()),
+                false => println!("The email address {} is not verified", email.red()),
+            }
+        }
+        IdentityCommand::List => identities::write_identities_as_text_pdf(ses_ops, ctx).await?,
+        IdentityCommand::CreateDomain {
+            domain,
+            mail_from,
+            reject_on_mx_failure,
+            byodkim_selector,
+            byodkim_private_key,
+            byodkim_public_key,
+            zone_file,
+        } => {
+            let byodkim = byodkim_selector
+                .as_deref()
+                .zip(byodkim_private_key.as_deref())
+                .map(|(selector, private_key)| Byodkim {
+                    selector,
+                    private_key,
+                    public_key: byodkim_public_key.as_deref(),
+                });
+            let setup = DomainSetup {
+                domain: &domain,
+                byodkim,
+                mail_from: mail_from.as_deref(),
+                reject_on_mx_failure,
+            };
+            let records = domains::create_domain(ctx, &setup).await?;
+            if !records.is_empty() {
+                domains::print_records(&records);
+                domains::write_zone(&records, zone_file.as_deref())?;
+            }
+        }
+        IdentityCommand::MailFrom {
+            domain,
+            mail_from,
+            reject_on_mx_failure,
+        } => {
+            domains::set_mail_from(ctx, &domain, mail_from.as_deref(), reject_on_mx_failure).await?
+        }
+        IdentityCommand::DnsRecords { domain, zone_file } => {
+            let records = domains::domain_records(ctx, &domain).await?;
+            domains::write_zone(&records, zone_file.as_deref())?;
+        }
+        IdentityCommand::CheckDns { domain, resolver } => {
+            let resolver = resolver.as_deref().map(parse_resolver).transpose()?;
+            dns_check::check_domain(ctx, &domain, resolver).await?
+        }
+        IdentityCommand::Delete { identity, yes } => {
+            let question = format!(
+                "Are you sure you want to delete the identity '{identity}' and its policies?"
+            );
+            if confirmed(&question, yes)? {
+                identities::delete(ctx, &identity).await?
+            }
+        }
+        IdentityCommand::PutPolicy {
+            identity,
+            name,
+            policy,
+            yes,
+        } => {
+            let exists = identities::policies(ctx, &identity)
+                .await?
+                .contains_key(&name);
+            let question =
+                format!("The policy '{name}' already exists, would you like to replace it?");
+            if !exists || confirmed(&question, yes)? {
+                identities::put_policy(ctx, &identity, &name, &policy).await?
+            }
+        }
+        IdentityCommand::GetPolicies {
+            identity,
+            name,
+            output,
+        } => identities::show_policies(ctx, &identity, name.as_deref(), output.as_deref()).await?,
+        IdentityCommand::DeletePolicy {
+            identity,
+            name,
+            yes,
+        } => {
+            let question =
+                format!("Are you sure you want to delete the policy '{name}' of '{identity}'?");
+            if confirmed(&question, yes)? {
+                identities::delete_policy(ctx, &identity, &name).await?
+            }
+        }
+        IdentityCommand::Status {
+            sort,
+            descending,
+            csv,
+            resend_pending,
+            yes,
+        } => {
+            let mut statuses = identity_status::identity_statuses(ctx).await?;
+            identity_status::sort(&mut statuses, sort, descending);
+            identity_status::print_table(&statuses);
+            if let Some(path) = csv {
+                identity_status::write_csv(&path, &statuses)?;
+            }
+            let pending = statuses
+                .iter()
+                .filter(|status| status.is_pending_email())
+                .count();
+            let question = format!(
+                "Resending deletes and creates the {pending} pending identities again, which drops their policies and settings. Continue?"
+            );
+            if resend_pending && (pending == 0 || confirmed(&question, yes)?) {
+                identity_status::resend_verification(ctx, &statuses).await?;
+            }
+        }
+        IdentityCommand::FeedbackForwarding { identity, enabled } => {
+            identities::set_feedback_forwarding(ctx, &identity, enabled).await?
+        }
+    }
+    Ok(())
+}
+
+/// Asks before a destructive action unless `--yes` was given, declining is not an error.
+fn confirmed(question: &str, yes: bool) -> Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    let confirmed = Confirm::new(&format!("{question}\n"))
+        .with_default(false)
+        .prompt()?;
+    if !confirmed {
+        println!("{}\n", "Nothing has been changed".yellow().bold());
+    }
+    Ok(confirmed)
+}
+
+async fn send_simple(args: SendSimpleArgs, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    let envelope = args.envelope.envelope()?;
+    verified_recipients(args.to.iter().chain(envelope.copies()), ctx).await?;
+    let body = read_body(&args.body).await?;
+    send::simple(
+        ses_ops,
+        ctx,
+        &args.to,
+        &args.subject,
+        &body,
+        &envelope,
+        args.from.as_deref(),
+    )
+    .await
+}
+
+async fn send_bulk_simple(args: SendBulkSimpleArgs, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    let list = existing_contact_list(args.list.as_deref(), ses_ops, ctx).await?;
+    let envelope =
+        bulk_envelope(args.envelope, args.topic, args.filter, list, ses_ops, ctx).await?;
+    let body = read_body(&args.body).await?;
+    send::bulk_simple(
+        ses_ops,
+        ctx,
+        list,
+        &args.subject,
+        &body,
+        &envelope,
+        args.from.as_deref(),
+    )
+    .await
+}
+
+async fn send_templated(args: SendTemplatedArgs, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    let envelope = args.envelope.envelope()?;
+    verified_recipients(args.to.iter().chain(envelope.copies()), ctx).await?;
+    let template_data = args
+        .data
+        .as_deref()
+        .map(|path| read_file(path, "Template Data"))
+        .transpose()?;
+    let template_data = match (args.contact_list.as_deref(), &args.to[..]) {
+        (None, _) => template_data.unwrap_or_default(),
+        (Some(list), [email]) => {
+            existing_contact_list(Some(list), ses_ops, ctx).await?;
+            contact_template_data(ctx, list, email, template_data.as_deref()).await?
+        }
+        (Some(_), _) => {
+            return Err(SesClientError::validation(
+                "The attributes of a contact can only be the template data of a send to that single address",
+            ))
+        }
+    };
+    send::templated(
+        ses_ops,
+        ctx,
+        &args.to,
+        args.template.as_deref(),
+        &template_data,
+        &envelope,
+        args.from.as_deref(),
+    )
+    .await
+}
+
+async fn send_bulk_templated(
+    args: SendBulkTemplatedArgs,
+    ses_ops: &SesOps,
+    ctx: &Context,
+) -> Result<()> {
+    let list = existing_contact_list(args.list.as_deref(), ses_ops, ctx).await?;
+    let envelope =
+        bulk_envelope(args.envelope, args.topic, args.filter, list, ses_ops, ctx).await?;
+    let template_name = args
+        .template
+        .unwrap_or_else(|| ses_ops.get_template_name().to_string());
+    existing_template(&template_name, ctx).await?;
+    let bulk = send::BulkTemplated {
+        template_name: Some(&template_name),
+        list_name: list,
+        from_address: args.from.as_deref(),
+        data_file: args.data.as_deref(),
+        default_data: args.default_data.as_deref(),
+        envelope,
+    };
+    send::bulk_templated(ses_ops, ctx, &bulk).await
+}
+
+/// Resolves an optional contact list name, `None` falls back to the default list.
+async fn existing_contact_list<'a>(
+    list: Option<&'a str>,
+    ses_ops: &SesOps,
+    ctx: &Context,
+) -> Result<Option<&'a str>> {
+    let name = list.unwrap_or(ses_ops.get_list_name());
+    match contact_lists::exists(ctx, name).await? {
+        true => Ok(list),
+        false => Err(SesClientError::validation(format!(
+            "The provided Contact List Name '{name}' doesn't exist, available lists: {:?}",
+            contact_lists::list_names(ctx).await?
+        ))),
+    }
+}
+
+/// The envelope of a bulk send, scoped to the topic when the list has it and
+/// narrowed to the contacts the filter selects.
+async fn bulk_envelope(
+    args: EnvelopeArgs,
+    topic: Option<String>,
+    filter: ContactFilterArgs,
+    list: Option<&str>,
+    ses_ops: &SesOps,
+    ctx: &Context,
+) -> Result<Envelope> {
+    if let Some(topic) = &topic {
+        existing_topic(ctx, list.unwrap_or(ses_ops.get_list_name()), topic).await?;
+    }
+    Ok(Envelope {
+        topic,
+        selection: filter.filter()?,
+        ..args.envelope()?
+    })
+}
+
+async fn existing_template(name: &str, ctx: &Context) -> Result<()> {
+    match templates::exists(ctx, name).await? {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "The template named '{name}' doesn't exist, available templates: {:?}",
+            templates::list_names(ctx).await?
+        ))),
+    }
+}
+
+async fn verified_recipients<'a>(
+    emails: impl IntoIterator<Item = &'a String>,
+    ctx: &Context,
+) -> Result<()> {
+    match unreachable_recipients(ctx, emails).await?.first() {
+        None => Ok(()),
+        Some(email) => Err(SesClientError::validation(format!(
+            "The account is in the SES sandbox and the provided email '{email}' has not been verified, run 'identity create' first"
+        ))),
+    }
+}
+
+fn read_template_files(files: &TemplateFiles) -> Result<(String, String, Option<String>)> {
+    let subject = read_file(&files.subject, "Subject")?;
+    let html = read_file(&files.html, "Template")?;
+    let text = files
+        .text
+        .as_deref()
+        .map(|path| read_file(path, "Text Body"))
+        .transpose()?;
+    Ok((subject, html, text))
+}
+
+/// The body with the separate text part, if one is given.
+async fn read_body(body: &BodySource) -> Result<SimpleBody> {
+    let content = match (&body.origin.body_file, &body.origin.body_url) {
+        (Some(path), _) => read_file(path, "simple email")?,
+        (None, Some(url)) => fetch_body(url).await?,
+        (None, None) => {
+            return Err(SesClientError::validation(
+                "Either a body file or a body URL is required",
+            ))
+        }
+    };
+    let text = body
+        .text_file
+        .as_deref()
+        .map(|path| read_file(path, "Text Body"))
+        .transpose()?;
+    SimpleBody::new(content, text)
+}
@@ -0,0 +1,362 @@
+use crate::cli::CredentialSource;
+use crate::error::{Result, SesClientError};
+use aws_types::region::Region;
+use aws_types::SdkConfig;
+use colored::Colorize;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env::{remove_var, set_var, var};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Settings read from `~/.config/ses_client/config.toml`, every key is optional.
+///
+/// ```toml
+/// endpoint_url = "http://localhost:4566"
+/// default_profile = "dev"
+///
+/// [profiles.dev]
+/// region = "us-east-1"
+/// credentials = "env"
+/// endpoint_url = "http://localhost:4566"
+/// list_name = "subscribers"
+/// template_name = "newsletter"
+/// from_address = "news@example.com"
+/// configuration_set = "tracking"
+/// email_tags = "campaign=newsletter,team=growth"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    pub endpoint_url: Option<String>,
+    /// Profile used when neither `--profile` nor `SES_CLIENT_PROFILE` names one
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of defaults, selected with `--profile` or from the main menu.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub region: Option<String>,
+    pub credentials: Option<CredentialSource>,
+    pub endpoint_url: Option<String>,
+    pub list_name: Option<String>,
+    pub template_name: Option<String>,
+    pub from_address: Option<String>,
+    pub configuration_set: Option<String>,
+    /// Comma separated `name=value` message tags added to every send
+    pub email_tags: Option<String>,
+}
+
+impl FileConfig {
+    /// The profile named `name`, `None` falls back to `default_profile`.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<(String, Profile)>> {
+        let Some(name) = name.or(self.default_profile.as_deref()) else {
+            return Ok(None);
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(Some((name.to_string(), profile.clone()))),
+            None => Err(SesClientError::Config(format!(
+                "The profile '{name}' isn't in the configuration file, available profiles: {:?}",
+                self.profiles.keys().collect::<Vec<_>>()
+            ))),
+        }
+    }
+}
+
+/// Where a default value came from, shown on the 'Default Values' screen.
+#[derive(Debug, Clone)]
+pub enum Source {
+    Flag(&'static str),
+    Env(&'static str),
+    Profile(String),
+    File,
+    Unset,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Flag(flag) => write!(f, "the {flag} flag or its environment variable"),
+            Source::Env(name) => write!(f, "the '{name}' environment variable"),
+            Source::Profile(name) => write!(f, "the profile '{name}'"),
+            Source::File => write!(f, "the configuration file"),
+            Source::Unset => write!(f, "not set"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting {
+    pub value: Option<String>,
+    pub source: Source,
+}
+
+impl Setting {
+    fn unset() -> Self {
+        Setting {
+            value: None,
+            source: Source::Unset,
+        }
+    }
+
+    /// The first of the candidates that holds a non-empty value.
+    fn first(candidates: impl IntoIterator<Item = (Option<String>, Source)>) -> Self {
+        candidates
+            .into_iter()
+            .find_map(|(value, source)| {
+                value
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| Setting {
+                        value: Some(value),
+                        source,
+                    })
+            })
+            .unwrap_or_else(Setting::unset)
+    }
+}
+
+/// The environment variables the defaults are read from, captured before a profile
+/// writes its values to them so switching profiles never mistakes those for user set ones.
+pub struct EnvDefaults {
+    list_name: Option<String>,
+    template_name: Option<String>,
+    from_address: Option<String>,
+    configuration_set: Option<String>,
+    email_tags: Option<String>,
+}
+
+impl EnvDefaults {
+    pub fn capture() -> Self {
+        EnvDefaults {
+            list_name: var("LIST_NAME").ok(),
+            template_name: var("TEMPLATE_NAME").ok(),
+            from_address: var("FROM_ADDRESS").ok(),
+            configuration_set: var("CONFIGURATION_SET").ok(),
+            email_tags: var("EMAIL_TAGS").ok(),
+        }
+    }
+}
+
+/// The defaults every operation falls back to, along with their sources.
+///
+/// Environment variables take precedence over the selected profile.
+#[derive(Debug, Clone)]
+pub struct Defaults {
+    pub profile: Option<String>,
+    pub credentials: Option<CredentialSource>,
+    pub region: Setting,
+    pub endpoint_url: Setting,
+    pub list_name: Setting,
+    pub template_name: Setting,
+    pub from_address: Setting,
+    pub configuration_set: Setting,
+    pub email_tags: Setting,
+}
+
+impl Defaults {
+    pub fn resolve(
+        endpoint_flag: Option<String>,
+        env: &EnvDefaults,
+        file: &FileConfig,
+        profile: Option<(String, Profile)>,
+    ) -> Self {
+        let (name, profile) = match profile {
+            Some((name, profile)) => (Some(name), profile),
+            None => (None, Profile::default()),
+        };
+        let from_profile = || Source::Profile(name.clone().unwrap_or_default());
+        Defaults {
+            credentials: profile.credentials,
+            region: Setting::first([(profile.region, from_profile())]),
+            endpoint_url: Setting::first([
+                (endpoint_flag, Source::Flag("--endpoint-url")),
+                (profile.endpoint_url, from_profile()),
+                (file.endpoint_url.clone(), Source::File),
+            ]),
+            list_name: Setting::first([
+                (env.list_name.clone(), Source::Env("LIST_NAME")),
+                (profile.list_name, from_profile()),
+            ]),
+            template_name: Setting::first([
+                (env.template_name.clone(), Source::Env("TEMPLATE_NAME")),
+                (profile.template_name, from_profile()),
+            ]),
+            from_address: Setting::first([
+                (env.from_address.clone(), Source::Env("FROM_ADDRESS")),
+                (profile.from_address, from_profile()),
+            ]),
+            configuration_set: Setting::first([
+                (
+                    env.configuration_set.clone(),
+                    Source::Env("CONFIGURATION_SET"),
+                ),
+                (profile.configuration_set, from_profile()),
+            ]),
+            email_tags: Setting::first([
+                (env.email_tags.clone(), Source::Env("EMAIL_TAGS")),
+                (profile.email_tags, from_profile()),
+            ]),
+            profile: name,
+        }
+    }
+
+    /// `SesOps` reads its defaults from the environment, so the profile values are
+    /// written there before it is built.
+    pub fn apply_to_env(&self) {
+        for (name, setting) in [
+            ("LIST_NAME", &self.list_name),
+            ("TEMPLATE_NAME", &self.template_name),
+            ("FROM_ADDRESS", &self.from_address),
+            ("CONFIGURATION_SET", &self.configuration_set),
+        ] {
+            match (&setting.source, &setting.value) {
+                (Source::Profile(_), Some(value)) => set_var(name, value),
+                (Source::Unset, _) => remove_var(name),
+                _ => {}
+            }
+        }
+    }
+
+    /// Prints every default value along with where it came from.
+    pub fn print(&self) {
+        match &self.profile {
+            Some(profile) => println!("Profile: {}\n", profile.green().bold()),
+            None => println!("Profile: {}\n", "none".yellow().bold()),
+        }
+        for (label, setting) in [
+            ("Default Contact List Name", &self.list_name),
+            ("Default Template Name", &self.template_name),
+            ("Default from_address", &self.from_address),
+            ("Default Configuration Set", &self.configuration_set),
+            ("Default Email Tags", &self.email_tags),
+            ("Region", &self.region),
+            ("SES Endpoint URL", &self.endpoint_url),
+        ] {
+            let value = setting.value.as_deref().unwrap_or_default();
+            println!(
+                "{label}: {} ({})",
+                value.green().bold(),
+                setting.source.to_string().yellow()
+            );
+        }
+        println!();
+    }
+
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.value.as_deref()
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.value.as_deref()
+    }
+}
+
+/// The default location of the configuration file.
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ses_client").join("config.toml"))
+}
+
+/// Reads the configuration file, a missing file at the default location is not an error.
+pub fn load_file_config(path: Option<&str>) -> Result<FileConfig> {
+    let (path, explicit) = match path {
+        Some(path) => (PathBuf::from(path), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(FileConfig::default()),
+        },
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content).map_err(|err| {
+            SesClientError::Config(format!(
+                "Error while parsing the configuration file '{}': {err}",
+                path.display()
+            ))
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound && !explicit => {
+            Ok(FileConfig::default())
+        }
+        Err(err) => Err(SesClientError::Config(format!(
+            "Error opening the configuration file '{}': {err}",
+            path.display()
+        ))),
+    }
+}
+
+/// Points every SESv2 client built from the returned config at the endpoint URL of
+/// the defaults, e.g. a local SESv2 compatible stand-in, and at the region of the profile.
+pub fn with_overrides(sdk_config: SdkConfig, defaults: &Defaults) -> SdkConfig {
+    if defaults.endpoint_url().is_none() && defaults.region().is_none() {
+        return sdk_config;
+    }
+    let region = match defaults.region() {
+        Some(region) => Some(Region::new(region.to_string())),
+        None => sdk_config.region().cloned(),
+    };
+    let endpoint_url = defaults.endpoint_url().or(sdk_config.endpoint_url());
+    rebuild(&sdk_config, endpoint_url.map(String::from), region)
+}
+
+/// The same config in another region, e.g. to copy a contact list across regions.
+pub fn in_region(sdk_config: &SdkConfig, region: &str) -> SdkConfig {
+    rebuild(
+        sdk_config,
+        sdk_config.endpoint_url().map(String::from),
+        Some(Region::new(region.to_string())),
+    )
+}
+
+/// Copies every other setting of the config, aws-types 0.56 has no way to
+/// change a built config.
+fn rebuild(
+    sdk_config: &SdkConfig,
+    endpoint_url: Option<String>,
+    region: Option<Region>,
+) -> SdkConfig {
+    let mut builder = SdkConfig::builder();
+    builder
+        .set_credentials_provider(sdk_config.credentials_provider())
+        .set_credentials_cache(sdk_config.credentials_cache().cloned())
+        .set_retry_config(sdk_config.retry_config().cloned())
+        .set_timeout_config(sdk_config.timeout_config().cloned())
+        .set_sleep_impl(sdk_config.sleep_impl())
+        .set_time_source(sdk_config.time_source())
+        .set_app_name(sdk_config.app_name().cloned())
+        .set_http_connector(sdk_config.http_connector().cloned())
+        .set_use_fips(sdk_config.use_fips())
+        .set_use_dual_stack(sdk_config.use_dual_stack())
+        .set_endpoint_url(endpoint_url)
+        .set_region(region);
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_keep_the_endpoint_url_and_the_region() {
+        let sdk_config = SdkConfig::builder()
+            .region(Region::new("eu-west-1"))
+            .build();
+        let profile = Profile {
+            endpoint_url: Some("http://localhost:4566".into()),
+            ..Profile::default()
+        };
+        let defaults = Defaults::resolve(
+            None,
+            &EnvDefaults::capture(),
+            &FileConfig::default(),
+            Some(("local".into(), profile)),
+        );
+        let overridden = with_overrides(sdk_config, &defaults);
+        assert_eq!(overridden.endpoint_url(), Some("http://localhost:4566"));
+        assert_eq!(overridden.region(), Some(&Region::new("eu-west-1")));
+
+        let moved = in_region(&overridden, "us-east-1");
+        assert_eq!(moved.endpoint_url(), Some("http://localhost:4566"));
+        assert_eq!(moved.region(), Some(&Region::new("us-east-1")));
+    }
+}
@@ -0,0 +1,494 @@
+use crate::cli::{EventDestinationArgs, EventKind, EventTarget, TlsRequirement};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use aws_sdk_sesv2::primitives::DateTimeFormat;
+use aws_sdk_sesv2::types::{
+    CloudWatchDestination, CloudWatchDimensionConfiguration, DeliveryOptions, DimensionValueSource,
+    EventBridgeDestination, EventDestination, EventDestinationDefinition, EventType,
+    KinesisFirehoseDestination, ReputationOptions, SendingOptions, SnsDestination, TlsPolicy,
+    TrackingOptions,
+};
+use colored::Colorize;
+
+/// The options of a configuration set created by `create`.
+pub struct NewConfigurationSet<'a> {
+    pub name: &'a str,
+    pub reputation_metrics: bool,
+    pub sending_enabled: bool,
+    pub tls_policy: Option<TlsRequirement>,
+    pub tracking_domain: Option<&'a str>,
+}
+
+pub async fn create(ctx: &Context, set: &NewConfigurationSet<'_>) -> Result<()> {
+    if ctx.skipped("CreateConfigurationSet", set.name) {
+        return Ok(());
+    }
+    ctx.client
+        .create_configuration_set()
+        .configuration_set_name(set.name)
+        .reputation_options(
+            ReputationOptions::builder()
+                .reputation_metrics_enabled(set.reputation_metrics)
+                .build(),
+        )
+        .sending_options(
+            SendingOptions::builder()
+                .sending_enabled(set.sending_enabled)
+                .build(),
+        )
+        .set_delivery_options(set.tls_policy.map(|policy| {
+            DeliveryOptions::builder()
+                .tls_policy(tls_policy(policy))
+                .build()
+        }))
+        .set_tracking_options(set.tracking_domain.map(|domain| {
+            TrackingOptions::builder()
+                .custom_redirect_domain(domain)
+                .build()
+        }))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateConfigurationSet", err))?;
+    println!(
+        "The configuration set '{}' has been created\n",
+        set.name.green().bold()
+    );
+    Ok(())
+}
+
+/// The names of every configuration set, following the pagination of `ListConfigurationSets`.
+pub async fn list(ctx: &Context) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = ctx
+            .client
+            .list_configuration_sets()
+            .page_size(1000)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("ListConfigurationSets", err))?;
+        names.extend(
+            output
+                .configuration_sets()
+                .unwrap_or_default()
+                .iter()
+                .cloned(),
+        );
+        next_token = output.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+    Ok(names)
+}
+
+/// Prints the sending, reputation, delivery and tracking options of the set along
+/// with its event destinations.
+pub async fn describe(ctx: &Context, name: &str) -> Result<()> {
+    let output = ctx
+        .client
+        .get_configuration_set()
+        .configuration_set_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetConfigurationSet", err))?;
+    let enabled = |enabled: bool| match enabled {
+        true => "enabled".green().bold(),
+        false => "disabled".red().bold(),
+    };
+    println!("Configuration Set: {}", name.green().bold());
+    println!(
+        "    Sending: {}",
+        enabled(
+            output
+                .sending_options()
+                .map(|options| options.sending_enabled())
+                .unwrap_or(true)
+        )
+    );
+    let reputation = output.reputation_options();
+    println!(
+        "    Reputation Metrics: {}",
+        enabled(
+            reputation
+                .map(|options| options.reputation_metrics_enabled())
+                .unwrap_or_default()
+        )
+    );
+    if let Some(fresh_start) = reputation
+        .and_then(|options| options.last_fresh_start())
+        .and_then(|date| date.fmt(DateTimeFormat::DateTime).ok())
+    {
+        println!("    Last Fresh Start: {fresh_start}");
+    }
+    println!(
+        "    TLS Policy: {}",
+        output
+            .delivery_options()
+            .and_then(|options| options.tls_policy())
+            .map(|policy| policy.as_str())
+            .unwrap_or("OPTIONAL")
+            .green()
+            .bold()
+    );
+    println!(
+        "    Tracking Domain: {}",
+        output
+            .tracking_options()
+            .and_then(|options| options.custom_redirect_domain())
+            .unwrap_or("the SES domain")
+            .green()
+            .bold()
+    );
+
+    let destinations = ctx
+        .client
+        .get_configuration_set_event_destinations()
+        .configuration_set_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetConfigurationSetEventDestinations", err))?;
+    let destinations = destinations.event_destinations().unwrap_or_default();
+    match destinations.is_empty() {
+        true => println!("{}\n", "The set has no event destinations".yellow().bold()),
+        false => {
+            println!("Event Destinations:");
+            for destination in destinations {
+                print_event_destination(destination);
+            }
+            println!();
+        }
+    }
+    Ok(())
+}
+
+fn print_event_destination(destination: &EventDestination) {
+    let target = if let Some(sns) = destination.sns_destination() {
+        format!("SNS {}", sns.topic_arn().unwrap_or_default())
+    } else if let Some(firehose) = destination.kinesis_firehose_destination() {
+        format!(
+            "Firehose {}",
+            firehose.delivery_stream_arn().unwrap_or_default()
+        )
+    } else if let Some(event_bridge) = destination.event_bridge_destination() {
+        format!(
+            "EventBridge {}",
+            event_bridge.event_bus_arn().unwrap_or_default()
+        )
+    } else if let Some(cloud_watch) = destination.cloud_watch_destination() {
+        let dimensions = cloud_watch
+            .dimension_configurations()
+            .unwrap_or_default()
+            .iter()
+            .map(|dimension| {
+                format!(
+                    "{}:{}:{}",
+                    dimension.dimension_name().unwrap_or_default(),
+                    dimension
+                        .dimension_value_source()
+                        .map(|source| source.as_str())
+                        .unwrap_or_default(),
+                    dimension.default_dimension_value().unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("CloudWatch {}", dimensions.join(", "))
+    } else if destination.pinpoint_destination().is_some() {
+        "Pinpoint".to_string()
+    } else {
+        "unknown".to_string()
+    };
+    let events = destination
+        .matching_event_types()
+        .unwrap_or_default()
+        .iter()
+        .map(|event| event.as_str())
+        .collect::<Vec<_>>();
+    println!(
+        "    {} ({}) -> {target}\n        Events: {}",
+        destination.name().unwrap_or_default().green().bold(),
+        match destination.enabled() {
+            true => "enabled".green(),
+            false => "disabled".red(),
+        },
+        events.join(", ")
+    );
+}
+
+pub async fn delete(ctx: &Context, name: &str) -> Result<()> {
+    if ctx.skipped("DeleteConfigurationSet", name) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_configuration_set()
+        .configuration_set_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteConfigurationSet", err))?;
+    println!(
+        "The configuration set '{}' has been deleted\n",
+        name.green().bold()
+    );
+    Ok(())
+}
+
+pub async fn set_reputation_metrics(ctx: &Context, name: &str, enabled: bool) -> Result<()> {
+    if ctx.skipped("PutConfigurationSetReputationOptions", name) {
+        return Ok(());
+    }
+    ctx.client
+        .put_configuration_set_reputation_options()
+        .configuration_set_name(name)
+        .reputation_metrics_enabled(enabled)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutConfigurationSetReputationOptions", err))?;
+    println!(
+        "Reputation metrics are {} for '{}'\n",
+        on_off(enabled),
+        name.green().bold()
+    );
+    Ok(())
+}
+
+pub async fn set_sending(ctx: &Context, name: &str, enabled: bool) -> Result<()> {
+    if ctx.skipped("PutConfigurationSetSendingOptions", name) {
+        return Ok(());
+    }
+    ctx.client
+        .put_configuration_set_sending_options()
+        .configuration_set_name(name)
+        .sending_enabled(enabled)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutConfigurationSetSendingOptions", err))?;
+    println!(
+        "Sending is {} for '{}'\n",
+        on_off(enabled),
+        name.green().bold()
+    );
+    Ok(())
+}
+
+/// `PutConfigurationSetDeliveryOptions` replaces the dedicated IP pool as well, so
+/// the pool the set already uses is sent along with the new policy.
+pub async fn set_tls_policy(ctx: &Context, name: &str, policy: TlsRequirement) -> Result<()> {
+    if ctx.skipped("PutConfigurationSetDeliveryOptions", name) {
+        return Ok(());
+    }
+    let current = ctx
+        .client
+        .get_configuration_set()
+        .configuration_set_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetConfigurationSet", err))?;
+    let sending_pool = current
+        .delivery_options()
+        .and_then(|options| options.sending_pool_name())
+        .map(String::from);
+    ctx.client
+        .put_configuration_set_delivery_options()
+        .configuration_set_name(name)
+        .tls_policy(tls_policy(policy))
+        .set_sending_pool_name(sending_pool)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutConfigurationSetDeliveryOptions", err))?;
+    println!(
+        "The TLS policy of '{}' is {}\n",
+        name.green().bold(),
+        tls_policy(policy).as_str().green().bold()
+    );
+    Ok(())
+}
+
+/// `None` goes back to the tracking domain SES provides.
+pub async fn set_tracking_domain(ctx: &Context, name: &str, domain: Option<&str>) -> Result<()> {
+    if ctx.skipped("PutConfigurationSetTrackingOptions", name) {
+        return Ok(());
+    }
+    ctx.client
+        .put_configuration_set_tracking_options()
+        .configuration_set_name(name)
+        .set_custom_redirect_domain(domain.map(String::from))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutConfigurationSetTrackingOptions", err))?;
+    println!(
+        "The tracking domain of '{}' is {}\n",
+        name.green().bold(),
+        domain.unwrap_or("the SES domain").green().bold()
+    );
+    Ok(())
+}
+
+pub async fn add_event_destination(ctx: &Context, args: &EventDestinationArgs) -> Result<()> {
+    let mut definition = EventDestinationDefinition::builder()
+        .enabled(!args.disabled)
+        .set_matching_event_types(Some(args.events.iter().copied().map(event_type).collect()));
+    definition = match &args.target {
+        EventTarget::Sns { topic_arn } => {
+            definition.sns_destination(SnsDestination::builder().topic_arn(topic_arn).build())
+        }
+        EventTarget::CloudWatch { dimensions } => definition.cloud_watch_destination(
+            CloudWatchDestination::builder()
+                .set_dimension_configurations(Some(
+                    dimensions
+                        .iter()
+                        .map(|dimension| parse_dimension(dimension))
+                        .collect::<Result<Vec<_>>>()?,
+                ))
+                .build(),
+        ),
+        EventTarget::Firehose {
+            delivery_stream_arn,
+            iam_role_arn,
+        } => definition.kinesis_firehose_destination(
+            KinesisFirehoseDestination::builder()
+                .delivery_stream_arn(delivery_stream_arn)
+                .iam_role_arn(iam_role_arn)
+                .build(),
+        ),
+        EventTarget::EventBridge { event_bus_arn } => definition.event_bridge_destination(
+            EventBridgeDestination::builder()
+                .event_bus_arn(event_bus_arn)
+                .build(),
+        ),
+    };
+    if ctx.skipped(
+        "CreateConfigurationSetEventDestination",
+        &format!("{} of {}", args.name, args.set),
+    ) {
+        return Ok(());
+    }
+    ctx.client
+        .create_configuration_set_event_destination()
+        .configuration_set_name(&args.set)
+        .event_destination_name(&args.name)
+        .event_destination(definition.build())
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateConfigurationSetEventDestination", err))?;
+    println!(
+        "The event destination '{}' has been added to '{}'\n",
+        args.name.green().bold(),
+        args.set.green().bold()
+    );
+    Ok(())
+}
+
+pub async fn delete_event_destination(ctx: &Context, set: &str, name: &str) -> Result<()> {
+    if ctx.skipped(
+        "DeleteConfigurationSetEventDestination",
+        &format!("{name} of {set}"),
+    ) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_configuration_set_event_destination()
+        .configuration_set_name(set)
+        .event_destination_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteConfigurationSetEventDestination", err))?;
+    println!(
+        "The event destination '{}' has been deleted from '{}'\n",
+        name.green().bold(),
+        set.green().bold()
+    );
+    Ok(())
+}
+
+/// Parses a CloudWatch dimension written as `name:source:default`, the source is
+/// `message-tag`, `email-header` or `link-tag`.
+pub fn parse_dimension(dimension: &str) -> Result<CloudWatchDimensionConfiguration> {
+    let invalid = || {
+        SesClientError::validation(format!(
+            "The dimension '{dimension}' isn't 'name:source:default' with a source of message-tag, email-header or link-tag"
+        ))
+    };
+    let mut parts = dimension.splitn(3, ':').map(str::trim);
+    let (Some(name), Some(source), Some(default)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let source = match source.to_lowercase().as_str() {
+        "message-tag" => DimensionValueSource::MessageTag,
+        "email-header" => DimensionValueSource::EmailHeader,
+        "link-tag" => DimensionValueSource::LinkTag,
+        _ => return Err(invalid()),
+    };
+    if name.is_empty() || default.is_empty() {
+        return Err(invalid());
+    }
+    Ok(CloudWatchDimensionConfiguration::builder()
+        .dimension_name(name)
+        .dimension_value_source(source)
+        .default_dimension_value(default)
+        .build())
+}
+
+fn event_type(kind: EventKind) -> EventType {
+    match kind {
+        EventKind::Send => EventType::Send,
+        EventKind::Reject => EventType::Reject,
+        EventKind::Bounce => EventType::Bounce,
+        EventKind::Complaint => EventType::Complaint,
+        EventKind::Delivery => EventType::Delivery,
+        EventKind::Open => EventType::Open,
+        EventKind::Click => EventType::Click,
+        EventKind::RenderingFailure => EventType::RenderingFailure,
+        EventKind::DeliveryDelay => EventType::DeliveryDelay,
+        EventKind::Subscription => EventType::Subscription,
+    }
+}
+
+fn tls_policy(policy: TlsRequirement) -> TlsPolicy {
+    match policy {
+        TlsRequirement::Require => TlsPolicy::Require,
+        TlsRequirement::Optional => TlsPolicy::Optional,
+    }
+}
+
+fn on_off(enabled: bool) -> colored::ColoredString {
+    match enabled {
+        true => "on".green().bold(),
+        false => "off".red().bold(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dimensions() {
+        let dimension = parse_dimension(" campaign : Message-Tag : none ").unwrap();
+        assert_eq!(dimension.dimension_name(), Some("campaign"));
+        assert_eq!(
+            dimension.dimension_value_source(),
+            Some(&DimensionValueSource::MessageTag)
+        );
+        assert_eq!(dimension.default_dimension_value(), Some("none"));
+
+        // Only the first two colons separate, the default may hold more
+        let dimension = parse_dimension("link:link-tag:a:b").unwrap();
+        assert_eq!(dimension.default_dimension_value(), Some("a:b"));
+    }
+
+    #[test]
+    fn rejects_invalid_dimensions() {
+        for dimension in [
+            "campaign",
+            "campaign:message-tag",
+            "campaign:header:none",
+            ":email-header:none",
+            "campaign:email-header: ",
+        ] {
+            assert!(parse_dimension(dimension).is_err(), "{dimension}");
+        }
+    }
+}
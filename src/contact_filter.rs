@@ -0,0 +1,346 @@
+use crate::contacts::{get_contact_record, list_contacts};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::import::subscription_status;
+use aws_sdk_sesv2::types::{Contact, SubscriptionStatus, TopicPreference};
+use aws_sdk_sesv2::Client;
+use colored::Colorize;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Conditions a contact must meet to be selected, an empty filter selects every contact.
+///
+/// It is kept in the journal of a bulk send, so the regular expression is stored
+/// as text and compiled when the filter is applied.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ContactFilter {
+    /// Topic name to the `OPT_IN` or `OPT_OUT` status the contact has for it,
+    /// explicitly or through the topic default
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub topics: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unsubscribe_all: Option<bool>,
+    /// Attribute name to the value it must have, only these need a `GetContact` per contact
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    /// Domain of the email, subdomains don't match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// Regular expression the email must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+impl ContactFilter {
+    pub fn is_empty(&self) -> bool {
+        self.topics.is_empty()
+            && self.unsubscribe_all.is_none()
+            && self.attributes.is_empty()
+            && self.domain.is_none()
+            && self.pattern.is_none()
+    }
+
+    /// Checks the topic statuses and compiles the regular expression.
+    pub fn validate(&self) -> Result<()> {
+        self.topic_statuses()?;
+        self.regex()?;
+        Ok(())
+    }
+
+    /// The emails of the contacts that match, in the order they were listed.
+    pub async fn select(
+        &self,
+        client: &Client,
+        list_name: &str,
+        contacts: Vec<Contact>,
+    ) -> Result<Vec<String>> {
+        if self.is_empty() {
+            return Ok(contacts
+                .iter()
+                .filter_map(|contact| contact.email_address().map(String::from))
+                .collect());
+        }
+        let matches_listing = self.listing_matcher()?;
+        let emails = contacts
+            .iter()
+            .filter(|contact| matches_listing(contact))
+            .filter_map(|contact| contact.email_address())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        if self.attributes.is_empty() {
+            return Ok(emails);
+        }
+        let matches = stream::iter(emails)
+            .map(|email| async move {
+                let record = get_contact_record(client, list_name, &email).await?;
+                let matched = self.attributes.iter().all(|(name, expected)| {
+                    record
+                        .attributes
+                        .get(name)
+                        .is_some_and(|value| attribute_text(value) == *expected)
+                });
+                Ok::<_, SesClientError>(matched.then_some(email))
+            })
+            .buffered(10)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(matches.into_iter().flatten().collect())
+    }
+
+    /// Checks the conditions `ListContacts` returns the data for, every one but the attributes.
+    fn listing_matcher(&self) -> Result<impl Fn(&Contact) -> bool + '_> {
+        let topics = self.topic_statuses()?;
+        let regex = self.regex()?;
+        let domain = self.domain.as_deref().map(str::to_lowercase);
+        Ok(move |contact: &Contact| {
+            let email = contact.email_address().unwrap_or_default();
+            self.unsubscribe_all
+                .is_none_or(|unsubscribe_all| contact.unsubscribe_all() == unsubscribe_all)
+                && topics
+                    .iter()
+                    .all(|(topic, status)| topic_status(contact, topic) == Some(status))
+                && domain.as_deref().is_none_or(|domain| {
+                    email
+                        .rsplit_once('@')
+                        .is_some_and(|(_, email_domain)| email_domain.to_lowercase() == domain)
+                })
+                && regex.as_ref().is_none_or(|regex| regex.is_match(email))
+        })
+    }
+
+    fn topic_statuses(&self) -> Result<Vec<(&String, SubscriptionStatus)>> {
+        self.topics
+            .iter()
+            .map(|(topic, status)| match subscription_status(status) {
+                Some(status) => Ok((topic, status)),
+                None => Err(SesClientError::validation(format!(
+                    "The status '{status}' of the topic '{topic}' isn't OPT_IN or OPT_OUT"
+                ))),
+            })
+            .collect()
+    }
+
+    fn regex(&self) -> Result<Option<Regex>> {
+        self.pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    SesClientError::validation(format!(
+                        "The pattern '{pattern}' isn't a valid regular expression: {err}"
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// The preference of the contact for the topic, falling back to the topic default.
+fn topic_status<'a>(contact: &'a Contact, topic: &str) -> Option<&'a SubscriptionStatus> {
+    let find = |preferences: Option<&'a [TopicPreference]>| {
+        preferences
+            .unwrap_or_default()
+            .iter()
+            .find(|preference| preference.topic_name() == Some(topic))
+            .and_then(|preference| preference.subscription_status())
+    };
+    find(contact.topic_preferences()).or_else(|| find(contact.topic_default_preferences()))
+}
+
+/// Strings are compared without their quotes, any other value as its JSON text.
+fn attribute_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// The emails of the contacts of the list that match the filter.
+pub async fn matching_contacts(
+    ctx: &Context,
+    list_name: &str,
+    filter: &ContactFilter,
+) -> Result<Vec<String>> {
+    let contacts = list_contacts(&ctx.client, list_name, None).await?;
+    filter.select(&ctx.client, list_name, contacts).await
+}
+
+/// Writes the emails one per line, so they can be kept as a recipient selection.
+pub fn write_emails(path: &str, emails: &[String]) -> Result<()> {
+    let mut content = emails.join("\n");
+    content.push('\n');
+    std::fs::write(path, content).map_err(|source| SesClientError::Write {
+        path: path.into(),
+        source,
+    })?;
+    println!(
+        "{} emails have been written to '{}'\n",
+        emails.len(),
+        path.green().bold()
+    );
+    Ok(())
+}
+
+/// Parses comma separated `name=value` attribute conditions.
+pub fn parse_conditions(input: &str) -> Result<BTreeMap<String, String>> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|condition| !condition.is_empty())
+        .map(|condition| match condition.split_once('=') {
+            Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+            None => Err(SesClientError::validation(format!(
+                "The condition '{condition}' isn't a 'name=value' pair"
+            ))),
+        })
+        .collect()
+}
+
+/// Prints one page of the matching emails and returns its number, pages are
+/// numbered from 1 and a page past either end shows the nearest one.
+pub fn print_page(emails: &[String], page: usize, page_size: usize) -> usize {
+    let page_size = page_size.max(1);
+    let pages = emails.len().div_ceil(page_size).max(1);
+    let page = page.clamp(1, pages);
+    let start = (page - 1) * page_size;
+    let shown = &emails[start.min(emails.len())..(start + page_size).min(emails.len())];
+    for email in shown {
+        println!("{email}");
+    }
+    println!(
+        "\n{} of {} matching contacts, page {page} of {pages}\n",
+        shown.len().to_string().green().bold(),
+        emails.len().to_string().green().bold()
+    );
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn preference(topic: &str, status: SubscriptionStatus) -> TopicPreference {
+        TopicPreference::builder()
+            .topic_name(topic)
+            .subscription_status(status)
+            .build()
+    }
+
+    fn contact(email: &str) -> Contact {
+        Contact::builder()
+            .email_address(email)
+            .topic_default_preferences(preference("news", SubscriptionStatus::OptIn))
+            .topic_default_preferences(preference("offers", SubscriptionStatus::OptOut))
+            .build()
+    }
+
+    fn matches(filter: &ContactFilter, contact: &Contact) -> bool {
+        filter.listing_matcher().unwrap()(contact)
+    }
+
+    #[test]
+    fn matches_topics_through_preferences_and_defaults() {
+        let filter = ContactFilter {
+            topics: BTreeMap::from([("news".into(), "opt_in".into())]),
+            ..ContactFilter::default()
+        };
+        assert!(matches(&filter, &contact("a@example.com")));
+
+        let opted_out = Contact::builder()
+            .email_address("a@example.com")
+            .topic_preferences(preference("news", SubscriptionStatus::OptOut))
+            .topic_default_preferences(preference("news", SubscriptionStatus::OptIn))
+            .build();
+        assert!(!matches(&filter, &opted_out));
+
+        let offers = ContactFilter {
+            topics: BTreeMap::from([("offers".into(), "OPT_IN".into())]),
+            ..ContactFilter::default()
+        };
+        assert!(!matches(&offers, &contact("a@example.com")));
+    }
+
+    #[test]
+    fn matches_unsubscribed_contacts() {
+        let filter = ContactFilter {
+            unsubscribe_all: Some(true),
+            ..ContactFilter::default()
+        };
+        let unsubscribed = Contact::builder()
+            .email_address("a@example.com")
+            .unsubscribe_all(true)
+            .build();
+        assert!(matches(&filter, &unsubscribed));
+        assert!(!matches(&filter, &contact("a@example.com")));
+    }
+
+    #[test]
+    fn matches_the_domain_without_subdomains() {
+        let filter = ContactFilter {
+            domain: Some("Example.com".into()),
+            ..ContactFilter::default()
+        };
+        assert!(matches(&filter, &contact("a@EXAMPLE.com")));
+        assert!(!matches(&filter, &contact("a@mail.example.com")));
+        assert!(!matches(&filter, &contact("a@notexample.com")));
+    }
+
+    #[test]
+    fn matches_the_pattern_and_every_condition() {
+        let filter = ContactFilter {
+            domain: Some("example.com".into()),
+            pattern: Some("^sales[0-9]+@".into()),
+            ..ContactFilter::default()
+        };
+        assert!(matches(&filter, &contact("sales12@example.com")));
+        assert!(!matches(&filter, &contact("sales12@example.org")));
+        assert!(!matches(&filter, &contact("support@example.com")));
+    }
+
+    #[test]
+    fn rejects_invalid_statuses_and_patterns() {
+        let status = ContactFilter {
+            topics: BTreeMap::from([("news".into(), "subscribed".into())]),
+            ..ContactFilter::default()
+        };
+        assert!(status.validate().is_err());
+        let pattern = ContactFilter {
+            pattern: Some("(".into()),
+            ..ContactFilter::default()
+        };
+        assert!(pattern.validate().is_err());
+        assert!(ContactFilter::default().validate().is_ok());
+    }
+
+    #[test]
+    fn knows_when_it_is_empty() {
+        assert!(ContactFilter::default().is_empty());
+        let filter = ContactFilter {
+            attributes: parse_conditions("plan=pro").unwrap(),
+            ..ContactFilter::default()
+        };
+        assert!(!filter.is_empty());
+    }
+
+    #[test]
+    fn parses_attribute_conditions() {
+        assert_eq!(
+            parse_conditions(" plan = pro ,, seats=3").unwrap(),
+            BTreeMap::from([
+                ("plan".to_string(), "pro".to_string()),
+                ("seats".to_string(), "3".to_string())
+            ])
+        );
+        assert!(parse_conditions("plan").is_err());
+    }
+
+    #[test]
+    fn compares_attributes_as_text() {
+        assert_eq!(attribute_text(&json!("pro")), "pro");
+        assert_eq!(attribute_text(&json!(3)), "3");
+        assert_eq!(attribute_text(&json!(true)), "true");
+    }
+}
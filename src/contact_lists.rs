@@ -0,0 +1,693 @@
+use crate::contacts::{
+    get_contact_record, list_contact_emails, list_contact_records, ContactRecord,
+};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::import::{create_contacts, subscription_status, topic_preferences, ContactRow};
+use aws_apis::SesOps;
+use aws_sdk_sesv2::error::ProvideErrorMetadata;
+use aws_sdk_sesv2::types::{SubscriptionStatus, Topic};
+use aws_sdk_sesv2::Client;
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Parses `name:display name:OPT_IN|OPT_OUT[:description]`, the status is the
+/// preference of the contacts that didn't choose one for the topic.
+pub fn parse_topic(input: &str) -> Result<Topic> {
+    let mut parts = input.splitn(4, ':').map(str::trim);
+    let (Some(name), Some(display_name), Some(status)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(SesClientError::validation(format!(
+            "The topic '{input}' isn't a 'name:display name:OPT_IN|OPT_OUT[:description]' value"
+        )));
+    };
+    if name.is_empty() || display_name.is_empty() {
+        return Err(SesClientError::validation(format!(
+            "The topic '{input}' needs a name and a display name"
+        )));
+    }
+    let status = subscription_status(status).ok_or_else(|| {
+        SesClientError::validation(format!(
+            "The default preference '{status}' of the topic '{name}' isn't OPT_IN or OPT_OUT"
+        ))
+    })?;
+    Ok(Topic::builder()
+        .topic_name(name)
+        .display_name(display_name)
+        .set_description(
+            parts
+                .next()
+                .filter(|desc| !desc.is_empty())
+                .map(String::from),
+        )
+        .default_subscription_status(status)
+        .build())
+}
+
+/// Parses comma separated `topic=OPT_IN|OPT_OUT` contact preferences.
+pub fn parse_preferences(input: &str) -> Result<BTreeMap<String, String>> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|preference| !preference.is_empty())
+        .map(|preference| match preference.split_once('=') {
+            Some((topic, status)) => Ok((topic.trim().to_string(), status.trim().to_string())),
+            None => Err(SesClientError::validation(format!(
+                "The preference '{preference}' isn't a 'topic=OPT_IN|OPT_OUT' pair"
+            ))),
+        })
+        .collect()
+}
+
+/// The names of the contact lists in the region.
+pub async fn list_names(ctx: &Context) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = ctx
+            .client
+            .list_contact_lists()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("ListContactLists", err))?;
+        names.extend(
+            output
+                .contact_lists()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|list| list.contact_list_name().map(String::from)),
+        );
+        match output.next_token() {
+            Some(token) => next_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+    Ok(names)
+}
+
+/// Whether the contact list exists, any failure other than `NotFoundException` is an error.
+pub async fn exists(ctx: &Context, name: &str) -> Result<bool> {
+    match ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(name)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(err) if err.code() == Some("NotFoundException") => Ok(false),
+        Err(err) => Err(SesClientError::ses("GetContactList", err)),
+    }
+}
+
+/// Deletes the contact list along with every contact in it.
+pub async fn delete(ctx: &Context, name: &str) -> Result<()> {
+    if ctx.skipped("DeleteContactList", name) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_contact_list()
+        .contact_list_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteContactList", err))?;
+    println!(
+        "The contact list '{}' has been deleted\n",
+        name.green().bold()
+    );
+    Ok(())
+}
+
+pub async fn create(
+    ctx: &Context,
+    name: &str,
+    description: Option<&str>,
+    topics: Vec<Topic>,
+) -> Result<()> {
+    if ctx.skipped("CreateContactList", name) {
+        return Ok(());
+    }
+    let topic_count = topics.len();
+    ctx.client
+        .create_contact_list()
+        .contact_list_name(name)
+        .set_description(
+            description
+                .filter(|desc| !desc.is_empty())
+                .map(String::from),
+        )
+        .set_topics(Some(topics).filter(|topics| !topics.is_empty()))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateContactList", err))?;
+    println!(
+        "The contact list '{}' has been created with {topic_count} topic(s)\n",
+        name.green().bold()
+    );
+    Ok(())
+}
+
+/// The changes `update` makes to a contact list, everything else is kept.
+#[derive(Default)]
+pub struct ListUpdate {
+    /// Replaces the description, an empty one removes it
+    pub description: Option<String>,
+    /// Added to the topics, replacing the topic with the same name
+    pub topics: Vec<Topic>,
+    /// Names of the topics to remove
+    pub removed_topics: Vec<String>,
+}
+
+/// Changes the description and the topics of the list in place, keeping its contacts.
+pub async fn update(ctx: &Context, list_name: &str, update: ListUpdate) -> Result<()> {
+    if ctx.skipped("UpdateContactList", list_name) {
+        return Ok(());
+    }
+    let output = ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(list_name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetContactList", err))?;
+    let unknown = update
+        .removed_topics
+        .iter()
+        .filter(|removed| {
+            !output
+                .topics()
+                .unwrap_or_default()
+                .iter()
+                .any(|topic| topic.topic_name() == Some(removed.as_str()))
+        })
+        .collect::<Vec<_>>();
+    if !unknown.is_empty() {
+        return Err(SesClientError::validation(format!(
+            "The contact list '{list_name}' has no topics named {unknown:?}"
+        )));
+    }
+    let mut topics = output
+        .topics()
+        .unwrap_or_default()
+        .iter()
+        .filter(|topic| {
+            let name = topic.topic_name().unwrap_or_default();
+            !update.removed_topics.iter().any(|removed| removed == name)
+                && !update
+                    .topics
+                    .iter()
+                    .any(|new| new.topic_name() == Some(name))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    topics.extend(update.topics);
+    let description = match update.description {
+        Some(description) => Some(description).filter(|desc| !desc.is_empty()),
+        None => output.description().map(String::from),
+    };
+    ctx.client
+        .update_contact_list()
+        .contact_list_name(list_name)
+        .set_description(description)
+        .set_topics(Some(topics))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("UpdateContactList", err))?;
+    println!(
+        "The contact list '{}' has been updated\n",
+        list_name.green().bold()
+    );
+    Ok(())
+}
+
+/// Prints the description and the topics of a contact list.
+pub async fn describe(ctx: &Context, list_name: &str) -> Result<()> {
+    let output = ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(list_name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetContactList", err))?;
+    println!("Contact List: {}", list_name.green().bold());
+    if let Some(description) = output.description() {
+        println!("Description: {}", description.green().bold());
+    }
+    let topics = output.topics().unwrap_or_default();
+    if topics.is_empty() {
+        println!("{}\n", "The list has no topics".yellow().bold());
+        return Ok(());
+    }
+    for topic in topics {
+        println!(
+            "  Topic: {} ({})\n    Default Preference: {}",
+            topic.topic_name().unwrap_or_default().green().bold(),
+            topic.display_name().unwrap_or_default(),
+            topic
+                .default_subscription_status()
+                .map(SubscriptionStatus::as_str)
+                .unwrap_or_default()
+        );
+        if let Some(description) = topic.description() {
+            println!("    Description: {description}");
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Fails unless the contact list has the topic a bulk send is scoped to.
+pub async fn existing_topic(ctx: &Context, list_name: &str, topic: &str) -> Result<()> {
+    let topics = topic_names(ctx, list_name).await?;
+    match topics.iter().any(|name| name == topic) {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "The contact list '{list_name}' has no topic named '{topic}', available topics: {topics:?}"
+        ))),
+    }
+}
+
+/// The topic names of a contact list.
+pub async fn topic_names(ctx: &Context, list_name: &str) -> Result<Vec<String>> {
+    let output = ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(list_name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetContactList", err))?;
+    Ok(output
+        .topics()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|topic| topic.topic_name().map(String::from))
+        .collect())
+}
+
+/// Creates a contact with its own topic preferences, topics it has no preference
+/// for use their default.
+pub async fn add_contact(
+    ctx: &Context,
+    list_name: &str,
+    email: &str,
+    preferences: &BTreeMap<String, String>,
+    unsubscribe_all: bool,
+) -> Result<()> {
+    let preferences = topic_preferences(preferences).map_err(|err| {
+        SesClientError::validation(format!("The contact '{email}' can't be created, {err}"))
+    })?;
+    if ctx.skipped("CreateContact", email) {
+        return Ok(());
+    }
+    ctx.client
+        .create_contact()
+        .contact_list_name(list_name)
+        .email_address(email)
+        .set_topic_preferences(Some(preferences).filter(|preferences| !preferences.is_empty()))
+        .unsubscribe_all(unsubscribe_all)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateContact", err))?;
+    println!(
+        "The contact '{}' has been added to the list '{}'\n",
+        email.green().bold(),
+        list_name.green().bold()
+    );
+    Ok(())
+}
+
+/// Prints the attributes, topic preferences and unsubscribe-all flag of a contact.
+pub async fn show_contact(ctx: &Context, list_name: &str, email: &str) -> Result<()> {
+    let record = get_contact_record(&ctx.client, list_name, email).await?;
+    print_contact(&record);
+    Ok(())
+}
+
+fn print_contact(record: &ContactRecord) {
+    println!("Contact: {}", record.email.green().bold());
+    println!(
+        "Attributes: {}",
+        serde_json::to_string_pretty(&record.attributes).unwrap_or_default()
+    );
+    match record.topics.is_empty() {
+        true => println!("Topic Preferences: every topic uses its default"),
+        false => {
+            println!("Topic Preferences:");
+            for (topic, status) in &record.topics {
+                println!("  {topic}: {}", status.green().bold());
+            }
+        }
+    }
+    println!("Unsubscribed From Every Topic: {}", record.unsubscribe_all);
+    if let Some(last_updated) = &record.last_updated {
+        println!("Last Updated: {last_updated}");
+    }
+    println!();
+}
+
+/// The changes `update_contact` makes to a contact, everything else is kept.
+#[derive(Default)]
+pub struct ContactUpdate {
+    /// Added to the attributes, a `null` value removes the attribute
+    pub attributes: Map<String, Value>,
+    /// Replaces every attribute with `attributes` instead of adding them
+    pub replace_attributes: bool,
+    /// Topic name to `OPT_IN` or `OPT_OUT`, added to the current preferences
+    pub preferences: BTreeMap<String, String>,
+    pub unsubscribe_all: Option<bool>,
+}
+
+/// Parses the JSON object of attributes given to `update_contact`.
+pub fn parse_attributes(input: &str) -> Result<Map<String, Value>> {
+    match serde_json::from_str(input) {
+        Ok(Value::Object(attributes)) => Ok(attributes),
+        Ok(_) => Err(SesClientError::validation(
+            "The attributes must be a JSON object",
+        )),
+        Err(err) => Err(SesClientError::validation(format!(
+            "The attributes aren't valid JSON: {err}"
+        ))),
+    }
+}
+
+/// Applies the update to the current contact, `UpdateContact` replaces every
+/// field it is given so the unchanged ones are sent back as they are.
+pub async fn update_contact(
+    ctx: &Context,
+    list_name: &str,
+    email: &str,
+    update: &ContactUpdate,
+) -> Result<()> {
+    let mut record = get_contact_record(&ctx.client, list_name, email).await?;
+    if update.replace_attributes {
+        record.attributes.clear();
+    }
+    for (name, value) in &update.attributes {
+        match value {
+            Value::Null => record.attributes.remove(name),
+            value => record.attributes.insert(name.clone(), value.clone()),
+        };
+    }
+    record.topics.extend(update.preferences.clone());
+    if let Some(unsubscribe_all) = update.unsubscribe_all {
+        record.unsubscribe_all = unsubscribe_all;
+    }
+    let preferences = topic_preferences(&record.topics).map_err(|err| {
+        SesClientError::validation(format!("The contact '{email}' can't be updated, {err}"))
+    })?;
+    if ctx.skipped("UpdateContact", email) {
+        return Ok(());
+    }
+    ctx.client
+        .update_contact()
+        .contact_list_name(list_name)
+        .email_address(email)
+        .set_attributes_data(Some(Value::Object(record.attributes.clone()).to_string()))
+        .set_topic_preferences(Some(preferences))
+        .set_unsubscribe_all(Some(record.unsubscribe_all))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("UpdateContact", err))?;
+    println!("The contact has been updated\n");
+    record.last_updated = None;
+    print_contact(&record);
+    Ok(())
+}
+
+pub async fn delete_contact(ctx: &Context, list_name: &str, email: &str) -> Result<()> {
+    if ctx.skipped("DeleteContact", email) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_contact()
+        .contact_list_name(list_name)
+        .email_address(email)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteContact", err))?;
+    println!(
+        "The contact '{}' has been deleted from '{}'\n",
+        email.green().bold(),
+        list_name.green().bold()
+    );
+    Ok(())
+}
+
+/// Writes the emails of the list as text and PDF files with the aws_apis writer.
+///
+/// The writer doesn't report failures, so the emails are read through the client
+/// first and an unreadable list is returned as an error instead of leaving no files.
+pub async fn write_emails_as_text_pdf(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    list_name: &str,
+) -> Result<()> {
+    list_contact_emails(&ctx.client, list_name).await?;
+    ses_ops
+        .writing_email_addresses_from_provided_list_as_text_pdf(Some(list_name))
+        .await;
+    Ok(())
+}
+
+/// Deletes every contact of the list and keeps the list itself.
+pub async fn delete_all_contacts(ctx: &Context, list_name: &str) -> Result<()> {
+    let emails = list_contact_emails(&ctx.client, list_name).await?;
+    delete_contacts(ctx, list_name, emails).await
+}
+
+/// Deletes the contacts from the list, ten at a time, and prints how many were deleted.
+/// An error lists the addresses that couldn't be deleted.
+pub async fn delete_contacts(ctx: &Context, list_name: &str, emails: Vec<String>) -> Result<()> {
+    if ctx.skipped(
+        "DeleteContact",
+        &format!("{} matching contacts of '{list_name}'", emails.len()),
+    ) {
+        return Ok(());
+    }
+    let total = emails.len();
+    let failures = stream::iter(emails)
+        .map(|email| async move {
+            ctx.client
+                .delete_contact()
+                .contact_list_name(list_name)
+                .email_address(&email)
+                .send()
+                .await
+                .err()
+                .map(|err| (email, SesClientError::ses("DeleteContact", err).to_string()))
+        })
+        .buffered(10)
+        .filter_map(|failure| async move { failure })
+        .collect::<Vec<_>>()
+        .await;
+    println!(
+        "{} of {total} contacts have been deleted from '{}'",
+        (total - failures.len()).to_string().green().bold(),
+        list_name.green().bold()
+    );
+    for (email, err) in &failures {
+        println!("  {} {err}", email.red().bold());
+    }
+    println!();
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "{} of the {total} contacts couldn't be deleted: {}",
+            failures.len(),
+            failures
+                .iter()
+                .map(|(email, _)| email.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Where `copy` clones a contact list to.
+pub struct ListCopy<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    /// Region of the new list, `None` is the current region
+    pub region: Option<&'a str>,
+    /// Deletes the source list once its contacts are copied, which renames the
+    /// list or moves it to the other region
+    pub delete_source: bool,
+}
+
+/// Creates a list with the description and topics of the source list and copies
+/// every contact with its attributes, topic preferences and unsubscribe-all flag.
+///
+/// The source list is only deleted once every one of its contacts is in the new
+/// list, and is backed up to a JSON file in the format `contact-list import` reads
+/// first. SES allows a single contact list per region, so when it refuses the new
+/// list of a rename within the region, the source list has to be deleted before
+/// the new one is created. A failure after that names the backup to import.
+pub async fn copy(ctx: &Context, copy: &ListCopy<'_>) -> Result<()> {
+    if copy.region.is_none() && !copy.delete_source {
+        return Err(SesClientError::validation(
+            "SES allows a single contact list per region, copy it to another region or delete the source list",
+        ));
+    }
+    if copy.region.is_none() && copy.from == copy.to {
+        return Err(SesClientError::validation(
+            "The new list needs another name or another region",
+        ));
+    }
+    let source = ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(copy.from)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetContactList", err))?;
+    let records = list_contact_records(&ctx.client, copy.from, 10).await?;
+    println!(
+        "Read {} contacts of '{}'",
+        records.len().to_string().green().bold(),
+        copy.from.green().bold()
+    );
+    let backup = match copy.delete_source {
+        true => Some(write_backup(copy.from, &records)?),
+        false => None,
+    };
+    let total = records.len();
+    let rows = records
+        .into_iter()
+        .map(|record| ContactRow {
+            email: record.email,
+            attributes: record.attributes,
+            topics: record.topics,
+            unsubscribe_all: record.unsubscribe_all,
+        })
+        .collect::<Vec<_>>();
+
+    let client = ctx.client_in(copy.region);
+    let (description, topics) = (source.description(), source.topics());
+    if create_list(ctx, &client, copy, description, topics).await? {
+        fill_list(ctx, &client, copy.to, rows, total).await?;
+        if copy.delete_source {
+            delete_list(ctx, copy.from).await?;
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "SES allows a single contact list per region, '{}' is deleted to make room for '{}'",
+            copy.from, copy.to
+        )
+        .yellow()
+        .bold()
+    );
+    delete_list(ctx, copy.from).await?;
+    let renamed = async {
+        match create_list(ctx, &client, copy, description, topics).await? {
+            true => fill_list(ctx, &client, copy.to, rows, total).await,
+            false => Err(SesClientError::validation(format!(
+                "SES refused to create the contact list '{}'",
+                copy.to
+            ))),
+        }
+    };
+    renamed.await.map_err(|err| match &backup {
+        Some(path) => SesClientError::validation(format!(
+            "{err}\nThe contacts of '{}' are backed up in '{path}', restore them with 'ses_client contact-list import --file {path} --list {}'",
+            copy.from, copy.to
+        )),
+        None => err,
+    })
+}
+
+/// Creates the new list, `false` when SES refuses it because a rename within the
+/// region would exceed the single list it allows.
+async fn create_list(
+    ctx: &Context,
+    client: &Client,
+    copy: &ListCopy<'_>,
+    description: Option<&str>,
+    topics: Option<&[Topic]>,
+) -> Result<bool> {
+    if ctx.skipped("CreateContactList", copy.to) {
+        return Ok(true);
+    }
+    let created = client
+        .create_contact_list()
+        .contact_list_name(copy.to)
+        .set_description(description.map(String::from))
+        .set_topics(topics.map(<[_]>::to_vec))
+        .send()
+        .await;
+    match created {
+        Ok(_) => {}
+        Err(err) if copy.region.is_none() && err.code() == Some("LimitExceededException") => {
+            return Ok(false)
+        }
+        Err(err) => return Err(SesClientError::ses("CreateContactList", err)),
+    }
+    println!(
+        "The contact list '{}' has been created{}",
+        copy.to.green().bold(),
+        copy.region
+            .map(|region| format!(" in {region}"))
+            .unwrap_or_default()
+    );
+    Ok(true)
+}
+
+/// Creates the contacts in the new list and checks that none was skipped or failed.
+async fn fill_list(
+    ctx: &Context,
+    client: &Client,
+    list_name: &str,
+    rows: Vec<ContactRow>,
+    total: usize,
+) -> Result<()> {
+    let created = create_contacts(ctx, client, list_name, rows, 10, 3).await?;
+    match ctx.dry_run || created == total {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "Only {created} of the {total} contacts were copied to '{list_name}'"
+        ))),
+    }
+}
+
+fn write_backup(list_name: &str, records: &[ContactRecord]) -> Result<String> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let path = format!("{list_name}_backup_{millis}.json");
+    let write_error = |source| SesClientError::Write {
+        path: path.clone(),
+        source,
+    };
+    let content = serde_json::to_string_pretty(records).map_err(|err| write_error(err.into()))?;
+    std::fs::write(&path, content).map_err(write_error)?;
+    println!(
+        "The contacts have been backed up to '{}'",
+        path.green().bold()
+    );
+    Ok(path)
+}
+
+async fn delete_list(ctx: &Context, list_name: &str) -> Result<()> {
+    if ctx.skipped("DeleteContactList", list_name) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_contact_list()
+        .contact_list_name(list_name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteContactList", err))?;
+    println!(
+        "The contact list '{}' has been deleted",
+        list_name.green().bold()
+    );
+    Ok(())
+}
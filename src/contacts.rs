@@ -0,0 +1,146 @@
+use crate::bulk::with_retries;
+use crate::contact_filter::ContactFilter;
+use crate::error::{Result, SesClientError};
+use aws_sdk_sesv2::primitives::DateTimeFormat;
+use aws_sdk_sesv2::types::{Contact, ListContactsFilter, SubscriptionStatus, TopicFilter};
+use aws_sdk_sesv2::Client;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Every contact of the list `ListContacts` returns for the filter, following its pagination.
+pub async fn list_contacts(
+    client: &Client,
+    list_name: &str,
+    filter: Option<ListContactsFilter>,
+) -> Result<Vec<Contact>> {
+    let mut contacts = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = client
+            .list_contacts()
+            .contact_list_name(list_name)
+            .page_size(1000)
+            .set_filter(filter.clone())
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("ListContacts", err))?;
+        contacts.extend(output.contacts().unwrap_or_default().iter().cloned());
+        next_token = output.next_token().map(String::from);
+        if next_token.is_none() {
+            break;
+        }
+    }
+    Ok(contacts)
+}
+
+/// Every email address in the contact list.
+pub async fn list_contact_emails(client: &Client, list_name: &str) -> Result<Vec<String>> {
+    Ok(list_contacts(client, list_name, None)
+        .await?
+        .iter()
+        .filter_map(|contact| contact.email_address().map(String::from))
+        .collect())
+}
+
+/// The recipients of a bulk send, every contact of the list or, for a send scoped to
+/// a topic, the contacts subscribed to it, explicitly or through the topic's default.
+/// The `selection` narrows them further.
+pub async fn list_recipient_emails(
+    client: &Client,
+    list_name: &str,
+    topic: Option<&str>,
+    selection: &ContactFilter,
+) -> Result<Vec<String>> {
+    let filter = topic.map(|topic| {
+        ListContactsFilter::builder()
+            .filtered_status(SubscriptionStatus::OptIn)
+            .topic_filter(
+                TopicFilter::builder()
+                    .topic_name(topic)
+                    .use_default_if_preference_unavailable(true)
+                    .build(),
+            )
+            .build()
+    });
+    let contacts = list_contacts(client, list_name, filter)
+        .await?
+        .into_iter()
+        .filter(|contact| topic.is_none() || !contact.unsubscribe_all())
+        .collect();
+    selection.select(client, list_name, contacts).await
+}
+
+/// A contact with everything SES stores about it, the shape used by exports.
+#[derive(Serialize)]
+pub struct ContactRecord {
+    pub email: String,
+    pub attributes: Map<String, Value>,
+    /// Topic name to `OPT_IN` or `OPT_OUT`, only the explicit preferences of the contact
+    pub topics: BTreeMap<String, String>,
+    pub unsubscribe_all: bool,
+    /// RFC 3339 timestamp of the last change
+    pub last_updated: Option<String>,
+}
+
+/// Fetches a single contact, the attributes are only returned by `GetContact`.
+/// Throttled requests are retried with the bulk backoff.
+pub async fn get_contact_record(
+    client: &Client,
+    list_name: &str,
+    email: &str,
+) -> Result<ContactRecord> {
+    let output = with_retries(|| {
+        client
+            .get_contact()
+            .contact_list_name(list_name)
+            .email_address(email)
+            .send()
+    })
+    .await
+    .map_err(|err| SesClientError::ses("GetContact", err))?;
+    let attributes = match output.attributes_data() {
+        Some(data) if !data.trim().is_empty() => serde_json::from_str(data).map_err(|err| {
+            SesClientError::validation(format!(
+                "The attributes of the contact '{email}' aren't a JSON object: {err}"
+            ))
+        })?,
+        _ => Map::new(),
+    };
+    let topics = output
+        .topic_preferences()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|preference| {
+            Some((
+                preference.topic_name()?.to_string(),
+                preference.subscription_status()?.as_str().to_string(),
+            ))
+        })
+        .collect();
+    Ok(ContactRecord {
+        email: email.to_string(),
+        attributes,
+        topics,
+        unsubscribe_all: output.unsubscribe_all(),
+        last_updated: output
+            .last_updated_timestamp()
+            .and_then(|timestamp| timestamp.fmt(DateTimeFormat::DateTime).ok()),
+    })
+}
+
+/// Every contact of the list with its attributes, `concurrency` contacts are fetched at a time.
+pub async fn list_contact_records(
+    client: &Client,
+    list_name: &str,
+    concurrency: usize,
+) -> Result<Vec<ContactRecord>> {
+    let emails = list_contact_emails(client, list_name).await?;
+    stream::iter(emails)
+        .map(|email| async move { get_contact_record(client, list_name, &email).await })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
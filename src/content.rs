@@ -0,0 +1,42 @@
+use crate::error::{Result, SesClientError};
+use reqwest::get;
+
+/// Reads a whole file, `what` names the file in the error message, e.g. "Template".
+pub fn read_file(path: &str, what: &'static str) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|source| SesClientError::File {
+        what,
+        path: path.into(),
+        source,
+    })
+}
+
+pub fn write_file(path: &str, content: impl AsRef<[u8]>) -> Result<()> {
+    std::fs::write(path, content).map_err(|source| SesClientError::Write {
+        path: path.into(),
+        source,
+    })
+}
+
+/// Paths kept in a journal may be used from another directory, so they are stored absolute.
+pub fn absolute_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Downloads the email body from a publicly accessible link such as an S3 object URL.
+pub async fn fetch_body(url: &str) -> Result<String> {
+    let fetch_error = |source| SesClientError::Fetch {
+        url: url.into(),
+        source,
+    };
+    let body_data = get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(fetch_error)?
+        .text()
+        .await
+        .map_err(fetch_error)?;
+    let x: &[_] = &['\n', '\r', ' ', '\x1b', '\u{20}', '\u{7f}', '\u{80}'];
+    Ok(body_data.trim_matches(x).to_string())
+}
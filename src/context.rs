@@ -0,0 +1,60 @@
+use crate::cli::UnverifiedAction;
+use crate::config::{in_region, Defaults};
+use crate::dry_run;
+use aws_sdk_sesv2::Client;
+use aws_types::SdkConfig;
+
+/// State shared by every operation next to the `SesOps` of aws_apis, which only
+/// supplies the default names.
+pub struct Context {
+    /// SESv2 client every SES call goes through, built from the same config as `SesOps`
+    pub client: Client,
+    /// The config the client was built from, kept to build clients for other regions
+    sdk_config: SdkConfig,
+    /// Render and report sends and skip every mutating SES call
+    pub dry_run: bool,
+    /// The defaults of the selected profile and the environment
+    pub defaults: Defaults,
+    /// What a bulk send from a sandboxed account does with unverified recipients, `None` asks
+    pub unverified: Option<UnverifiedAction>,
+}
+
+impl Context {
+    pub fn new(sdk_config: &SdkConfig, dry_run: bool, defaults: Defaults) -> Self {
+        Context {
+            client: Client::new(sdk_config),
+            sdk_config: sdk_config.clone(),
+            dry_run,
+            defaults,
+            unverified: None,
+        }
+    }
+
+    /// Rebuilds the client after the credentials or the profile changed.
+    pub fn set_sdk_config(&mut self, sdk_config: &SdkConfig) {
+        self.client = Client::new(sdk_config);
+        self.sdk_config = sdk_config.clone();
+    }
+
+    /// A client for another region with the same credentials, `None` keeps the current one.
+    pub fn client_in(&self, region: Option<&str>) -> Client {
+        match region {
+            Some(region) => Client::new(&in_region(&self.sdk_config, region)),
+            None => self.client.clone(),
+        }
+    }
+
+    /// The region of the current config, if one is set.
+    pub fn region(&self) -> Option<String> {
+        self.sdk_config.region().map(|region| region.to_string())
+    }
+
+    /// In a dry run announces the mutating `operation` on `target` and returns true so the
+    /// caller can skip it.
+    pub fn skipped(&self, operation: &str, target: &str) -> bool {
+        if self.dry_run {
+            dry_run::skip(operation, target);
+        }
+        self.dry_run
+    }
+}
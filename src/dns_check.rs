@@ -0,0 +1,171 @@
+use crate::context::Context;
+use crate::domains::{domain_records, DnsRecord, RecordType};
+use crate::error::{Result, SesClientError};
+use crate::identities::is_verified;
+use colored::Colorize;
+use futures::future::join_all;
+use std::net::{IpAddr, SocketAddr};
+use trust_dns_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use trust_dns_resolver::error::ResolveErrorKind;
+use trust_dns_resolver::proto::rr::{RData, RecordType as QueryType};
+use trust_dns_resolver::TokioAsyncResolver;
+
+/// Whether a record SES expects is published, with what the resolver returned for its name.
+pub struct RecordCheck {
+    pub record: DnsRecord,
+    pub passed: bool,
+    /// The published values, or why none could be read
+    pub found: Vec<String>,
+}
+
+/// Parses the address of a DNS server, the port defaults to 53.
+pub fn parse_resolver(address: &str) -> Result<SocketAddr> {
+    address
+        .parse::<SocketAddr>()
+        .or_else(|_| address.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 53)))
+        .map_err(|_| {
+            SesClientError::validation(format!(
+                "'{address}' isn't a resolver address, use an IP with an optional port like 127.0.0.1:5353"
+            ))
+        })
+}
+
+/// A resolver asking the given server, or the one the system is configured with.
+fn resolver(address: Option<SocketAddr>) -> Result<TokioAsyncResolver> {
+    match address {
+        Some(address) => {
+            let servers =
+                NameServerConfigGroup::from_ips_clear(&[address.ip()], address.port(), true);
+            Ok(TokioAsyncResolver::tokio(
+                ResolverConfig::from_parts(None, Vec::new(), servers),
+                ResolverOpts::default(),
+            ))
+        }
+        None => TokioAsyncResolver::tokio_from_system_conf().map_err(|err| {
+            SesClientError::Config(format!(
+                "The system DNS configuration couldn't be read, give a resolver address instead: {err}"
+            ))
+        }),
+    }
+}
+
+/// Looks up every record of the domain identity and prints which ones are published,
+/// next to the verification status SES reports.
+pub async fn check_domain(
+    ctx: &Context,
+    domain: &str,
+    resolver_address: Option<SocketAddr>,
+) -> Result<()> {
+    let records = domain_records(ctx, domain).await?;
+    let resolver = resolver(resolver_address)?;
+    let checks = join_all(records.into_iter().map(|record| check(&resolver, record))).await;
+    let verified = is_verified(ctx, domain).await?;
+    print_checks(domain, verified, &checks);
+    Ok(())
+}
+
+async fn check(resolver: &TokioAsyncResolver, record: DnsRecord) -> RecordCheck {
+    let query_type = match record.record_type {
+        RecordType::Cname => QueryType::CNAME,
+        RecordType::Mx => QueryType::MX,
+        RecordType::Txt => QueryType::TXT,
+    };
+    let found = match resolver.lookup(record.name.as_str(), query_type).await {
+        Ok(lookup) => lookup.iter().filter_map(rdata_text).collect::<Vec<_>>(),
+        Err(err) => {
+            let reason = match err.kind() {
+                ResolveErrorKind::NoRecordsFound { .. } => "nothing published".to_string(),
+                _ => format!("lookup failed: {err}"),
+            };
+            return RecordCheck {
+                record,
+                passed: false,
+                found: vec![reason],
+            };
+        }
+    };
+    let passed = found.iter().any(|value| matches(&record, value));
+    RecordCheck {
+        record,
+        passed,
+        found,
+    }
+}
+
+/// The record data in the form `DnsRecord::value` uses.
+fn rdata_text(rdata: &RData) -> Option<String> {
+    match rdata {
+        RData::CNAME(name) => Some(name.0.to_string()),
+        RData::MX(mx) => Some(format!("{} {}", mx.preference(), mx.exchange())),
+        RData::TXT(txt) => Some(
+            txt.txt_data()
+                .iter()
+                .map(|part| String::from_utf8_lossy(part))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Host names are compared without case and trailing dot. The SPF and DMARC records
+/// only need to be present in a compatible form since they are often already
+/// customised, any MX preference will do.
+fn matches(record: &DnsRecord, found: &str) -> bool {
+    let host = |value: &str| value.trim_end_matches('.').to_lowercase();
+    match record.record_type {
+        RecordType::Cname => host(found) == host(&record.value),
+        RecordType::Mx => {
+            let exchange = |value: &str| value.split_whitespace().last().map(host);
+            exchange(found) == exchange(&record.value)
+        }
+        RecordType::Txt if record.value.starts_with("v=spf1") => {
+            found.starts_with("v=spf1") && found.contains("include:amazonses.com")
+        }
+        RecordType::Txt if record.value.starts_with("v=DMARC1") => found.starts_with("v=DMARC1"),
+        RecordType::Txt => {
+            let compact = |value: &str| value.split_whitespace().collect::<String>();
+            compact(found) == compact(&record.value)
+        }
+    }
+}
+
+fn print_checks(domain: &str, verified: bool, checks: &[RecordCheck]) {
+    let status = match verified {
+        true => "verified".green().bold(),
+        false => "not verified".red().bold(),
+    };
+    println!("SES verification status of '{}': {status}\n", domain.bold());
+    let name_width = checks
+        .iter()
+        .map(|check| check.record.name.len())
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{:<6} {:<5} {:<name_width$}  PURPOSE",
+        "RESULT", "TYPE", "NAME"
+    );
+    for check in checks {
+        let result = match check.passed {
+            true => format!("{:<6}", "PASS").green().bold(),
+            false => format!("{:<6}", "FAIL").red().bold(),
+        };
+        println!(
+            "{result} {:<5} {:<name_width$}  {}",
+            check.record.record_type.as_str(),
+            check.record.name,
+            check.record.purpose
+        );
+        if !check.passed {
+            println!("       expected: {}", check.record.value.yellow());
+            for found in &check.found {
+                println!("       found:    {found}");
+            }
+        }
+    }
+    let passed = checks.iter().filter(|check| check.passed).count();
+    println!(
+        "\n{} of {} records are published\n",
+        passed.to_string().green().bold(),
+        checks.len().to_string().green().bold()
+    );
+}
@@ -0,0 +1,348 @@
+use crate::content::{read_file, write_file};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use aws_sdk_sesv2::types::{
+    BehaviorOnMxFailure, DkimSigningAttributes, DkimSigningAttributesOrigin,
+};
+use colored::Colorize;
+
+/// TTL of the records in the zone-file snippet.
+const ZONE_TTL: u32 = 1800;
+
+/// The kinds of DNS records a domain identity needs.
+#[derive(Clone, Copy, PartialEq)]
+pub enum RecordType {
+    Cname,
+    Mx,
+    Txt,
+}
+
+impl RecordType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordType::Cname => "CNAME",
+            RecordType::Mx => "MX",
+            RecordType::Txt => "TXT",
+        }
+    }
+}
+
+/// A DNS record to publish for a domain identity, `value` is the record data
+/// without the quotes of a TXT record.
+#[derive(Clone)]
+pub struct DnsRecord {
+    pub name: String,
+    pub record_type: RecordType,
+    pub value: String,
+    /// What the record is for, shown next to it and as a comment in the zone file
+    pub purpose: &'static str,
+}
+
+/// A key pair for Bring Your Own DKIM, both files are PEM encoded.
+pub struct Byodkim<'a> {
+    pub selector: &'a str,
+    pub private_key: &'a str,
+    /// Only used to print the TXT record, SES never needs it
+    pub public_key: Option<&'a str>,
+}
+
+/// The options of a domain identity created by `create_domain`.
+pub struct DomainSetup<'a> {
+    pub domain: &'a str,
+    /// Easy DKIM is used without it
+    pub byodkim: Option<Byodkim<'a>>,
+    /// A subdomain of `domain` used as the MAIL FROM domain
+    pub mail_from: Option<&'a str>,
+    /// Reject messages while the MX record of the MAIL FROM domain is missing,
+    /// instead of falling back to amazonses.com
+    pub reject_on_mx_failure: bool,
+}
+
+/// Creates the domain identity and its custom MAIL FROM domain, and returns the
+/// DNS records to publish.
+pub async fn create_domain(ctx: &Context, setup: &DomainSetup<'_>) -> Result<Vec<DnsRecord>> {
+    check_domain(setup.domain)?;
+    if let Some(mail_from) = setup.mail_from {
+        check_mail_from(setup.domain, mail_from)?;
+    }
+    let signing_attributes = setup
+        .byodkim
+        .as_ref()
+        .map(|byodkim| {
+            Ok::<_, SesClientError>(
+                DkimSigningAttributes::builder()
+                    .domain_signing_selector(byodkim.selector)
+                    .domain_signing_private_key(pem_body(&read_file(
+                        byodkim.private_key,
+                        "DKIM Private Key",
+                    )?))
+                    .build(),
+            )
+        })
+        .transpose()?;
+    let public_key = setup
+        .byodkim
+        .as_ref()
+        .and_then(|byodkim| byodkim.public_key)
+        .map(|path| read_file(path, "DKIM Public Key").map(|key| pem_body(&key)))
+        .transpose()?;
+    if ctx.skipped("CreateEmailIdentity", setup.domain) {
+        return Ok(Vec::new());
+    }
+    let output = ctx
+        .client
+        .create_email_identity()
+        .email_identity(setup.domain)
+        .set_dkim_signing_attributes(signing_attributes)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateEmailIdentity", err))?;
+    println!(
+        "The domain identity '{}' has been created, publish its DNS records to verify it\n",
+        setup.domain.green().bold()
+    );
+    let mut records = match &setup.byodkim {
+        None => easy_dkim_records(
+            setup.domain,
+            output
+                .dkim_attributes()
+                .and_then(|dkim| dkim.tokens())
+                .unwrap_or_default(),
+        ),
+        Some(byodkim) => match public_key {
+            Some(public_key) => vec![DnsRecord {
+                name: format!("{}._domainkey.{}", byodkim.selector, setup.domain),
+                record_type: RecordType::Txt,
+                value: format!("v=DKIM1; k=rsa; p={public_key}"),
+                purpose: "DKIM public key",
+            }],
+            None => {
+                println!(
+                    "{}\n",
+                    format!(
+                        "Publish the public key of the pair as a TXT record of '{}._domainkey.{}'",
+                        byodkim.selector, setup.domain
+                    )
+                    .yellow()
+                    .bold()
+                );
+                Vec::new()
+            }
+        },
+    };
+    if let Some(mail_from) = setup.mail_from {
+        set_mail_from(
+            ctx,
+            setup.domain,
+            Some(mail_from),
+            setup.reject_on_mx_failure,
+        )
+        .await?;
+        records.extend(mail_from_records(mail_from, &region(ctx)?));
+    }
+    records.push(dmarc_record(setup.domain));
+    Ok(records)
+}
+
+/// Sets the custom MAIL FROM domain of the identity, `None` goes back to amazonses.com.
+pub async fn set_mail_from(
+    ctx: &Context,
+    domain: &str,
+    mail_from: Option<&str>,
+    reject_on_mx_failure: bool,
+) -> Result<()> {
+    if let Some(mail_from) = mail_from {
+        check_mail_from(domain, mail_from)?;
+    }
+    if ctx.skipped("PutEmailIdentityMailFromAttributes", domain) {
+        return Ok(());
+    }
+    let behavior = match reject_on_mx_failure {
+        true => BehaviorOnMxFailure::RejectMessage,
+        false => BehaviorOnMxFailure::UseDefaultValue,
+    };
+    ctx.client
+        .put_email_identity_mail_from_attributes()
+        .email_identity(domain)
+        .set_mail_from_domain(mail_from.map(String::from))
+        .behavior_on_mx_failure(behavior)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutEmailIdentityMailFromAttributes", err))?;
+    match mail_from {
+        Some(mail_from) => println!(
+            "The MAIL FROM domain of '{}' is now '{}'\n",
+            domain.green().bold(),
+            mail_from.green().bold()
+        ),
+        None => println!(
+            "The MAIL FROM domain of '{}' has been removed\n",
+            domain.green().bold()
+        ),
+    }
+    Ok(())
+}
+
+/// The records of an existing domain identity, as far as SES reports them.
+///
+/// SES doesn't return the selector of a BYODKIM key, so its TXT record is left out.
+pub async fn domain_records(ctx: &Context, domain: &str) -> Result<Vec<DnsRecord>> {
+    let output = ctx
+        .client
+        .get_email_identity()
+        .email_identity(domain)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetEmailIdentity", err))?;
+    let mut records = Vec::new();
+    if let Some(dkim) = output.dkim_attributes() {
+        match dkim.signing_attributes_origin() {
+            Some(DkimSigningAttributesOrigin::External) => println!(
+                "{}\n",
+                "The domain uses BYODKIM, its public key TXT record isn't reported by SES"
+                    .yellow()
+                    .bold()
+            ),
+            _ => records.extend(easy_dkim_records(domain, dkim.tokens().unwrap_or_default())),
+        }
+    }
+    if let Some(mail_from) = output
+        .mail_from_attributes()
+        .and_then(|attributes| attributes.mail_from_domain())
+    {
+        records.extend(mail_from_records(mail_from, &region(ctx)?));
+    }
+    records.push(dmarc_record(domain));
+    Ok(records)
+}
+
+fn easy_dkim_records(domain: &str, tokens: &[String]) -> Vec<DnsRecord> {
+    tokens
+        .iter()
+        .map(|token| DnsRecord {
+            name: format!("{token}._domainkey.{domain}"),
+            record_type: RecordType::Cname,
+            value: format!("{token}.dkim.amazonses.com"),
+            purpose: "Easy DKIM",
+        })
+        .collect()
+}
+
+fn mail_from_records(mail_from: &str, region: &str) -> Vec<DnsRecord> {
+    vec![
+        DnsRecord {
+            name: mail_from.to_string(),
+            record_type: RecordType::Mx,
+            value: format!("10 feedback-smtp.{region}.amazonses.com"),
+            purpose: "MAIL FROM bounces and complaints",
+        },
+        DnsRecord {
+            name: mail_from.to_string(),
+            record_type: RecordType::Txt,
+            value: "v=spf1 include:amazonses.com ~all".into(),
+            purpose: "MAIL FROM SPF",
+        },
+    ]
+}
+
+/// A monitoring-only DMARC policy, tighten `p=` once the reports look right.
+fn dmarc_record(domain: &str) -> DnsRecord {
+    DnsRecord {
+        name: format!("_dmarc.{domain}"),
+        record_type: RecordType::Txt,
+        value: "v=DMARC1; p=none;".into(),
+        purpose: "DMARC policy, recommended",
+    }
+}
+
+pub fn print_records(records: &[DnsRecord]) {
+    for record in records {
+        println!(
+            "{} {} {}\n    {}",
+            record.record_type.as_str().green().bold(),
+            record.name,
+            record.value.yellow(),
+            record.purpose
+        );
+    }
+    println!();
+}
+
+/// The records as zone-file lines with absolute names, ready to paste into a DNS provider.
+pub fn zone_snippet(records: &[DnsRecord]) -> String {
+    let mut zone = String::new();
+    for record in records {
+        let value = match record.record_type {
+            RecordType::Cname | RecordType::Mx => format!("{}.", record.value),
+            RecordType::Txt => quoted_txt(&record.value),
+        };
+        zone.push_str(&format!(
+            "; {}\n{}. {ZONE_TTL} IN {} {value}\n",
+            record.purpose,
+            record.name,
+            record.record_type.as_str()
+        ));
+    }
+    zone
+}
+
+/// Writes the zone-file snippet to `path`, or prints it when there is none.
+pub fn write_zone(records: &[DnsRecord], path: Option<&str>) -> Result<()> {
+    let zone = zone_snippet(records);
+    match path {
+        Some(path) => {
+            write_file(path, zone)?;
+            println!(
+                "The zone-file snippet has been written to '{}'\n",
+                path.green().bold()
+            );
+        }
+        None => println!("{zone}"),
+    }
+    Ok(())
+}
+
+/// TXT data is a list of quoted strings of at most 255 characters each.
+fn quoted_txt(value: &str) -> String {
+    value
+        .as_bytes()
+        .chunks(255)
+        .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The base64 body of a PEM file, SES takes BYODKIM keys without the armor lines.
+fn pem_body(pem: &str) -> String {
+    pem.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("-----"))
+        .collect()
+}
+
+fn check_domain(domain: &str) -> Result<()> {
+    match !domain.contains('@') && domain.contains('.') && !domain.starts_with('.') {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "'{domain}' isn't a domain, use 'identity create' for email addresses"
+        ))),
+    }
+}
+
+fn check_mail_from(domain: &str, mail_from: &str) -> Result<()> {
+    match mail_from.ends_with(&format!(".{domain}")) {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "The MAIL FROM domain '{mail_from}' must be a subdomain of '{domain}', e.g. 'mail.{domain}'"
+        ))),
+    }
+}
+
+/// The MX record of a MAIL FROM domain points at the feedback endpoint of the region.
+fn region(ctx: &Context) -> Result<String> {
+    ctx.region().ok_or_else(|| {
+        SesClientError::validation(
+            "The region is needed for the MAIL FROM MX record, set it in the profile or the environment",
+        )
+    })
+}
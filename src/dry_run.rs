@@ -0,0 +1,121 @@
+use crate::body::SimpleBody;
+use crate::content::write_file;
+use crate::envelope::Envelope;
+use crate::error::Result;
+use crate::mime::RawEmail;
+use crate::render::render;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A message exactly as it would have been sent, one per recipient.
+#[derive(Serialize)]
+pub struct RenderedEmail {
+    pub recipient: String,
+    pub from: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    pub subject: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    #[serde(flatten)]
+    pub envelope: Envelope,
+}
+
+impl RenderedEmail {
+    pub fn simple(recipient: &str, from: &str, subject: &str, body: &SimpleBody) -> Self {
+        RenderedEmail {
+            recipient: recipient.into(),
+            from: from.into(),
+            template: None,
+            subject: subject.into(),
+            html: body.html.clone(),
+            text: body.text.clone(),
+            attachments: Vec::new(),
+            envelope: Envelope::default(),
+        }
+    }
+
+    pub fn raw(recipient: &str, from: &str, email: &RawEmail) -> Self {
+        RenderedEmail {
+            recipient: recipient.into(),
+            from: from.into(),
+            template: None,
+            subject: email.subject.clone(),
+            html: email.html.clone(),
+            text: email.text.clone(),
+            attachments: email.file_names(),
+            envelope: Envelope::default(),
+        }
+    }
+
+    pub fn templated(
+        recipient: &str,
+        from: &str,
+        template_name: &str,
+        (subject, html, text): &(String, String, String),
+        data: &Value,
+    ) -> Self {
+        RenderedEmail {
+            recipient: recipient.into(),
+            from: from.into(),
+            template: Some(template_name.into()),
+            subject: render(subject, data),
+            html: Some(render(html, data)),
+            text: Some(render(text, data)).filter(|text| !text.is_empty()),
+            attachments: Vec::new(),
+            envelope: Envelope::default(),
+        }
+    }
+
+    /// Adds the CC, BCC and Reply-To addresses the message would have been sent with.
+    pub fn with_envelope(mut self, envelope: &Envelope) -> Self {
+        self.envelope = envelope.clone();
+        self
+    }
+}
+
+/// Writes one JSON line per recipient to `dry_run_<unix time in ms>.jsonl` in the
+/// current directory and prints who would have received which subject.
+pub fn report(emails: &[RenderedEmail]) -> Result<()> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let path = format!("dry_run_{millis}.jsonl");
+    let mut lines = String::new();
+    for email in emails {
+        lines
+            .push_str(&serde_json::to_string(email).expect("RenderedEmail is always serializable"));
+        lines.push('\n');
+    }
+    write_file(&path, lines)?;
+    for email in emails {
+        println!(
+            "    {} <- {}",
+            email.recipient.green().bold(),
+            email.subject
+        );
+    }
+    println!(
+        "{} {} message(s) rendered without sending, the full report is written to '{}'\n",
+        "Dry run:".yellow().bold(),
+        emails.len(),
+        path.green().bold()
+    );
+    Ok(())
+}
+
+/// Announces a mutating SES call that the dry run skipped.
+pub fn skip(operation: &str, target: &str) {
+    println!(
+        "{} the {operation} operation for '{}' is skipped\n",
+        "Dry run:".yellow().bold(),
+        target.green().bold()
+    );
+}
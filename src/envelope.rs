@@ -0,0 +1,266 @@
+use crate::config::Defaults;
+use crate::contact_filter::ContactFilter;
+use crate::error::{Result, SesClientError};
+use crate::import::is_valid_email;
+use aws_sdk_sesv2::types::{Destination, ListManagementOptions, MessageTag};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// SES accepts at most this many To, CC and BCC addresses together in one message.
+pub const MAX_RECIPIENTS: usize = 50;
+
+/// SES limits the name and the value of a message tag to this many characters.
+const MAX_TAG_LENGTH: usize = 256;
+
+/// Everything but the To addresses and the content added to every message of a
+/// send: the CC, BCC and Reply-To addresses, the configuration set and the message tags.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Envelope {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bcc: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reply_to: Vec<String>,
+    /// Routes the events of the messages to the event destinations of the set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub configuration_set: Option<String>,
+    /// Message tags, e.g. to segment the metrics of a campaign
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Scopes a bulk send to a topic of the contact list, contacts who opted out
+    /// of it or unsubscribed from every topic are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// Narrows a bulk send to the contacts of the list that match
+    #[serde(default, skip_serializing_if = "ContactFilter::is_empty")]
+    pub selection: ContactFilter,
+}
+
+impl Envelope {
+    /// Falls back to the default configuration set and adds the default tags,
+    /// a tag given for the send overrides the default tag with the same name.
+    pub fn with_defaults(mut self, defaults: &Defaults) -> Result<Self> {
+        if self.configuration_set.is_none() {
+            self.configuration_set = defaults.configuration_set.value.clone();
+        }
+        if let Some(default_tags) = &defaults.email_tags.value {
+            let mut tags = parse_tags(default_tags)?;
+            tags.append(&mut self.tags);
+            self.tags = tags;
+        }
+        Ok(self)
+    }
+
+    /// Checks the To addresses of a single message along with the envelope.
+    pub fn validate(&self, to: &[String]) -> Result<()> {
+        if to.is_empty() {
+            return Err(SesClientError::validation(
+                "At least one To address is required",
+            ));
+        }
+        check_addresses(to.iter())?;
+        if self.topic.is_some() || !self.selection.is_empty() {
+            return Err(SesClientError::validation(
+                "A topic or a contact filter only applies to bulk sends to a contact list",
+            ));
+        }
+        self.validate_copies(to.len())
+    }
+
+    /// Checks every address and that the CC and BCC addresses leave room for
+    /// `to_count` To addresses under the SES limit, a bulk send has one per message.
+    pub fn validate_copies(&self, to_count: usize) -> Result<()> {
+        check_addresses(self.cc.iter().chain(&self.bcc).chain(&self.reply_to))?;
+        check_tags(&self.tags)?;
+        self.selection.validate()?;
+        let recipients = to_count + self.cc.len() + self.bcc.len();
+        if recipients > MAX_RECIPIENTS {
+            return Err(SesClientError::validation(format!(
+                "A message can have at most {MAX_RECIPIENTS} To, CC and BCC addresses together, this one has {recipients}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The recipients of a bulk message, its To address and every CC and BCC address.
+    /// SES meters the send rate and the quota per recipient, not per message.
+    pub fn recipients_per_message(&self) -> usize {
+        1 + self.cc.len() + self.bcc.len()
+    }
+
+    /// The To, CC and BCC addresses of a message.
+    pub fn destination(&self, to: Vec<String>) -> Destination {
+        let addresses = |addresses: &Vec<String>| Some(addresses.clone()).filter(|a| !a.is_empty());
+        Destination::builder()
+            .set_to_addresses(Some(to))
+            .set_cc_addresses(addresses(&self.cc))
+            .set_bcc_addresses(addresses(&self.bcc))
+            .build()
+    }
+
+    pub fn reply_to_addresses(&self) -> Option<Vec<String>> {
+        Some(self.reply_to.clone()).filter(|addresses| !addresses.is_empty())
+    }
+
+    pub fn email_tags(&self) -> Option<Vec<MessageTag>> {
+        let tags = self
+            .tags
+            .iter()
+            .map(|(name, value)| MessageTag::builder().name(name).value(value).build())
+            .collect::<Vec<_>>();
+        Some(tags).filter(|tags| !tags.is_empty())
+    }
+
+    /// Lets SES honor the subscription preferences of the contact list for the topic.
+    pub fn list_management(&self, list_name: &str) -> Option<ListManagementOptions> {
+        self.topic.as_ref().map(|topic| {
+            ListManagementOptions::builder()
+                .contact_list_name(list_name)
+                .set_topic_name(Some(topic.clone()))
+                .build()
+        })
+    }
+
+    /// The CC and BCC addresses, which receive the message like the To addresses.
+    pub fn copies(&self) -> impl Iterator<Item = &String> {
+        self.cc.iter().chain(&self.bcc)
+    }
+}
+
+fn check_addresses<'a>(addresses: impl Iterator<Item = &'a String>) -> Result<()> {
+    let invalid = addresses
+        .filter(|address| !is_valid_email(address))
+        .map(|address| format!("'{address}'"))
+        .collect::<Vec<_>>();
+    match invalid.is_empty() {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "These addresses aren't valid: {}",
+            invalid.join(", ")
+        ))),
+    }
+}
+
+/// Tag names and values may only hold ASCII letters, numbers, underscores and dashes.
+fn check_tags(tags: &BTreeMap<String, String>) -> Result<()> {
+    let is_valid = |part: &str| {
+        !part.is_empty()
+            && part.len() <= MAX_TAG_LENGTH
+            && part
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-')
+    };
+    match tags
+        .iter()
+        .find(|(name, value)| !is_valid(name) || !is_valid(value))
+    {
+        None => Ok(()),
+        Some((name, value)) => Err(SesClientError::validation(format!(
+            "The tag '{name}={value}' isn't valid, names and values need 1 to {MAX_TAG_LENGTH} ASCII letters, numbers, underscores or dashes"
+        ))),
+    }
+}
+
+/// Parses comma separated `name=value` tags.
+pub fn parse_tags(input: &str) -> Result<BTreeMap<String, String>> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| match tag.split_once('=') {
+            Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+            None => Err(SesClientError::validation(format!(
+                "The tag '{tag}' isn't a 'name=value' pair"
+            ))),
+        })
+        .collect()
+}
+
+/// Splits a comma, semicolon or whitespace separated list of addresses.
+pub fn parse_addresses(input: &str) -> Vec<String> {
+    input
+        .split(|char: char| char == ',' || char == ';' || char.is_whitespace())
+        .filter(|address| !address.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|index| format!("user{index}@example.com"))
+            .collect()
+    }
+
+    #[test]
+    fn parses_tags() {
+        let tags = parse_tags(" campaign = spring-sale ,team=growth,, ").unwrap();
+        assert_eq!(
+            tags.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("campaign".to_string(), "spring-sale".to_string()),
+                ("team".to_string(), "growth".to_string())
+            ]
+        );
+        assert!(parse_tags("").unwrap().is_empty());
+        assert!(parse_tags("campaign").is_err());
+    }
+
+    #[test]
+    fn checks_tag_characters_and_length() {
+        let envelope = |tags: &str| Envelope {
+            tags: parse_tags(tags).unwrap(),
+            ..Envelope::default()
+        };
+        assert!(envelope("campaign=spring_sale-2")
+            .validate_copies(1)
+            .is_ok());
+        assert!(envelope("campaign=spring sale").validate_copies(1).is_err());
+        assert!(envelope("campaign=").validate_copies(1).is_err());
+        let long = format!("campaign={}", "a".repeat(MAX_TAG_LENGTH + 1));
+        assert!(envelope(&long).validate_copies(1).is_err());
+    }
+
+    #[test]
+    fn limits_the_recipients_of_a_message() {
+        let envelope = Envelope {
+            cc: addresses(20),
+            bcc: addresses(29),
+            ..Envelope::default()
+        };
+        assert!(envelope.validate(&addresses(1)).is_ok());
+        assert!(envelope.validate(&addresses(2)).is_err());
+        assert!(envelope.validate(&[]).is_err());
+        assert!(envelope.validate(&["not-an-address".into()]).is_err());
+        assert_eq!(envelope.recipients_per_message(), 50);
+        assert_eq!(Envelope::default().recipients_per_message(), 1);
+    }
+
+    #[test]
+    fn topics_only_apply_to_bulk_sends() {
+        let envelope = Envelope {
+            topic: Some("offers".into()),
+            ..Envelope::default()
+        };
+        assert!(envelope.validate(&addresses(1)).is_err());
+        assert!(envelope.validate_copies(1).is_ok());
+    }
+
+    #[test]
+    fn splits_addresses() {
+        assert_eq!(
+            parse_addresses("a@example.com, b@example.com;c@example.com\nd@example.com"),
+            vec![
+                "a@example.com",
+                "b@example.com",
+                "c@example.com",
+                "d@example.com"
+            ]
+        );
+        assert!(parse_addresses(" , ").is_empty());
+    }
+}
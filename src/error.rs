@@ -0,0 +1,90 @@
+use colored::Colorize;
+use inquire::InquireError;
+use thiserror::Error;
+
+pub type Result<T, E = SesClientError> = std::result::Result<T, E>;
+
+/// Everything that can go wrong while executing an operation.
+///
+/// None of these end the session, the interactive menu reports them and is
+/// shown again while the scripted commands exit with a non-zero status.
+#[derive(Debug, Error)]
+pub enum SesClientError {
+    #[error("Error opening the {what} file path '{path}' you specified: {source}")]
+    File {
+        what: &'static str,
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Error while writing the file '{path}': {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("The provided link '{url}' doesn't seem to be working: {source}")]
+    Fetch { url: String, source: reqwest::Error },
+    #[error("The {operation} operation failed: {message}")]
+    Ses {
+        operation: &'static str,
+        message: String,
+    },
+    #[error("{0}")]
+    Validation(String),
+    #[error("{0}")]
+    Config(String),
+    #[error("The operation was cancelled")]
+    Cancelled,
+    #[error("Error while reading the input: {0}")]
+    Prompt(InquireError),
+}
+
+impl SesClientError {
+    /// Wraps an SDK error, keeping the messages of the whole source chain since
+    /// the top level message of an `SdkError` alone is rarely helpful.
+    pub fn ses(operation: &'static str, err: impl std::error::Error) -> Self {
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        SesClientError::Ses { operation, message }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        SesClientError::Validation(message.into())
+    }
+
+    /// Prints the error in the style of the interactive menu.
+    pub fn report(&self) {
+        match self {
+            SesClientError::Cancelled => println!(
+                "{}\n",
+                "The operation was cancelled, returning to the menu"
+                    .yellow()
+                    .bold()
+            ),
+            _ => {
+                println!("{}", self.to_string().red().bold());
+                println!(
+                    "{}\n",
+                    "Take a look at the 'Common Errors' option if the reason isn't clear"
+                        .yellow()
+                        .bold()
+                );
+            }
+        }
+    }
+}
+
+impl From<InquireError> for SesClientError {
+    fn from(err: InquireError) -> Self {
+        match err {
+            InquireError::OperationCanceled | InquireError::OperationInterrupted => {
+                SesClientError::Cancelled
+            }
+            other => SesClientError::Prompt(other),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::cli::ExportFormat;
+use crate::contacts::{get_contact_record, list_contact_emails, ContactRecord};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Writes every contact of the list with its attributes, topic preferences,
+/// unsubscribe-all status and last-updated timestamp to `output`, `None` or `-`
+/// streams it to stdout so it can be piped into other tools.
+///
+/// Each contact is written as soon as it is fetched. A contact that can't be read
+/// even after the throttling retries is left out and reported at the end.
+pub async fn export_contacts(
+    ctx: &Context,
+    list_name: &str,
+    format: ExportFormat,
+    output: Option<&str>,
+) -> Result<()> {
+    let output = output.filter(|path| *path != "-");
+    // Progress goes to stderr so it never ends up in an export piped from stdout
+    eprintln!("Fetching the contacts of '{}'...", list_name.green().bold());
+    let topics = list_topics(ctx, list_name).await?;
+    let emails = list_contact_emails(&ctx.client, list_name).await?;
+    let write_error = |source: io::Error| SesClientError::Write {
+        path: output.unwrap_or("stdout").into(),
+        source,
+    };
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(write_error)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = RecordWriter::new(format, writer, topics).map_err(write_error)?;
+    let total = emails.len();
+    let mut records = stream::iter(emails)
+        .map(|email| async move {
+            let record = get_contact_record(&ctx.client, list_name, &email).await;
+            (email, record)
+        })
+        .buffered(10);
+    let mut failed = Vec::new();
+    while let Some((email, record)) = records.next().await {
+        match record {
+            Ok(record) => writer.write(&record).map_err(write_error)?,
+            Err(err) => failed.push((email, err)),
+        }
+    }
+    writer.finish().map_err(write_error)?;
+    let exported = total - failed.len();
+    match output {
+        Some(path) => eprintln!(
+            "{exported} contacts are exported to '{}'",
+            path.green().bold()
+        ),
+        None => eprintln!("{exported} contacts are exported"),
+    }
+    for (email, err) in &failed {
+        eprintln!("    {} '{email}': {err}", "Failed".red().bold());
+    }
+    eprintln!();
+    match failed.is_empty() {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "{} of the {total} contacts couldn't be exported",
+            failed.len()
+        ))),
+    }
+}
+
+/// The topic names of the list, one CSV column each.
+async fn list_topics(ctx: &Context, list_name: &str) -> Result<Vec<String>> {
+    let output = ctx
+        .client
+        .get_contact_list()
+        .contact_list_name(list_name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetContactList", err))?;
+    Ok(output
+        .topics()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|topic| topic.topic_name().map(String::from))
+        .collect())
+}
+
+/// Writes the records one at a time. The CSV columns are known before the first
+/// contact is read, so the attributes go in a single JSON `attributes` column.
+enum RecordWriter {
+    Csv {
+        writer: Box<csv::Writer<Box<dyn Write>>>,
+        topics: Vec<String>,
+    },
+    Json {
+        writer: Box<dyn Write>,
+        empty: bool,
+    },
+}
+
+impl RecordWriter {
+    fn new(
+        format: ExportFormat,
+        mut writer: Box<dyn Write>,
+        topics: Vec<String>,
+    ) -> io::Result<Self> {
+        match format {
+            ExportFormat::Csv => {
+                let mut writer = Box::new(csv::Writer::from_writer(writer));
+                let mut header = vec![
+                    "email".to_string(),
+                    "unsubscribe_all".to_string(),
+                    "last_updated".to_string(),
+                ];
+                header.extend(topics.iter().map(|topic| format!("topic:{topic}")));
+                header.push("attributes".into());
+                writer.write_record(&header)?;
+                Ok(RecordWriter::Csv { writer, topics })
+            }
+            ExportFormat::Json => {
+                write!(writer, "[")?;
+                Ok(RecordWriter::Json {
+                    writer,
+                    empty: true,
+                })
+            }
+        }
+    }
+
+    fn write(&mut self, record: &ContactRecord) -> io::Result<()> {
+        match self {
+            RecordWriter::Csv { writer, topics } => {
+                let mut row = vec![
+                    record.email.clone(),
+                    record.unsubscribe_all.to_string(),
+                    record.last_updated.clone().unwrap_or_default(),
+                ];
+                row.extend(
+                    topics
+                        .iter()
+                        .map(|topic| record.topics.get(topic).cloned().unwrap_or_default()),
+                );
+                row.push(match record.attributes.is_empty() {
+                    true => String::new(),
+                    false => Value::Object(record.attributes.clone()).to_string(),
+                });
+                writer.write_record(&row)?;
+            }
+            RecordWriter::Json { writer, empty } => {
+                let json = serde_json::to_string_pretty(record)?;
+                let separator = if *empty { "" } else { "," };
+                write!(writer, "{separator}\n  {}", json.replace('\n', "\n  "))?;
+                *empty = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            RecordWriter::Csv { mut writer, .. } => writer.flush(),
+            RecordWriter::Json { mut writer, empty } => {
+                match empty {
+                    true => writeln!(writer, "]")?,
+                    false => writeln!(writer, "\n]")?,
+                }
+                writer.flush()
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use crate::bulk::with_retries;
+use crate::content::{read_file, write_file};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use aws_apis::SesOps;
+use aws_sdk_sesv2::error::ProvideErrorMetadata;
+use colored::Colorize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Every identity of the account with whether sending from it is enabled.
+pub async fn list_identities(ctx: &Context) -> Result<Vec<(String, bool)>> {
+    let mut identities = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = ctx
+            .client
+            .list_email_identities()
+            .page_size(1000)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("ListEmailIdentities", err))?;
+        identities.extend(
+            output
+                .email_identities()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|identity| {
+                    let name = identity.identity_name()?.to_string();
+                    Some((name, identity.sending_enabled()))
+                }),
+        );
+        match output.next_token() {
+            Some(token) => next_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+    Ok(identities)
+}
+
+/// The names of every identity of the account.
+pub async fn identity_names(ctx: &Context) -> Result<Vec<String>> {
+    Ok(list_identities(ctx)
+        .await?
+        .into_iter()
+        .map(|(identity, _)| identity)
+        .collect())
+}
+
+/// Writes the details of every identity as text and PDF files with the aws_apis writer.
+///
+/// The writer doesn't report failures, so the identities are listed through the
+/// client first and a failing call is returned instead of leaving no files behind.
+pub async fn write_identities_as_text_pdf(ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    list_identities(ctx).await?;
+    ses_ops.writing_email_identies_details_as_text_pdf().await;
+    Ok(())
+}
+
+/// Whether the identity is verified for sending, an unknown identity isn't.
+/// Throttled requests are retried with the bulk backoff.
+pub async fn is_verified(ctx: &Context, identity: &str) -> Result<bool> {
+    match with_retries(|| {
+        ctx.client
+            .get_email_identity()
+            .email_identity(identity)
+            .send()
+    })
+    .await
+    {
+        Ok(output) => Ok(output.verified_for_sending_status()),
+        Err(err) if err.code() == Some("NotFoundException") => Ok(false),
+        Err(err) => Err(SesClientError::ses("GetEmailIdentity", err)),
+    }
+}
+
+/// Creates the email identity and says so when it already exists, in which case SES
+/// doesn't send the verification email again.
+pub async fn create(ctx: &Context, email: &str) -> Result<()> {
+    if !send_verification(ctx, email).await? {
+        println!(
+            "{}\n",
+            format!("The identity '{email}' already exists, no verification email was sent")
+                .yellow()
+                .bold()
+        );
+    }
+    Ok(())
+}
+
+/// Deletes an email address or domain identity, messages can't be sent from it afterwards.
+pub async fn delete(ctx: &Context, identity: &str) -> Result<()> {
+    if ctx.skipped("DeleteEmailIdentity", identity) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_email_identity()
+        .email_identity(identity)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteEmailIdentity", err))?;
+    println!(
+        "The identity '{}' has been deleted\n",
+        identity.green().bold()
+    );
+    Ok(())
+}
+
+/// Creates the email identity, which makes SES send the verification email.
+///
+/// SES only sends it on creation, so nothing is sent for an identity that already
+/// exists and `false` is returned, `recreate` sends it again.
+pub async fn send_verification(ctx: &Context, email: &str) -> Result<bool> {
+    if ctx.skipped("CreateEmailIdentity", email) {
+        return Ok(true);
+    }
+    match ctx
+        .client
+        .create_email_identity()
+        .email_identity(email)
+        .send()
+        .await
+    {
+        Ok(_) => {
+            println!("The verification email has been sent to {}", email.green());
+            Ok(true)
+        }
+        Err(err) if err.code() == Some("AlreadyExistsException") => Ok(false),
+        Err(err) => Err(SesClientError::ses("CreateEmailIdentity", err)),
+    }
+}
+
+/// Deletes the identity and creates it again so SES sends a new verification email.
+///
+/// The sending authorization policies, feedback forwarding, DKIM and MAIL FROM
+/// settings of the identity are lost, so only call it once the user has agreed.
+pub async fn recreate(ctx: &Context, identity: &str) -> Result<()> {
+    if ctx.dry_run {
+        ctx.skipped("DeleteEmailIdentity", identity);
+        ctx.skipped("CreateEmailIdentity", identity);
+        return Ok(());
+    }
+    ctx.client
+        .delete_email_identity()
+        .email_identity(identity)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteEmailIdentity", err))?;
+    ctx.client
+        .create_email_identity()
+        .email_identity(identity)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateEmailIdentity", err))?;
+    println!(
+        "The verification email has been sent to {}",
+        identity.green()
+    );
+    Ok(())
+}
+
+/// The sending authorization policies of the identity by name.
+pub async fn policies(ctx: &Context, identity: &str) -> Result<HashMap<String, String>> {
+    let output = ctx
+        .client
+        .get_email_identity_policies()
+        .email_identity(identity)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetEmailIdentityPolicies", err))?;
+    Ok(output.policies().cloned().unwrap_or_default())
+}
+
+/// Reads a policy document like `all_policy.json` and attaches it to the identity,
+/// replacing the policy of the same name if there is one.
+pub async fn put_policy(ctx: &Context, identity: &str, name: &str, path: &str) -> Result<()> {
+    let policy = read_file(path, "Policy")?;
+    serde_json::from_str::<Value>(&policy).map_err(|err| {
+        SesClientError::validation(format!("The policy in '{path}' isn't valid JSON: {err}"))
+    })?;
+    let exists = policies(ctx, identity).await?.contains_key(name);
+    let operation = match exists {
+        true => "UpdateEmailIdentityPolicy",
+        false => "CreateEmailIdentityPolicy",
+    };
+    if ctx.skipped(operation, &format!("{name} of {identity}")) {
+        return Ok(());
+    }
+    match exists {
+        true => ctx
+            .client
+            .update_email_identity_policy()
+            .email_identity(identity)
+            .policy_name(name)
+            .policy(policy)
+            .send()
+            .await
+            .map(|_| ()),
+        false => ctx
+            .client
+            .create_email_identity_policy()
+            .email_identity(identity)
+            .policy_name(name)
+            .policy(policy)
+            .send()
+            .await
+            .map(|_| ()),
+    }
+    .map_err(|err| SesClientError::ses(operation, err))?;
+    println!(
+        "The policy '{}' of '{}' has been {}\n",
+        name.green().bold(),
+        identity.green().bold(),
+        if exists { "replaced" } else { "created" }
+    );
+    Ok(())
+}
+
+/// Prints the policies of the identity, or only the named one, and writes what is
+/// printed to `output` if given.
+///
+/// A single policy is written as the document itself so it can be put back with
+/// `put_policy`, several are written as an object of documents by name.
+pub async fn show_policies(
+    ctx: &Context,
+    identity: &str,
+    name: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    let mut policies = policies(ctx, identity).await?;
+    if let Some(name) = name {
+        policies.retain(|policy_name, _| policy_name == name);
+        if policies.is_empty() {
+            return Err(SesClientError::validation(format!(
+                "The identity '{identity}' has no policy named '{name}'"
+            )));
+        }
+    }
+    if policies.is_empty() {
+        println!(
+            "{}\n",
+            format!("The identity '{identity}' has no policies")
+                .yellow()
+                .bold()
+        );
+        return Ok(());
+    }
+    let documents = policies
+        .iter()
+        .map(|(name, policy)| {
+            let document = serde_json::from_str::<Value>(policy)
+                .unwrap_or_else(|_| Value::String(policy.clone()));
+            (name.clone(), document)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    for (name, document) in &documents {
+        println!("{}", name.green().bold());
+        println!("{}\n", pretty(document));
+    }
+    if let Some(output) = output {
+        let content = match (name, documents.values().next()) {
+            (Some(_), Some(document)) => pretty(document),
+            _ => pretty(&Value::Object(documents)),
+        };
+        write_file(output, content)?;
+        println!(
+            "The policies have been written to '{}'\n",
+            output.green().bold()
+        );
+    }
+    Ok(())
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+pub async fn delete_policy(ctx: &Context, identity: &str, name: &str) -> Result<()> {
+    if ctx.skipped(
+        "DeleteEmailIdentityPolicy",
+        &format!("{name} of {identity}"),
+    ) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_email_identity_policy()
+        .email_identity(identity)
+        .policy_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteEmailIdentityPolicy", err))?;
+    println!(
+        "The policy '{}' of '{}' has been deleted\n",
+        name.green().bold(),
+        identity.green().bold()
+    );
+    Ok(())
+}
+
+/// Turns the forwarding of bounces and complaints to the sender on or off.
+///
+/// SES only allows turning it off once the identity publishes bounce and complaint
+/// notifications to SNS, otherwise the call fails.
+pub async fn set_feedback_forwarding(ctx: &Context, identity: &str, enabled: bool) -> Result<()> {
+    if ctx.skipped("PutEmailIdentityFeedbackAttributes", identity) {
+        return Ok(());
+    }
+    ctx.client
+        .put_email_identity_feedback_attributes()
+        .email_identity(identity)
+        .email_forwarding_enabled(enabled)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("PutEmailIdentityFeedbackAttributes", err))?;
+    println!(
+        "Feedback forwarding of '{}' is now {}\n",
+        identity.green().bold(),
+        if enabled { "on" } else { "off" }
+    );
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use crate::bulk::with_retries;
+use crate::cli::IdentityColumn;
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::identities::{list_identities, recreate};
+use aws_sdk_sesv2::types::{DkimStatus, IdentityType};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+/// The state of an identity as SES reports it, one row of the status table and the CSV export.
+#[derive(Serialize)]
+pub struct IdentityStatus {
+    pub identity: String,
+    /// `EMAIL_ADDRESS`, `DOMAIN` or `MANAGED_DOMAIN`
+    #[serde(rename = "type")]
+    pub identity_type: String,
+    /// `VERIFIED`, `PENDING`, or for domains whose DKIM check failed `FAILED` and
+    /// `TEMPORARY_FAILURE`
+    pub verification: String,
+    /// The DKIM status, `-` when SES reports none
+    pub dkim: String,
+    pub sending_enabled: bool,
+    /// The status of the custom MAIL FROM domain, `-` when there is none
+    pub mail_from: String,
+    /// Why the details couldn't be fetched, the other columns are `-` then
+    pub error: Option<String>,
+}
+
+impl IdentityStatus {
+    /// An email address that can't be sent from until its verification link is followed.
+    pub fn is_pending_email(&self) -> bool {
+        self.identity_type == IdentityType::EmailAddress.as_str() && self.verification != "VERIFIED"
+    }
+}
+
+/// Lists every identity of the account and fetches its details, 10 at a time.
+///
+/// Throttled requests are retried, an identity whose details still can't be fetched
+/// gets an `ERROR` row instead of failing the whole table.
+pub async fn identity_statuses(ctx: &Context) -> Result<Vec<IdentityStatus>> {
+    let identities = list_identities(ctx).await?;
+    Ok(stream::iter(identities)
+        .map(|(identity, sending_enabled)| async move {
+            identity_status(ctx, &identity, sending_enabled)
+                .await
+                .unwrap_or_else(|err| IdentityStatus {
+                    identity,
+                    identity_type: "-".into(),
+                    verification: "ERROR".into(),
+                    dkim: "-".into(),
+                    sending_enabled,
+                    mail_from: "-".into(),
+                    error: Some(err.to_string()),
+                })
+        })
+        .buffered(10)
+        .collect()
+        .await)
+}
+
+async fn identity_status(
+    ctx: &Context,
+    identity: &str,
+    sending_enabled: bool,
+) -> Result<IdentityStatus> {
+    let output = with_retries(|| {
+        ctx.client
+            .get_email_identity()
+            .email_identity(identity)
+            .send()
+    })
+    .await
+    .map_err(|err| SesClientError::ses("GetEmailIdentity", err))?;
+    let dkim_status = output.dkim_attributes().and_then(|dkim| dkim.status());
+    let identity_type = output.identity_type();
+    let verification = match (
+        output.verified_for_sending_status(),
+        identity_type,
+        dkim_status,
+    ) {
+        (true, _, _) => "VERIFIED",
+        (false, Some(IdentityType::EmailAddress), _) => "PENDING",
+        (false, _, Some(DkimStatus::Failed)) => "FAILED",
+        (false, _, Some(DkimStatus::TemporaryFailure)) => "TEMPORARY_FAILURE",
+        (false, _, _) => "PENDING",
+    };
+    Ok(IdentityStatus {
+        identity: identity.to_string(),
+        identity_type: identity_type
+            .map(|kind| kind.as_str().to_string())
+            .unwrap_or_else(|| "-".into()),
+        verification: verification.into(),
+        dkim: dkim_status
+            .map(|status| status.as_str().to_string())
+            .unwrap_or_else(|| "-".into()),
+        sending_enabled,
+        mail_from: output
+            .mail_from_attributes()
+            .and_then(|attributes| attributes.mail_from_domain_status())
+            .map(|status| status.as_str().to_string())
+            .unwrap_or_else(|| "-".into()),
+        error: None,
+    })
+}
+
+/// Sorts by the column, ties keep the identities in alphabetical order.
+pub fn sort(statuses: &mut [IdentityStatus], column: IdentityColumn, descending: bool) {
+    statuses.sort_by(|a, b| {
+        let ordering = match column {
+            IdentityColumn::Identity => a.identity.cmp(&b.identity),
+            IdentityColumn::Type => a.identity_type.cmp(&b.identity_type),
+            IdentityColumn::Verification => a.verification.cmp(&b.verification),
+            IdentityColumn::Dkim => a.dkim.cmp(&b.dkim),
+            IdentityColumn::Sending => a.sending_enabled.cmp(&b.sending_enabled),
+            IdentityColumn::MailFrom => a.mail_from.cmp(&b.mail_from),
+        };
+        let ordering = match descending {
+            true => ordering.reverse(),
+            false => ordering,
+        };
+        ordering.then_with(|| a.identity.cmp(&b.identity))
+    });
+}
+
+pub fn print_table(statuses: &[IdentityStatus]) {
+    if statuses.is_empty() {
+        println!(
+            "{}\n",
+            "There are no identities in the account".yellow().bold()
+        );
+        return;
+    }
+    let width = |header: &str, value: fn(&IdentityStatus) -> usize| {
+        statuses
+            .iter()
+            .map(value)
+            .max()
+            .unwrap_or_default()
+            .max(header.len())
+    };
+    let identity_width = width("IDENTITY", |status| status.identity.len());
+    let type_width = width("TYPE", |status| status.identity_type.len());
+    let verification_width = width("VERIFICATION", |status| status.verification.len());
+    let dkim_width = width("DKIM", |status| status.dkim.len());
+    println!(
+        "{:<identity_width$}  {:<type_width$}  {:<verification_width$}  {:<dkim_width$}  {:<7}  MAIL FROM",
+        "IDENTITY", "TYPE", "VERIFICATION", "DKIM", "SENDING"
+    );
+    for status in statuses {
+        let verification = format!("{:<verification_width$}", status.verification);
+        let verification = match status.verification.as_str() {
+            "VERIFIED" => verification.green(),
+            "PENDING" => verification.yellow(),
+            _ => verification.red(),
+        };
+        println!(
+            "{:<identity_width$}  {:<type_width$}  {verification}  {:<dkim_width$}  {:<7}  {}",
+            status.identity,
+            status.identity_type,
+            status.dkim,
+            if status.sending_enabled { "yes" } else { "no" },
+            status.mail_from
+        );
+        if let Some(error) = &status.error {
+            println!("    {}", error.red());
+        }
+    }
+    let verified = statuses
+        .iter()
+        .filter(|status| status.verification == "VERIFIED")
+        .count();
+    println!(
+        "\n{} of {} identities are verified\n",
+        verified.to_string().green().bold(),
+        statuses.len().to_string().green().bold()
+    );
+}
+
+pub fn write_csv(path: &str, statuses: &[IdentityStatus]) -> Result<()> {
+    let write_error = |source: io::Error| SesClientError::Write {
+        path: path.into(),
+        source,
+    };
+    let file = File::create(path).map_err(write_error)?;
+    let mut writer = csv::Writer::from_writer(BufWriter::new(file));
+    for status in statuses {
+        writer
+            .serialize(status)
+            .map_err(|err| write_error(err.into()))?;
+    }
+    writer.flush().map_err(write_error)?;
+    println!(
+        "{} identities are exported to '{}'\n",
+        statuses.len(),
+        path.green().bold()
+    );
+    Ok(())
+}
+
+/// Sends the verification email again to every pending email address by recreating
+/// its identity, which drops the policies and settings of the identity.
+///
+/// Every address is tried, the ones that failed are listed at the end.
+pub async fn resend_verification(ctx: &Context, statuses: &[IdentityStatus]) -> Result<()> {
+    let pending = statuses
+        .iter()
+        .filter(|status| status.is_pending_email())
+        .map(|status| status.identity.as_str())
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        println!(
+            "{}\n",
+            "There are no email addresses waiting for verification"
+                .yellow()
+                .bold()
+        );
+        return Ok(());
+    }
+    let mut failed = Vec::new();
+    for email in &pending {
+        if let Err(err) = recreate(ctx, email).await {
+            failed.push((*email, err));
+        }
+    }
+    println!();
+    for (email, err) in &failed {
+        println!("    {} '{email}': {err}", "Failed".red().bold());
+    }
+    match failed.is_empty() {
+        true => Ok(()),
+        false => Err(SesClientError::validation(format!(
+            "The verification email couldn't be sent again to {} of the {} pending addresses",
+            failed.len(),
+            pending.len()
+        ))),
+    }
+}
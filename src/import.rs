@@ -0,0 +1,452 @@
+use crate::bulk::is_retryable;
+use crate::content::read_file;
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use aws_sdk_sesv2::error::ProvideErrorMetadata;
+use aws_sdk_sesv2::types::{SubscriptionStatus, TopicPreference};
+use aws_sdk_sesv2::Client;
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+/// The longest wait between two attempts of a throttled request.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// One contact of an import file.
+///
+/// A JSON file is an array of these objects. In a CSV file the `email` and
+/// `unsubscribe_all` columns map to the fields of the same name, a
+/// `topic:<name>` column holds the `OPT_IN`/`OPT_OUT` preference for that
+/// topic and every other column becomes an attribute.
+#[derive(Deserialize)]
+pub struct ContactRow {
+    pub email: String,
+    #[serde(default)]
+    pub attributes: Map<String, Value>,
+    /// Topic name to `OPT_IN` or `OPT_OUT`
+    #[serde(default)]
+    pub topics: BTreeMap<String, String>,
+    #[serde(default)]
+    pub unsubscribe_all: bool,
+}
+
+type ScreenedRow = (usize, ContactRow, Vec<TopicPreference>);
+
+enum Outcome {
+    Created,
+    Skipped(String),
+    Failed(String),
+}
+
+/// Reads the contacts of a CSV or JSON file and creates them in the contact list,
+/// at most `concurrency` at a time, retrying throttled requests up to `retries` times.
+///
+/// Invalid and duplicate addresses are skipped, a summary of the created, skipped
+/// and failed rows is printed and an error is returned if any row failed.
+pub async fn import_contacts(
+    ctx: &Context,
+    list_name: &str,
+    path: &str,
+    concurrency: usize,
+    retries: u32,
+) -> Result<()> {
+    let rows = read_rows(path)?;
+    create_contacts(ctx, &ctx.client, list_name, rows, concurrency, retries)
+        .await
+        .map(|_| ())
+}
+
+/// Creates the contacts in the list with `client`, which may be in another region
+/// than the one of `ctx`, prints the same summary as an import and returns how
+/// many were created.
+pub async fn create_contacts(
+    ctx: &Context,
+    client: &Client,
+    list_name: &str,
+    rows: Vec<ContactRow>,
+    concurrency: usize,
+    retries: u32,
+) -> Result<usize> {
+    let total = rows.len();
+    let (contacts, mut skipped) = screen_rows(rows);
+
+    if ctx.skipped(
+        "CreateContact",
+        &format!("{} contacts of '{list_name}'", contacts.len()),
+    ) {
+        print_summary(total, 0, &skipped, &[]);
+        return Ok(0);
+    }
+
+    println!(
+        "Creating {} contacts in '{}'...\n",
+        contacts.len(),
+        list_name.green().bold()
+    );
+    let outcomes = stream::iter(contacts)
+        .map(|(line, row, topics)| async move {
+            let outcome = create_contact(client, list_name, &row, topics, retries).await;
+            (line, row.email, outcome)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut created = 0;
+    let mut failed = Vec::new();
+    for (line, email, outcome) in outcomes {
+        match outcome {
+            Outcome::Created => created += 1,
+            Outcome::Skipped(reason) => skipped.push((line, email, reason)),
+            Outcome::Failed(reason) => failed.push((line, email, reason)),
+        }
+    }
+    skipped.sort_by_key(|(line, _, _)| *line);
+    failed.sort_by_key(|(line, _, _)| *line);
+    print_summary(total, created, &skipped, &failed);
+    match failed.is_empty() {
+        true => Ok(created),
+        false => Err(SesClientError::validation(format!(
+            "{} of the {total} contacts couldn't be created",
+            failed.len()
+        ))),
+    }
+}
+
+/// Splits the rows into the contacts to create with their topic preferences and the
+/// skipped rows with the reason, both numbered by line.
+fn screen_rows(rows: Vec<ContactRow>) -> (Vec<ScreenedRow>, Vec<(usize, String, String)>) {
+    let mut skipped = Vec::new();
+    let mut seen = HashSet::new();
+    let mut contacts = Vec::new();
+    for (index, mut row) in rows.into_iter().enumerate() {
+        // Rows are numbered from 1 in the summary, the CSV header isn't counted
+        let line = index + 1;
+        row.email = row.email.trim().to_string();
+        if !is_valid_email(&row.email) {
+            skipped.push((line, row.email, "invalid email address".to_string()));
+        } else if !seen.insert(row.email.to_lowercase()) {
+            skipped.push((line, row.email, "duplicate of an earlier row".to_string()));
+        } else {
+            match topic_preferences(&row.topics) {
+                Ok(topics) => contacts.push((line, row, topics)),
+                Err(reason) => skipped.push((line, row.email, reason)),
+            }
+        }
+    }
+    (contacts, skipped)
+}
+
+/// A deliberately loose check, SES has the final say on what it accepts.
+pub fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Parses `OPT_IN`/`OPT_OUT` in any case into a subscription status.
+pub fn subscription_status(status: &str) -> Option<SubscriptionStatus> {
+    match status.trim().to_uppercase().as_str() {
+        "OPT_IN" => Some(SubscriptionStatus::OptIn),
+        "OPT_OUT" => Some(SubscriptionStatus::OptOut),
+        _ => None,
+    }
+}
+
+pub fn topic_preferences(
+    topics: &BTreeMap<String, String>,
+) -> std::result::Result<Vec<TopicPreference>, String> {
+    topics
+        .iter()
+        .map(|(topic, status)| match subscription_status(status) {
+            Some(status) => Ok(TopicPreference::builder()
+                .topic_name(topic)
+                .subscription_status(status)
+                .build()),
+            None => Err(format!(
+                "the preference '{status}' of the topic '{topic}' isn't OPT_IN or OPT_OUT"
+            )),
+        })
+        .collect()
+}
+
+fn read_rows(path: &str) -> Result<Vec<ContactRow>> {
+    parse_rows(path, &read_file(path, "Contacts")?)
+}
+
+/// Parses the content of the file at `path` by its extension.
+fn parse_rows(path: &str, content: &str) -> Result<Vec<ContactRow>> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("json") => serde_json::from_str(content).map_err(|err| {
+            SesClientError::validation(format!(
+                "The contacts file '{path}' isn't a JSON array of contacts: {err}"
+            ))
+        }),
+        Some("csv") => read_csv_rows(path, content),
+        _ => Err(SesClientError::validation(format!(
+            "The contacts file '{path}' must have a .csv or .json extension"
+        ))),
+    }
+}
+
+fn read_csv_rows(path: &str, content: &str) -> Result<Vec<ContactRow>> {
+    let csv_error = |err: csv::Error| {
+        SesClientError::validation(format!("The CSV file '{path}' couldn't be read: {err}"))
+    };
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    if !headers.iter().any(|header| header.trim() == "email") {
+        return Err(SesClientError::validation(format!(
+            "The CSV file '{path}' needs an 'email' column"
+        )));
+    }
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let mut row = ContactRow {
+            email: String::new(),
+            attributes: Map::new(),
+            topics: BTreeMap::new(),
+            unsubscribe_all: false,
+        };
+        for (header, value) in headers.iter().zip(record.iter()) {
+            let (header, value) = (header.trim(), value.trim());
+            if value.is_empty() {
+                continue;
+            }
+            match header {
+                "email" => row.email = value.to_string(),
+                "unsubscribe_all" => {
+                    row.unsubscribe_all =
+                        matches!(value.to_lowercase().as_str(), "true" | "yes" | "1")
+                }
+                _ => match header.strip_prefix("topic:") {
+                    Some(topic) => {
+                        row.topics.insert(topic.to_string(), value.to_string());
+                    }
+                    // The JSON object of attributes an export writes
+                    None if header == "attributes" && value.starts_with('{') => {
+                        let attributes = serde_json::from_str::<Map<String, Value>>(value)
+                            .map_err(|err| {
+                                SesClientError::validation(format!(
+                                    "The attributes of '{}' in '{path}' aren't a JSON object: {err}",
+                                    row.email
+                                ))
+                            })?;
+                        row.attributes.extend(attributes);
+                    }
+                    None => {
+                        row.attributes
+                            .insert(header.to_string(), Value::String(value.to_string()));
+                    }
+                },
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+async fn create_contact(
+    client: &Client,
+    list_name: &str,
+    row: &ContactRow,
+    topics: Vec<TopicPreference>,
+    retries: u32,
+) -> Outcome {
+    let attributes =
+        (!row.attributes.is_empty()).then(|| Value::Object(row.attributes.clone()).to_string());
+    let topics = (!topics.is_empty()).then_some(topics);
+    let mut attempt = 0;
+    loop {
+        let result = client
+            .create_contact()
+            .contact_list_name(list_name)
+            .email_address(&row.email)
+            .set_attributes_data(attributes.clone())
+            .set_topic_preferences(topics.clone())
+            .unsubscribe_all(row.unsubscribe_all)
+            .send()
+            .await;
+        match result {
+            Ok(_) => return Outcome::Created,
+            Err(err) if err.code() == Some("AlreadyExistsException") => {
+                return Outcome::Skipped("already in the contact list".into())
+            }
+            Err(err) if attempt < retries && is_retryable(&err) => {
+                tokio::time::sleep(retry_delay(attempt)).await;
+                attempt += 1;
+            }
+            Err(err) => {
+                return Outcome::Failed(SesClientError::ses("CreateContact", err).to_string())
+            }
+        }
+    }
+}
+
+/// Doubles from 200 ms on every attempt, up to `MAX_RETRY_DELAY`.
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis(200u64.saturating_mul(2u64.saturating_pow(attempt))).min(MAX_RETRY_DELAY)
+}
+
+fn print_summary(
+    total: usize,
+    created: usize,
+    skipped: &[(usize, String, String)],
+    failed: &[(usize, String, String)],
+) {
+    for (line, email, reason) in skipped {
+        println!(
+            "    {} row {line} '{email}': {reason}",
+            "Skipped".yellow().bold()
+        );
+    }
+    for (line, email, reason) in failed {
+        println!(
+            "    {} row {line} '{email}': {reason}",
+            "Failed".red().bold()
+        );
+    }
+    println!(
+        "{total} rows read: {} created, {} skipped, {} failed\n",
+        created.to_string().green().bold(),
+        skipped.len().to_string().yellow().bold(),
+        failed.len().to_string().red().bold()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_maximum() {
+        assert_eq!(retry_delay(0), Duration::from_millis(200));
+        assert_eq!(retry_delay(3), Duration::from_millis(1600));
+        assert_eq!(retry_delay(10), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    fn row(email: &str) -> ContactRow {
+        ContactRow {
+            email: email.into(),
+            attributes: Map::new(),
+            topics: BTreeMap::new(),
+            unsubscribe_all: false,
+        }
+    }
+
+    #[test]
+    fn maps_the_csv_columns() {
+        let csv = "email,name,topic:news,unsubscribe_all\n\
+                   ada@example.com , Ada ,opt_in,yes\n\
+                   bob@example.com,,,\n";
+        let rows = parse_rows("contacts.CSV", csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].email, "ada@example.com");
+        assert_eq!(rows[0].attributes["name"], "Ada");
+        assert_eq!(rows[0].topics["news"], "opt_in");
+        assert!(rows[0].unsubscribe_all);
+        // Empty cells are left out
+        assert_eq!(rows[1].email, "bob@example.com");
+        assert!(rows[1].attributes.is_empty());
+        assert!(rows[1].topics.is_empty());
+        assert!(!rows[1].unsubscribe_all);
+    }
+
+    #[test]
+    fn merges_the_exported_attributes_column() {
+        let csv = "email,attributes,city\n\
+                   ada@example.com,\"{\"\"plan\"\": \"\"pro\"\"}\",London\n";
+        let rows = parse_rows("contacts.csv", csv).unwrap();
+        assert_eq!(rows[0].attributes["plan"], "pro");
+        assert_eq!(rows[0].attributes["city"], "London");
+    }
+
+    #[test]
+    fn rejects_bad_csv_files() {
+        assert!(parse_rows("contacts.csv", "name\nAda\n").is_err());
+        assert!(parse_rows("contacts.csv", "email,attributes\na@b.com,{oops\n").is_err());
+    }
+
+    #[test]
+    fn parses_json_rows() {
+        let json = r#"[
+            {"email": "ada@example.com", "attributes": {"plan": "pro"}, "topics": {"news": "OPT_OUT"}},
+            {"email": "bob@example.com", "unsubscribe_all": true}
+        ]"#;
+        let rows = parse_rows("contacts.json", json).unwrap();
+        assert_eq!(rows[0].attributes["plan"], "pro");
+        assert_eq!(rows[0].topics["news"], "OPT_OUT");
+        assert!(!rows[0].unsubscribe_all);
+        assert!(rows[1].unsubscribe_all);
+        assert!(parse_rows("contacts.json", r#"{"email": "ada@example.com"}"#).is_err());
+        assert!(parse_rows("contacts.txt", json).is_err());
+    }
+
+    #[test]
+    fn validates_email_addresses() {
+        for email in ["ada@example.com", "ada.lovelace+news@mail.example.co.uk"] {
+            assert!(is_valid_email(email), "{email}");
+        }
+        for email in [
+            "",
+            "ada",
+            "@example.com",
+            "ada@example",
+            "ada@.example.com",
+            "ada@example.com.",
+            "ada@b@example.com",
+            "ada lovelace@example.com",
+        ] {
+            assert!(!is_valid_email(email), "{email}");
+        }
+    }
+
+    #[test]
+    fn skips_invalid_duplicate_and_badly_subscribed_rows() {
+        let mut opted = row("carl@example.com");
+        opted.topics.insert("news".into(), "maybe".into());
+        let rows = vec![
+            row(" ada@example.com "),
+            row("not an email"),
+            row("ADA@example.com"),
+            opted,
+            row("bob@example.com"),
+        ];
+        let (contacts, skipped) = screen_rows(rows);
+        let created = contacts
+            .iter()
+            .map(|(line, row, _)| (*line, row.email.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(created, [(1, "ada@example.com"), (5, "bob@example.com")]);
+        let skipped = skipped
+            .iter()
+            .map(|(line, email, _)| (*line, email.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            skipped,
+            [
+                (2, "not an email"),
+                (3, "ADA@example.com"),
+                (4, "carl@example.com")
+            ]
+        );
+    }
+}
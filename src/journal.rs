@@ -0,0 +1,240 @@
+use crate::bulk::Delivery;
+use crate::content::read_file;
+use crate::envelope::Envelope;
+use crate::error::{Result, SesClientError};
+use crate::mime::RawFiles;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Everything needed to send a bulk campaign again, the first line of its journal.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Campaign {
+    Simple {
+        list_name: String,
+        from_address: String,
+        subject: String,
+        html: Option<String>,
+        text: Option<String>,
+        #[serde(flatten)]
+        envelope: Envelope,
+    },
+    Templated {
+        list_name: String,
+        from_address: String,
+        template_name: String,
+        data_file: Option<String>,
+        default_data: Option<String>,
+        #[serde(flatten)]
+        envelope: Envelope,
+    },
+    Raw {
+        list_name: String,
+        from_address: String,
+        #[serde(flatten)]
+        files: RawFiles,
+        #[serde(flatten)]
+        envelope: Envelope,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Delivered,
+    Failed,
+}
+
+/// One line per recipient after the campaign line, written as soon as SES answers.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    recipient: String,
+    status: Status,
+    message_id: Option<String>,
+    error: Option<String>,
+    timestamp: u64,
+}
+
+/// A JSONL record of a bulk send, so an interrupted send can be resumed
+/// without sending twice to the recipients SES already accepted.
+pub struct Journal {
+    path: String,
+    file: Mutex<File>,
+    delivered: HashSet<String>,
+}
+
+impl Journal {
+    /// Starts the journal of a new campaign as `campaign_<unix time in ms>.jsonl` in the current directory.
+    pub fn create(campaign: &Campaign) -> Result<Self> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+        let path = format!("campaign_{millis}.jsonl");
+        let write_error = |source| SesClientError::Write {
+            path: path.clone(),
+            source,
+        };
+        let mut file = File::create(&path).map_err(write_error)?;
+        let line = serde_json::to_string(campaign).expect("Campaign is always serializable");
+        writeln!(file, "{line}").map_err(write_error)?;
+        println!(
+            "The send journal is written to '{}', resume an interrupted send with 'ses_client resume --journal {path}'\n",
+            path.green().bold()
+        );
+        Ok(Journal {
+            path,
+            file: Mutex::new(file),
+            delivered: HashSet::new(),
+        })
+    }
+
+    /// Reopens the journal of an earlier campaign, later entries are appended to it.
+    pub fn open(path: &str) -> Result<(Campaign, Self)> {
+        let content = read_file(path, "Journal")?;
+        let (campaign, delivered) = parse(path, &content)?;
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|source| SesClientError::Write {
+                path: path.into(),
+                source,
+            })?;
+        println!(
+            "{} recipients of '{}' are already delivered and will be skipped\n",
+            delivered.len().to_string().green().bold(),
+            path.green().bold()
+        );
+        Ok((
+            campaign,
+            Journal {
+                path: path.into(),
+                file: Mutex::new(file),
+                delivered,
+            },
+        ))
+    }
+
+    pub fn is_delivered(&self, recipient: &str) -> bool {
+        self.delivered.contains(recipient)
+    }
+
+    /// Appends the outcome of a recipient and flushes it right away.
+    pub fn record(&self, delivery: &Delivery) -> Result<()> {
+        let entry = match &delivery.outcome {
+            Ok(message_id) => Entry {
+                recipient: delivery.recipient.clone(),
+                status: Status::Delivered,
+                message_id: Some(message_id.clone()),
+                error: None,
+                timestamp: unix_time(),
+            },
+            Err(error) => Entry {
+                recipient: delivery.recipient.clone(),
+                status: Status::Failed,
+                message_id: None,
+                error: Some(error.clone()),
+                timestamp: unix_time(),
+            },
+        };
+        let line = serde_json::to_string(&entry).expect("Entry is always serializable");
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        writeln!(file, "{line}")
+            .and_then(|_| file.flush())
+            .map_err(|source| SesClientError::Write {
+                path: self.path.clone(),
+                source,
+            })
+    }
+}
+
+/// The campaign on the first line and the recipients the entries after it have as delivered.
+fn parse(path: &str, content: &str) -> Result<(Campaign, HashSet<String>)> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let campaign =
+        serde_json::from_str::<Campaign>(lines.next().unwrap_or_default()).map_err(|err| {
+            SesClientError::validation(format!(
+                "The first line of the journal '{path}' isn't a campaign: {err}"
+            ))
+        })?;
+    let mut delivered = HashSet::new();
+    for line in lines {
+        // A send killed mid-write leaves a truncated last line, its recipient is sent again
+        let Ok(entry) = serde_json::from_str::<Entry>(line) else {
+            continue;
+        };
+        if entry.status == Status::Delivered {
+            delivered.insert(entry.recipient);
+        }
+    }
+    Ok((campaign, delivered))
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn campaign_line() -> String {
+        serde_json::to_string(&Campaign::Simple {
+            list_name: "subscribers".into(),
+            from_address: "news@example.com".into(),
+            subject: "News".into(),
+            html: Some("<p>Hi</p>".into()),
+            text: None,
+            envelope: Envelope::default(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_the_campaign_and_the_delivered_recipients() {
+        let content = format!(
+            "{}\n\
+            {{\"recipient\":\"a@example.com\",\"status\":\"delivered\",\"message_id\":\"1\",\"error\":null,\"timestamp\":1}}\n\
+            {{\"recipient\":\"b@example.com\",\"status\":\"failed\",\"message_id\":null,\"error\":\"throttled\",\"timestamp\":2}}\n\
+            \n\
+            {{\"recipient\":\"c@example.com\",\"status\":\"deliv",
+            campaign_line()
+        );
+        let (campaign, delivered) = parse("journal.jsonl", &content).unwrap();
+        match campaign {
+            Campaign::Simple {
+                list_name, html, ..
+            } => {
+                assert_eq!(list_name, "subscribers");
+                assert_eq!(html.as_deref(), Some("<p>Hi</p>"));
+            }
+            _ => panic!("expected a simple campaign"),
+        }
+        assert_eq!(delivered, HashSet::from(["a@example.com".to_string()]));
+    }
+
+    #[test]
+    fn reads_raw_campaigns_written_without_an_envelope() {
+        let content = r#"{"kind":"raw","list_name":"customers","from_address":"a@example.com","subject":"Prices","html":null,"text":"/tmp/prices.txt","attachments":[],"inline":[]}"#;
+        let (campaign, delivered) = parse("journal.jsonl", content).unwrap();
+        assert!(matches!(campaign, Campaign::Raw { envelope, .. } if envelope.cc.is_empty()));
+        assert!(delivered.is_empty());
+    }
+
+    #[test]
+    fn rejects_a_journal_without_a_campaign() {
+        assert!(parse("journal.jsonl", "").is_err());
+        assert!(parse("journal.jsonl", "{\"kind\":\"unknown\"}").is_err());
+    }
+}
@@ -1,72 +1,163 @@
-use aws_apis::{
-    load_credential_from_env, CredentInitialize, SesOps, SimpleMail, Simple_, TemplateMail,
-    Template_,
+mod body;
+mod bulk;
+mod cli;
+mod commands;
+mod config;
+mod configuration_sets;
+mod contact_filter;
+mod contact_lists;
+mod contacts;
+mod content;
+mod context;
+mod dns_check;
+mod domains;
+mod dry_run;
+mod envelope;
+mod error;
+mod export;
+mod identities;
+mod identity_status;
+mod import;
+mod journal;
+mod mime;
+mod preview;
+mod recipients;
+mod render;
+mod sandbox;
+mod send;
+mod templates;
+
+use aws_apis::{CredentInitialize, SesOps};
+use aws_sdk_sesv2::types::Topic;
+use body::{is_html, SimpleBody};
+use clap::Parser;
+use cli::{
+    Cli, CredentialSource, EventDestinationArgs, EventKind, EventTarget, ExportFormat,
+    IdentityColumn, TlsRequirement,
 };
 use colored::Colorize;
-use dotenv::dotenv;
+use config::{Defaults, EnvDefaults};
+use configuration_sets::NewConfigurationSet;
+use contact_filter::{
+    matching_contacts, parse_conditions, print_page, write_emails, ContactFilter,
+};
+use contact_lists::{
+    parse_attributes, parse_preferences, parse_topic, ContactUpdate, ListCopy, ListUpdate,
+};
+use content::{fetch_body, read_file};
+use context::Context;
+use dns_check::parse_resolver;
+use domains::{Byodkim, DomainSetup};
+use envelope::{parse_addresses, parse_tags, Envelope, MAX_RECIPIENTS};
+use error::{Result, SesClientError};
 use inquire::{
     ui::{Attributes, RenderConfig, StyleSheet, Styled},
-    Confirm, Select, Text,
+    Confirm, InquireError, MultiSelect, Select, Text,
 };
-use reqwest::get;
-use std::{env::var, fs::OpenOptions, io::Read};
+use recipients::contact_template_data;
+use sandbox::unreachable_recipients;
+use std::collections::BTreeMap;
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+    let file_config = match config::load_file_config(cli.config.as_deref()) {
+        Ok(file_config) => file_config,
+        Err(err) => {
+            eprintln!("{}", err.to_string().red().bold());
+            std::process::exit(1);
+        }
+    };
+    let env_defaults = EnvDefaults::capture();
+    let defaults = match file_config.profile(cli.profile.as_deref()) {
+        Ok(profile) => Defaults::resolve(
+            cli.endpoint_url.clone(),
+            &env_defaults,
+            &file_config,
+            profile,
+        ),
+        Err(err) => {
+            eprintln!("{}", err.to_string().red().bold());
+            std::process::exit(1);
+        }
+    };
+    if let Some(command) = cli.command {
+        let mut credential = CredentInitialize::default();
+        let source = cli
+            .credentials
+            .or(defaults.credentials)
+            .unwrap_or(CredentialSource::Config);
+        let outcome =
+            match commands::build_ses_ops(&mut credential, source, defaults, cli.dry_run).await {
+                Ok((ses_ops, mut ctx)) => {
+                    ctx.unverified = cli.unverified;
+                    commands::run(command, &ses_ops, &ctx).await
+                }
+                Err(err) => Err(err),
+            };
+        credential.empty();
+        if let Err(err) = outcome {
+            eprintln!("{}", err.to_string().red().bold());
+            std::process::exit(1);
+        }
+        return;
+    }
     inquire::set_global_render_config(global_render_config());
     let operations: Vec<&str> = vec![
         "Verify the Credential\n",
         "Print Credentials Information\n",
         "AWS Simple Email Service(SES) Operations\n",
+        "Toggle Dry Run Mode\n",
+        "Select a Profile\n",
         "Quit the application\n",
     ];
     let mut credential = CredentInitialize::default();
-    let mut sdk_config = credential.build();
+    defaults.apply_to_env();
+    let mut sdk_config = config::with_overrides(credential.build(), &defaults);
     let mut ses_ops: SesOps = SesOps::build(&sdk_config);
+    let mut ctx = Context::new(&sdk_config, cli.dry_run, defaults);
+    ctx.unverified = cli.unverified;
     'main: loop {
-        let choice = Select::new(
+        let choice = match Select::new(
             "Select the option to execute the operation\n",
             operations.clone(),
         )
         .with_help_message(
             "Don't enclose data in quotation marks or add spaces around it in any operations",
         )
-        .with_page_size(4)
+        .with_page_size(6)
         .prompt()
-        .unwrap();
+        {
+            Ok(choice) => choice,
+            Err(InquireError::OperationCanceled) => continue 'main,
+            Err(_) => "Quit the application\n",
+        };
 
         match choice {
             "Verify the Credential\n" => {
-                let choices = Confirm::new("Load the credentials from the configuration file or from environment variables\n")
+                let choices = match Confirm::new("Load the credentials from the configuration file or from environment variables\n")
                           .with_placeholder("Use 'Yes' to load from the environment and 'No' to load from environment variables\n")
                           .with_help_message("Without proper credentials, no operations can be executed successfully")
+                          .with_default(!matches!(ctx.defaults.credentials, Some(CredentialSource::Env)))
                           .prompt()
-                          .unwrap();
-
-                match choices {
-                    true => {
-                        let (credentials, region) = load_credential_from_env().await;
-                        credential.update(
-                            credentials.access_key_id(),
-                            credentials.secret_access_key(),
-                            region.as_deref(),
-                        );
-                        sdk_config = credential.build();
-                        ses_ops = SesOps::build(&sdk_config);
-                        println!("{}\n","Please verify the credentials by printing the credential information before proceeding with any operations".blue().bold());
+                {
+                    Ok(choices) => choices,
+                    Err(err) => {
+                        SesClientError::from(err).report();
+                        continue 'main;
                     }
-                    false => {
-                        dotenv().ok();
-                        let access_key = var("AWS_ACCESS_KEY_ID")
-                        .expect("Ensure that the 'AWS_ACCESS_KEY_ID' environment variable is set, and its value is provided by AWS\n");
-                        let secret_key = var("AWS_SECRET_ACCESS_KEY")
-                        .expect("Ensure that the 'AWS_SECRET_ACCESS_KEY' environment variable is set, and its value is provided by AWS\n");
-                        let region = var("AWS_DEFAULT_REGION")
-                        .expect("Ensure that the 'AWS_DEFAULT_REGION' environment variable is set, and its value is provided by AWS\n");
-                        credential.update(&access_key, &secret_key, Some(&region));
-                        sdk_config = credential.build();
+                };
+                let source = match choices {
+                    true => CredentialSource::Config,
+                    false => CredentialSource::Env,
+                };
+                match commands::update_credentials(&mut credential, source).await {
+                    Ok(()) => {
+                        sdk_config = config::with_overrides(credential.build(), &ctx.defaults);
                         ses_ops = SesOps::build(&sdk_config);
-                        println!("{}\n","Please verify the credentials by printing the credential information before proceeding with any operations".red().bold());
+                        ctx.set_sdk_config(&sdk_config);
+                        println!("{}\n","Please verify the credentials by printing the credential information before proceeding with any operations".blue().bold());
                     }
+                    Err(err) => err.report(),
                 }
             }
             "Print Credentials Information\n" => {
@@ -80,12 +171,18 @@ async fn main() {
                 .with_help_message("This is solely for verification purposes")
                 .with_default(false)
                 .prompt()
-                .unwrap();
+                .unwrap_or(false);
 
                 match confirm {
                     true => {
-                        println!("{}\n","Here is your credential informations".yellow().bold());
+                        println!(
+                            "{}\n",
+                            "Here is your credential informations".yellow().bold()
+                        );
                         credential.print_credentials();
+                        if let Some(endpoint_url) = sdk_config.endpoint_url() {
+                            println!("SES Endpoint URL: {}\n", endpoint_url.green().bold());
+                        }
                     }
                     false => {
                         println!("{}\n", "Sure...".green().bold())
@@ -96,16 +193,21 @@ async fn main() {
                 let ses_operations = vec![
                     "Create a Contact List Name\n",
                     "Add an email to the list\n",
+                    "Import Contacts from a CSV or JSON File\n",
                     "Send a Single Simple Email\n",
                     "Send a Bulk of Simple Emails\n",
+                    "Send an Email with Attachments\n",
                     "Default Values\n",
                     "Create Email Template\n",
                     "Get Email Template\n",
                     "Get Email Template Variables\n",
                     "Match Template Variables\n",
+                    "Preview Template with Data\n",
                     "Send a Single Templated Email\n",
                     "Send a Bulk of Templated Emails\n",
+                    "Resume a Bulk Send\n",
                     "Retrieve emails from the provided list\n",
+                    "Export Contacts as CSV or JSON\n",
                     "Create Email Identity\n",
                     "Email Verification\n",
                     "Get Email Identities\n",
@@ -114,11 +216,19 @@ async fn main() {
                     "Delete Contact\n",
                     "Delete Contacts\n",
                     "Delete Contact List Name\n",
+                    "Update a Contact List\n",
+                    "Copy a Contact List\n",
+                    "Describe a Contact List\n",
+                    "Search Contacts\n",
+                    "Get a Contact\n",
+                    "Update a Contact\n",
+                    "Configuration Set Operations\n",
+                    "Identity Operations\n",
                     "Common Errors\n",
                     "Return to the Main Menu\n",
                 ];
                 loop {
-                    let email_choice = Select::new(
+                    let email_choice = match Select::new(
                         "Select the option to execute the operation\n",
                         ses_operations.clone(),
                     )
@@ -126,1418 +236,2082 @@ async fn main() {
                     .with_vim_mode(true)
                     .with_page_size(13)
                     .prompt()
-                    .unwrap();
-                    match email_choice {
-                        "Match Template Variables\n" => {
-                            let available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder = format!(
-                                "Below are the template names available in your account:\n{}\n",
-                                available_template_names.join("\n")
-                            );
-                            let help_placeholder = format!(
-                                "The default template name is '{}'",
-                                ses_ops.get_template_name()
-                            );
-                            let template_name = Text::new("Please provide the template name with which you want to compare the template data you have\n")
-        .with_placeholder(&placeholder)
-        .with_formatter(&|input| format!("Received Template Name: {input}\n"))
-        .with_help_message(&help_placeholder)
-        .prompt_skippable()
-        .unwrap()
-        .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    if ses_ops.is_email_template_exist(&template_name).await {
-                                        let template_data_path = Text::new("Please specify the JSON template data path\n")
-                .with_formatter(&|input| format!("Received Template Json Data Path: {input}\n"))
-                .with_placeholder(
-                    "Ensure that the JSON template data matches the template you selected above\n",
-                )
-                .with_help_message("Do not include spaces, commas, or apostrophes around keys")
-                .prompt()
-                .unwrap();
-                                        match template_data_path.is_empty() {
-                                            false => {
-                                                ses_ops
-                                                    .match_template_data_with_template(
-                                                        Some(&template_name),
-                                                        &template_data_path,
-                                                    )
-                                                    .await;
-                                            }
-                                            true => println!(
-                                                "{}\n",
-                                                "The Template Json Data Path can't be empty"
-                                                    .red()
-                                                    .bold()
-                                            ),
-                                        }
-                                    } else {
-                                        println!(
-                                            "The specified template name '{}' does not exist",
-                                            template_name.red().bold()
-                                        );
-                                        println!(
-                    "{}\n",
-                    "Here is a list of template names in your credentials if any"
-                        .yellow()
-                        .bold()
-                );
-                                        for template_name in available_template_names {
-                                            println!("    {}", template_name.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                                true => {
-                                    if ses_ops.is_email_template_exist(&template_name).await {
-                                        let template_data_path = Text::new("Please specify the JSON template data path\n")
-                .with_formatter(&|input| format!("Received Template Json Data Path: {input}\n"))
-                .with_placeholder(
-                    "Ensure that the JSON template data matches the template you selected above\n",
-                )
-                .with_help_message("Do not include spaces, commas, or apostrophes around keys")
-                .prompt()
-                .unwrap();
-                                        match template_data_path.is_empty() {
-                                            false => {
-                                                ses_ops
-                                                    .match_template_data_with_template(
-                                                        None,
-                                                        &template_data_path,
-                                                    )
-                                                    .await;
-                                            }
-                                            true => println!(
-                                                "{}\n",
-                                                "The Template Json Data Path can't be empty"
-                                                    .red()
-                                                    .bold()
-                                            ),
-                                        }
-                                    } else {
-                                        println!("{}","Make sure you have set the 'TEMPLATE_NAME' environment variable, and the value it holds should exist in your credentials".red().bold());
-                                        println!(
-                    "{}\n",
-                    "Here is a list of template names in your credentials if any"
-                        .yellow()
-                        .bold()
-                );
-                                        for template_name in available_template_names {
-                                            println!("    {}", template_name.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                            }
-                        }
-                        "Create Email Template\n" => {
-                            let get_available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder_info = format!("Please note that these template names are already available for your use:\n{:#?}",get_available_template_names);
-                            let template_name = Text::new(
-                                "Please provide the new template name for this template\n",
-                            )
-                            .with_placeholder(&placeholder_info)
-                            .with_formatter(&|input| format!("Received Template Name: {input}\n"))
-                            .prompt_skippable()
-                            .unwrap()
-                            .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    let is_template_already_exist =
-                                        ses_ops.is_email_template_exist(&template_name).await;
-                                    if !is_template_already_exist {
-                                        let subject_path =Text::new("Please provide the path to the subject data in HTML format to create Subject for Email Template\n")
-                                    .with_placeholder("The subject can contain template variables to personalize the email template's subject line\nDo not use apostrophes, or commas around template variables\n")
-                                    .with_help_message("An example subject template is available here https://tinyurl.com/anxch48r")
-                                    .with_formatter(&|input| format!("Received Subject Path: {input}\n"))
-                                    .prompt()
-                                    .unwrap();
-
-                                        let template_path = Text::new("Please provide the path for the template in HTML format to Create a HTML body for the Email Template\n")
-                                          .with_formatter(&|input| format!("Received Template Path: {input}\n"))
-                                          .with_placeholder("The HTML body can contain both template variables and HTML content\n")
-                                          .with_help_message("Example template is available at this location: https://tinyurl.com/4ssuz7fy")
-                                          .prompt()
-                                          .unwrap();
-                                        match (subject_path.is_empty(), template_path.is_empty()) {
-                                            (false, false) => {
-                                                let mut reading_template_data = OpenOptions::new()
-                                        .read(true)
-                                        .write(true)
-                                        .open(&template_path)
-                                        .expect(
-                                            "Error opening the Template file path you specified\n",
-                                        );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading data\n");
-                                                let mut reading_subject_data = OpenOptions::new()
-                                        .read(true)
-                                        .write(true)
-                                        .open(&subject_path)
-                                        .expect(
-                                            "Error opening the Subject file path you specified\n",
-                                        );
-                                                let mut subject_data = String::new();
-                                                reading_subject_data
-                                                    .read_to_string(&mut subject_data)
-                                                    .expect("Error while reading data\n");
-                                                let text_path =Text::new("Please provide the path to the text body for the email template\n")
-                                        .with_placeholder("This section is optional, but it's essential to include for recipients who do not support HTML\n")
-                                        .with_formatter(&|input| format!("Received Text Body Path: {input}\n"))
-                                        .with_help_message("Example text body data is available here https://tinyurl.com/5n84m4xk")
-                                        .prompt_skippable()
-                                        .unwrap()
-                                        .unwrap();
-                                                match text_path.is_empty() {
-                                                    false => {
-                                                        let mut reading_text_data = OpenOptions::new()
-                                                         .read(true)
-                                                         .write(true)
-                                                         .open(&text_path)
-                                                         .expect("Error opening the Text Body file path you specified\n");
-                                                        let mut text_data = String::new();
-                                                        reading_text_data
-                                                    .read_to_string(&mut text_data)
-                                                    .expect(
-                                                    "Error opening the file path you specified\n",
-                                                );
-
-                                                        ses_ops
-                                                            .create_email_template(
-                                                                &template_name,
-                                                                &subject_data,
-                                                                &template_data,
-                                                                Some(text_data),
-                                                            )
-                                                            .await;
-                                                    }
-                                                    true => {
-                                                        ses_ops
-                                                            .create_email_template(
-                                                                &template_name,
-                                                                &subject_data,
-                                                                &template_data,
-                                                                None,
-                                                            )
-                                                            .await;
-                                                    }
-                                                }
-                                            }
-                                            _ => println!(
-                                                "{}\n",
-                                                "Subject or Template path can't be empty"
-                                                    .red()
-                                                    .bold()
-                                            ),
-                                        }
-                                    } else {
-                                        println!(
-                                            "Template '{}' already exists",
-                                            template_name.red().bold()
-                                        );
-                                        println!(
-                                            "{}",
-                                            "Try using different template name".yellow().bold()
-                                        );
-                                        println!(
-                "{}\n",
-                "Below are the available template names in your credentials and region"
-                    .yellow()
-                    .bold()
+                    {
+                        Ok(choice) => choice,
+                        Err(_) => continue 'main,
+                    };
+                    if email_choice == "Return to the Main Menu\n" {
+                        continue 'main;
+                    }
+                    if let Err(err) = ses_operation(email_choice, &ses_ops, &ctx).await {
+                        err.report();
+                    }
+                }
+            }
+            "Select a Profile\n" => {
+                let profiles = file_config.profiles.keys().cloned().collect::<Vec<_>>();
+                if profiles.is_empty() {
+                    println!("{}\n", "There are no profiles in the configuration file, add them as [profiles.<name>] tables".yellow().bold());
+                    continue 'main;
+                }
+                let profile = match Select::new("Select the profile whose defaults you want to use\n", profiles)
+                    .with_help_message("Environment variables such as 'LIST_NAME' still take precedence over the profile")
+                    .prompt()
+                {
+                    Ok(profile) => profile,
+                    Err(err) => {
+                        SesClientError::from(err).report();
+                        continue 'main;
+                    }
+                };
+                match file_config.profile(Some(&profile)) {
+                    Ok(profile) => {
+                        let defaults = Defaults::resolve(
+                            cli.endpoint_url.clone(),
+                            &env_defaults,
+                            &file_config,
+                            profile,
+                        );
+                        defaults.apply_to_env();
+                        sdk_config = config::with_overrides(credential.build(), &defaults);
+                        ses_ops = SesOps::build(&sdk_config);
+                        ctx.set_sdk_config(&sdk_config);
+                        ctx.defaults = defaults;
+                        ctx.defaults.print();
+                    }
+                    Err(err) => err.report(),
+                }
+            }
+            "Toggle Dry Run Mode\n" => {
+                ctx.dry_run = !ctx.dry_run;
+                match ctx.dry_run {
+                    true => println!("{}\n","Dry run mode is on: sends are rendered and written to a report, and no SES resource is created, updated or deleted".yellow().bold()),
+                    false => println!("{}\n", "Dry run mode is off".green().bold()),
+                }
+            }
+            "Quit the application\n" => {
+                credential.empty();
+                break 'main;
+            }
+            _other => {
+                println!("This branch never reach..");
+            }
+        }
+    }
+}
+/// Executes a single entry of the SES menu, any failure is reported by the caller
+/// and the menu is shown again.
+async fn ses_operation(email_choice: &str, ses_ops: &SesOps, ctx: &Context) -> Result<()> {
+    match email_choice {
+        "Match Template Variables\n" => {
+            let available_template_names = templates::list_names(ctx).await?;
+            let placeholder = format!(
+                "Below are the template names available in your account:\n{}\n",
+                available_template_names.join("\n")
             );
-                                        let templates = ses_ops.list_email_templates().await;
-                                        for template_name in templates {
-                                            println!("    {}", template_name.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                                _ => {
-                                    println!("{}\n", "Fields should not be left empty".red().bold())
-                                }
+            let help_placeholder = format!(
+                "The default template name is '{}'",
+                ses_ops.get_template_name()
+            );
+            let template_name = Text::new("Please provide the template name with which you want to compare the template data you have\n")
+                .with_placeholder(&placeholder)
+                .with_formatter(&|input| format!("Received Template Name: {input}\n"))
+                .with_help_message(&help_placeholder)
+                .prompt_skippable()?
+                .unwrap_or_default();
+            match template_name.is_empty() {
+                false => {
+                    if templates::exists(ctx, &template_name).await? {
+                        let template_data_path = Text::new("Please specify the JSON template data path\n")
+                            .with_formatter(&|input| format!("Received Template Json Data Path: {input}\n"))
+                            .with_placeholder("Ensure that the JSON template data matches the template you selected above\n")
+                            .with_help_message("Do not include spaces, commas, or apostrophes around keys")
+                            .prompt()?;
+                        match template_data_path.is_empty() {
+                            false => {
+                                templates::match_data(ctx, &template_name, &template_data_path)
+                                    .await?;
                             }
+                            true => println!(
+                                "{}\n",
+                                "The Template Json Data Path can't be empty".red().bold()
+                            ),
                         }
-                        "Delete Contact\n" => {
-                            let get_available_contact_lists = ses_ops.list_contact_lists().await;
-                            let format_it = format!(
-                                "The contact list name below is available in your credentials or region if any:\n    {}\n",
-                                get_available_contact_lists.join("")
-                            );
-                            let placeholder = format!(
-                                "Default Contact List Name Is: {}",
-                                ses_ops.get_list_name()
-                            );
-                            let contact_list = Text::new("Please enter the name of the contact list from which you want to delete the contact or email\n")
-                                .with_placeholder(&format_it)
-                                .with_formatter(&|input| format!("Received Contact List Name: {input}\n"))
-                                .with_help_message(&placeholder)
-                                .prompt_skippable()
-                                .unwrap()
-                                .unwrap();
-                            match contact_list.is_empty() {
-                                false => {
-                                    if ses_ops.is_contact_list_name_exist(&contact_list).await {
-                                        let contact = Text::new(
-                                            "Please enter the contact you wish to delete from the provided contact list\n",
-                                        )
-                                        .with_placeholder(
-                                            "The contact is an email address stored in the specified contact list\n",
-                                        )
-                                        .with_formatter(&|input| format!("Received Contact or Email: {input}\n"))
-                                        .prompt()
-                                        .unwrap();
-                                        match contact.is_empty() {
-                                            false => {
-                                                ses_ops
-                                                    .delete_contact(
-                                                        &contact,
-                                                        Some(&contact_list),
-                                                        true,
-                                                    )
-                                                    .await;
-                                            }
-                                            true => println!(
-                                                "{}\n",
-                                                "Email/Contact can't be empty".red().bold()
-                                            ),
-                                        }
-                                    } else {
-                                        println!(
-                                            "The provided Contact List Name '{}' doesn't exist",
-                                            contact_list.red().bold()
-                                        );
-                                        println!(
-                                            "{}\n",
-                                            "The contact list name below is available in your credentials or region if any"
-                                                .yellow()
-                                                .bold()
-                                        );
-                                        let contact_lists = ses_ops.list_contact_lists().await;
-                                        for contact_list in contact_lists {
-                                            println!("    {}", contact_list.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                                true => {
-                                    if ses_ops.is_contact_list_name_exist(&contact_list).await {
-                                        let contact = Text::new(
-                                            "Please enter the contact you wish to delete from the provided contact list\n",
-                                        )
-                                        .with_placeholder(
-                                            "The contact is an email address stored in the specified contact list\n",
-                                        )
-                                        .with_formatter(&|input| format!("Received Contact or Email: {input}\n"))
-                                        .prompt()
-                                        .unwrap();
-                                        match contact.is_empty() {
-                                            false => {
-                                                ses_ops.delete_contact(&contact, None, true).await;
-                                            }
-                                            true => println!(
-                                                "{}\n",
-                                                "Email/Contact can't be empty".red().bold()
-                                            ),
-                                        }
-                                    } else {
-                                        println!(
-                                            "The provided Contact List Name '{}' doesn't exist",
-                                            contact_list.red().bold()
-                                        );
-                                        println!(
-                                            "{}\n",
-                                            "The contact list name below is available in your credentials or region if any"
-                                                .yellow()
-                                                .bold()
-                                        );
-                                        let contact_lists = ses_ops.list_contact_lists().await;
-                                        for contact_list in contact_lists {
-                                            println!("    {}", contact_list.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                            }
+                    } else {
+                        println!(
+                            "The specified template name '{}' does not exist",
+                            template_name.red().bold()
+                        );
+                        println!(
+                            "{}\n",
+                            "Here is a list of template names in your credentials if any"
+                                .yellow()
+                                .bold()
+                        );
+                        for template_name in available_template_names {
+                            println!("    {}", template_name.green().bold());
                         }
-                        "Delete Contacts\n" => {
-                            let get_available_contact_lists = ses_ops.list_contact_lists().await;
-                            let format_it = format!(
-        "The contact list name below is available in your credentials or region if any:\n    {}\n",
-        get_available_contact_lists.join("")
-    );
-                            let placeholder = format!(
-                                "Default Contact List Name Is: {}",
-                                ses_ops.get_list_name()
-                            );
-                            let contact_list = Text::new("Please enter the name of the contact list from which you want to delete all the contacts or emails within it\n")
-        .with_placeholder(&format_it)
-        .with_formatter(&|input| format!("Received Contact List Name: {input}\n"))
-        .with_help_message(&placeholder)
-        .prompt_skippable()
-        .unwrap()
-        .unwrap();
-                            match contact_list.is_empty() {
-                                false => {
-                                    ses_ops.delete_contacts(Some(&contact_list)).await;
-                                }
-                                true => {
-                                    ses_ops.delete_contacts(None).await;
-                                }
+                        println!();
+                    }
+                }
+                true => {
+                    if templates::exists(ctx, ses_ops.get_template_name()).await? {
+                        let template_data_path = Text::new("Please specify the JSON template data path\n")
+                            .with_formatter(&|input| format!("Received Template Json Data Path: {input}\n"))
+                            .with_placeholder("Ensure that the JSON template data matches the template you selected above\n")
+                            .with_help_message("Do not include spaces, commas, or apostrophes around keys")
+                            .prompt()?;
+                        match template_data_path.is_empty() {
+                            false => {
+                                templates::match_data(
+                                    ctx,
+                                    ses_ops.get_template_name(),
+                                    &template_data_path,
+                                )
+                                .await?;
                             }
+                            true => println!(
+                                "{}\n",
+                                "The Template Json Data Path can't be empty".red().bold()
+                            ),
                         }
-                        "Update Email Template\n" => {
-                            let get_available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder_info = format!(
-                                "Available Template Names in Your Credentials\n{:#?}",
-                                get_available_template_names
-                            );
-                            let template_name = Text::new(
-                        "Please provide the template name to update the associated template\n",)
+                    } else {
+                        println!("{}","Make sure you have set the 'TEMPLATE_NAME' environment variable, and the value it holds should exist in your credentials".red().bold());
+                        println!(
+                            "{}\n",
+                            "Here is a list of template names in your credentials if any"
+                                .yellow()
+                                .bold()
+                        );
+                        for template_name in available_template_names {
+                            println!("    {}", template_name.green().bold());
+                        }
+                        println!();
+                    }
+                }
+            }
+        }
+        "Preview Template with Data\n" => {
+            let available_template_names = templates::list_names(ctx).await?;
+            let placeholder = format!(
+                "Below are the template names available in your account:\n{}\n",
+                available_template_names.join("\n")
+            );
+            let help_placeholder = format!(
+                "The default template name is '{}', or enter the path of a directory with subject.html, template.html and text_body.txt",
+                ses_ops.get_template_name()
+            );
+            let template_source = Text::new("Please provide the template name or the directory of the template files you want to preview\n")
+                .with_placeholder(&placeholder)
+                .with_formatter(&|input| format!("Received Template: {input}\n"))
+                .with_help_message(&help_placeholder)
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let template_data_path = Text::new("Please specify the JSON template data path\n")
+                .with_formatter(&|input| format!("Received Template Json Data Path: {input}\n"))
+                .with_placeholder("Every template variable should have a value in the JSON data\n")
+                .prompt()?;
+            let output = Text::new("Please enter the path of the HTML file to write\n")
+                .with_placeholder("Defaults to 'template_preview.html'\n")
+                .with_formatter(&|input| format!("Received Output Path: {input}\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let output = match output.is_empty() {
+                true => "template_preview.html".to_string(),
+                false => output,
+            };
+            let parts = match std::path::Path::new(&template_source).is_dir() {
+                true => preview::template_parts_from_dir(&template_source)?,
+                false => {
+                    let template_name = match template_source.is_empty() {
+                        true => ses_ops.get_template_name(),
+                        false => &template_source,
+                    };
+                    templates::parts(ctx, template_name).await?
+                }
+            };
+            let data =
+                send::parse_template_data(&read_file(&template_data_path, "Template Data")?)?;
+            preview::preview(&parts, &data, &output)?;
+        }
+        "Create Email Template\n" => {
+            let get_available_template_names = templates::list_names(ctx).await?;
+            let placeholder_info = format!(
+                "Please note that these template names are already available for your use:\n{:#?}",
+                get_available_template_names
+            );
+            let template_name =
+                Text::new("Please provide the new template name for this template\n")
                     .with_placeholder(&placeholder_info)
-                    .with_formatter(&|input| format!("Received Template Name Is: {input}\n"))
-                    .prompt()
-                    .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    let is_template_exist =
-                                        ses_ops.is_email_template_exist(&template_name).await;
-                                    if is_template_exist {
-                                        let is_template_exist = ses_ops
-                                            .get_template_subject_html_and_text(
-                                                &template_name,
-                                                false,
-                                            );
-                                        match is_template_exist.await {
-                                            Some((
-                                                current_subject,
-                                                current_template_html,
-                                                current_text,
-                                            )) => {
-                                                let current_subject = format!(
-                                                    "Your current email template subject is:\n {}",
-                                                    current_subject
-                                                );
-                                                let subject_path =Text::new("Please provide the path to the subject data in JSON or HTML format to update\n")
-                                    .with_placeholder(&current_subject)
-                                    .with_formatter(&|input| format!("Received Subject Path: {input}\n"))
-                                    .prompt()
-                                    .unwrap();
-                                                let current_template_variables = ses_ops
-                                                .get_template_variables_of_subject_and_html_body(
-                                                    &current_subject,
-                                                    &current_template_html,
-                                                );
-                                                let current_template_variables = format!("These are the current template variables in the template named '{}'\n{}",template_name,current_template_variables.1.join("\n"));
-                                                let template_path = Text::new("Please provide the path for the template in JSON or HTML format to update it with the old one\n")
-                                          .with_formatter(&|input| format!("Received Template Path Is: {input}\n"))
-                                          .with_placeholder(&current_template_variables)
-                                          .with_help_message("Example template is available at this location: https://tinyurl.com/4ssuz7fy")
-                                          .prompt()
-                                          .unwrap();
-                                                let current_text = if current_text.len() > 50 {
-                                                    let format_text = current_text
-                                                        .lines()
-                                                        .take(10)
-                                                        .collect::<String>();
-                                                    format!(
-                                                    "Your current email template text is:\n{}\n",
-                                                    format_text
-                                                )
-                                                } else {
-                                                    format!("Your current email template text is:\n{}\n",current_text)
-                                                };
-                                                let text_path =Text::new("Please provide the path to the text body for the email template\n")
-                                    .with_placeholder(&current_text)
-                                    .with_help_message("This section is optional, but it's essential to include for recipients who do not support HTML")
-                                    .with_formatter(&|input| format!("Received Text Body Is: {input}\n"))
-                                    .prompt_skippable()
-                                    .unwrap()
-                                    .unwrap();
-                                                let mut reading_template_data = OpenOptions::new()
-                                                    .read(true)
-                                                    .write(true)
-                                                    .open(&template_path)
-                                                    .expect(
-                                                        "Error opening the Template file path you specified\n",
-                                                    );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading template data\n");
-                                                let mut reading_subject_data = OpenOptions::new()
-                                                    .read(true)
-                                                    .write(true)
-                                                    .open(&subject_path)
-                                                    .expect(
-                                                        "Error opening the Subject file path you specified",
-                                                    );
-                                                let mut subject_data = String::new();
-                                                reading_subject_data
-                                                    .read_to_string(&mut subject_data)
-                                                    .expect("Error while reading subject data\n");
+                    .with_formatter(&|input| format!("Received Template Name: {input}\n"))
+                    .prompt_skippable()?
+                    .unwrap_or_default();
+            match template_name.is_empty() {
+                false => {
+                    let is_template_already_exist = templates::exists(ctx, &template_name).await?;
+                    if !is_template_already_exist {
+                        let subject_path =Text::new("Please provide the path to the subject data in HTML format to create Subject for Email Template\n")
+                            .with_placeholder("The subject can contain template variables to personalize the email template's subject line\nDo not use apostrophes, or commas around template variables\n")
+                            .with_help_message("An example subject template is available here https://tinyurl.com/anxch48r")
+                            .with_formatter(&|input| format!("Received Subject Path: {input}\n"))
+                            .prompt()?;
 
-                                                match text_path.is_empty() {
-                                                    false => {
-                                                        let mut read_text_data = OpenOptions::new()
-                                        .read(true)
-                                        .write(true)
-                                        .open(&text_path)
-                                        .expect("Error opening the Text Body file path you specified\n");
-                                                        let mut text = String::new();
-                                                        read_text_data
-                                                            .read_to_string(&mut text)
-                                                            .expect(
-                                                                "Error While Reading to String ",
-                                                            );
-                                                        ses_ops
-                                                            .update_template(
-                                                                &template_name,
-                                                                &subject_data,
-                                                                &template_data,
-                                                                Some(text),
-                                                            )
-                                                            .await;
-                                                    }
-                                                    true => {
-                                                        ses_ops
-                                                            .update_template(
-                                                                &template_name,
-                                                                &subject_data,
-                                                                &template_data,
-                                                                None,
-                                                            )
-                                                            .await;
-                                                    }
-                                                }
-                                            }
-                                            None => {}
-                                        }
-                                    } else {
-                                        println!(
-                                            "The template named '{}' doesn't exist",
-                                            template_name.red().bold()
-                                        );
-                                        println!(
-                                            "{}\n",
-                                            "Here are the available template names in your credentials and region"
-                                                .yellow()
-                                                .bold()
-                                        );
-                                        let templates = ses_ops.list_email_templates().await;
-                                        for template_name in templates {
-                                            println!("    {}", template_name.green().bold());
-                                        }
-                                        println!("");
-                                    }
-                                }
-                                true => {
-                                    println!("{}\n", "Template Name can't be empty".red().bold())
-                                }
-                            }
-                        }
-                        "Get Email Template\n" => {
-                            let get_available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder_info = format!(
-                                "Available Template Names in Your Credentials\n{:#?}",
-                                get_available_template_names
-                            );
-                            let template_name = Text::new("Please provide the template name for which you want to download the HTML, subject, and text parts\n")
-                                .with_placeholder(&placeholder_info)
-                                .with_formatter(&|input| {
-                                    format!("Received Template Name: {input}\n")
-                                })
-                                .prompt()
-                                .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    ses_ops
-                                        .get_template_subject_html_and_text(&template_name, true)
-                                        .await;
-                                }
-                                true => {
-                                    println!("{}\n", "Template Name can't be empty".red().bold())
-                                }
-                            }
-                        }
-                        "Get Email Template Variables\n" => {
-                            let get_available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder_info = format!(
-                                "Available Template Names in Your Credentials\n{:#?}",
-                                get_available_template_names
-                            );
-                            let template_name = Text::new(
-                                "Please provide the template name for which you want to retrieve template variables\n",
-                            )
-                            .with_placeholder(&placeholder_info)
-                            .with_help_message("Look for the placeholder to identify the available template names in your credentials")
-                            .with_formatter(&|input| {
-                                format!("Received Template Name Is: {input}\n")
-                            })
-                            .prompt_skippable()
-                            .unwrap()
-                            .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    let is_template_exist = ses_ops
-                                        .get_template_subject_html_and_text(&template_name, false);
-                                    match is_template_exist.await {
-                                        Some((subject_data, template_data, _)) => {
-                                            let (subject_variables, html_variables) = ses_ops
-                                                .get_template_variables_of_subject_and_html_body(
-                                                    &subject_data,
-                                                    &template_data,
-                                                );
-                                            println!(
-                                                "{}\n",
-                                                "Subject Template Variables if any".yellow().bold()
-                                            );
-                                            subject_variables.into_iter().for_each(|variable| {
-                                                println!("    {}", variable.green().bold());
-                                            });
-                                            println!("");
-                                            println!(
-                                                "{}\n",
-                                                "HTML Template Variables if any".yellow().bold()
-                                            );
-                                            html_variables.into_iter().for_each(|variable| {
-                                                println!("    {}", variable.green().bold());
-                                            });
-                                            println!("");
-                                        }
-                                        None => {}
-                                    }
-                                }
-                                true => {
-                                    println!("{}\n", "Template Name can't be empty".red().bold())
-                                }
+                        let template_path = Text::new("Please provide the path for the template in HTML format to Create a HTML body for the Email Template\n")
+                            .with_formatter(&|input| format!("Received Template Path: {input}\n"))
+                            .with_placeholder("The HTML body can contain both template variables and HTML content\n")
+                            .with_help_message("Example template is available at this location: https://tinyurl.com/4ssuz7fy")
+                            .prompt()?;
+                        match (subject_path.is_empty(), template_path.is_empty()) {
+                            (false, false) => {
+                                let template_data = read_file(&template_path, "Template")?;
+                                let subject_data = read_file(&subject_path, "Subject")?;
+                                let text_path =Text::new("Please provide the path to the text body for the email template\n")
+                                    .with_placeholder("This section is optional, but it's essential to include for recipients who do not support HTML\n")
+                                    .with_formatter(&|input| format!("Received Text Body Path: {input}\n"))
+                                    .with_help_message("Example text body data is available here https://tinyurl.com/5n84m4xk")
+                                    .prompt_skippable()?
+                                    .unwrap_or_default();
+                                let text_data = match text_path.is_empty() {
+                                    false => Some(read_file(&text_path, "Text Body")?),
+                                    true => None,
+                                };
+                                templates::create(
+                                    ctx,
+                                    &template_name,
+                                    &subject_data,
+                                    &template_data,
+                                    text_data,
+                                )
+                                .await?;
                             }
+                            _ => println!(
+                                "{}\n",
+                                "Subject or Template path can't be empty".red().bold()
+                            ),
                         }
-                        "Delete Template\n" => {
-                            let get_available_template_names = ses_ops.list_email_templates().await;
-                            let placeholder_info = format!(
-                                "Available Templates \n{:#?}",
-                                get_available_template_names
-                            );
-                            let template_name =
-                                Text::new("Please provide the template name for deletion\n")
-                                    .with_placeholder(&placeholder_info)
-                                    .with_formatter(&|str| format!(".....{str}.....\n"))
-                                    .prompt()
-                                    .unwrap();
-                            match template_name.is_empty() {
-                                false => {
-                                    let is_template_name_exist =
-                                        ses_ops.is_email_template_exist(&template_name).await;
-                                    match is_template_name_exist {
-                                        true => {
-                                            ses_ops
-                                                .get_template_subject_html_and_text(
-                                                    &template_name,
-                                                    true,
-                                                )
-                                                .await;
-                                            ses_ops.delete_template(&template_name).await;
-                                            println!("{}\n","The template has been downloaded to your current directory in case you need it".yellow().bold());
-                                        }
-                                        false => {
-                                            println!(
-                                                "The template named '{}' doesn't exist",
-                                                template_name.red().bold()
-                                            );
-                                            println!(
-                                                "{}\n",
-                                                "Here are the available template names in your credentials or region"
-                                                    .yellow()
-                                                    .bold()
-                                            );
-                                            let templates = ses_ops.list_email_templates().await;
-                                            for template_name in templates {
-                                                println!("    {}", template_name.green().bold());
-                                            }
-                                            println!("");
-                                        }
-                                    }
-                                }
-                                true => {
-                                    println!("{}\n", "Template Name can't be empty".red().bold())
-                                }
-                            }
+                    } else {
+                        println!("Template '{}' already exists", template_name.red().bold());
+                        println!("{}", "Try using different template name".yellow().bold());
+                        println!(
+                            "{}\n",
+                            "Below are the available template names in your credentials and region"
+                                .yellow()
+                                .bold()
+                        );
+                        let available = templates::list_names(ctx).await?;
+                        for template_name in available {
+                            println!("    {}", template_name.green().bold());
                         }
-
-                        "Create a Contact List Name\n" => {
-                            let lst_name = Text::new(
-                                "Enter the list name to add to the AWS Simple Email Service\n",
-                            )
-                            .with_placeholder("The name should be unique\n")
-                            .with_formatter(&|str| format!(".....{str}.....\n"))
-                            .with_help_message("This is where the emails are stored")
-                            .prompt()
-                            .unwrap();
-                            let description = Text::new("Small Description about the list name\n")
-            .with_placeholder("Eg: A list named 'Zone Email Contacts' is used to add the emails\nof people in a specific area but can be skipped\n")
-            .with_formatter(&|str| format!(".....{str}.....\n"))
-            .prompt_skippable()
-            .unwrap()
-            .unwrap();
-                            match (lst_name.is_empty(), description.is_empty()) {
-                                (false, false) => {
-                                    ses_ops
-                                        .create_contact_list_name(&lst_name, Some(description))
-                                        .await;
-                                }
-                                (false, true) => {
-                                    ses_ops.create_contact_list_name(&lst_name, None).await;
-                                }
-                                _ => println!(
-                                    "{}\n",
-                                    "Contact Name Can't be empty..try again".red().bold()
-                                ),
+                        println!();
+                    }
+                }
+                _ => {
+                    println!("{}\n", "Fields should not be left empty".red().bold())
+                }
+            }
+        }
+        "Delete Contact\n" => {
+            let get_available_contact_lists = contact_lists::list_names(ctx).await?;
+            let format_it = format!(
+                "The contact list name below is available in your credentials or region if any:\n    {}\n",
+                get_available_contact_lists.join("")
+            );
+            let placeholder = format!("Default Contact List Name Is: {}", ses_ops.get_list_name());
+            let contact_list = Text::new("Please enter the name of the contact list from which you want to delete the contact or email\n")
+                .with_placeholder(&format_it)
+                .with_formatter(&|input| format!("Received Contact List Name: {input}\n"))
+                .with_help_message(&placeholder)
+                .prompt_skippable()?
+                .unwrap_or_default();
+            match contact_list.is_empty() {
+                false => {
+                    if contact_lists::exists(ctx, &contact_list).await? {
+                        let contact = Text::new(
+                            "Please enter the contact you wish to delete from the provided contact list\n",
+                        )
+                                .with_placeholder(
+                            "The contact is an email address stored in the specified contact list\n",
+                        )
+                                .with_formatter(&|input| format!("Received Contact or Email: {input}\n"))
+                                .prompt()?;
+                        match contact.is_empty() {
+                            false => {
+                                contact_lists::delete_contact(ctx, &contact_list, &contact).await?;
                             }
+                            true => println!("{}\n", "Email/Contact can't be empty".red().bold()),
                         }
-                        "Delete Contact List Name\n" => {
-                            let get_available_contact_lists = ses_ops.list_contact_lists().await;
-                            let contact_list_names = format!(
-                                "Available Contact List Names:\n{:#?}\n",
-                                get_available_contact_lists
-                            );
-                            let lst_name = Text::new("Enter the Contact List name to delete from AWS Simple Email Service\n")
-            .with_placeholder(&contact_list_names)
-            .with_formatter(&|str| format!(".....{str}.....\n"))
-            .with_help_message("This is where the emails are stored")
-            .prompt()
-            .unwrap();
-                            match lst_name.is_empty() {
-                                false => {
-                                    ses_ops.delete_contact_list_name(&lst_name).await;
-                                }
-                                true => println!(
-                                    "{}\n",
-                                    "Contact List Name can't be empty".red().bold()
-                                ),
-                            }
+                    } else {
+                        println!(
+                            "The provided Contact List Name '{}' doesn't exist",
+                            contact_list.red().bold()
+                        );
+                        println!(
+                            "{}\n",
+                            "The contact list name below is available in your credentials or region if any"
+                                .yellow()
+                                .bold()
+                        );
+                        let available = contact_lists::list_names(ctx).await?;
+                        for contact_list in available {
+                            println!("    {}", contact_list.green().bold());
                         }
-
-                        "Add an email to the list\n" => {
-                            let get_contact_list_name = ses_ops.get_list_name();
-                            let get_contact_list_name =
-                                format!("Default contact list name: {}\n", get_contact_list_name);
-                            let email = Text::new("Enter the email\n")
+                        println!();
+                    }
+                }
+                true => {
+                    if contact_lists::exists(ctx, ses_ops.get_list_name()).await? {
+                        let contact = Text::new(
+                            "Please enter the contact you wish to delete from the provided contact list\n",
+                        )
                                 .with_placeholder(
-                                    "Emails should be without quotation marks around them\n",
+                            "The contact is an email address stored in the specified contact list\n",
+                        )
+                                .with_formatter(&|input| format!("Received Contact or Email: {input}\n"))
+                                .prompt()?;
+                        match contact.is_empty() {
+                            false => {
+                                contact_lists::delete_contact(
+                                    ctx,
+                                    ses_ops.get_list_name(),
+                                    &contact,
                                 )
-                                .with_formatter(&|str| format!(".....{str}.....\n"))
-                                .prompt()
-                                .unwrap();
-                            let list_name =
-                                Text::new("Enter the list name you want the email add in it\n")
-                                    .with_placeholder(&get_contact_list_name)
-                                    .with_formatter(&|str| format!(".....{str}.....\n"))
-                                    .prompt_skippable()
-                                    .unwrap()
-                                    .unwrap();
-                            let to_verified = Confirm::new("Would you like to send the verification email as well?\n")
-                                   .with_formatter(&|str| format!(".....{str}.....\n"))
-                                   .with_placeholder("Selecting 'Yes' means you want to receive a verification, while choosing 'No' means your email will be added to the list without verification\n")
-                                   .prompt()
-                                   .unwrap();
-
-                            match (list_name.is_empty(), email.is_empty(), to_verified) {
-                                (false, false, false) => {
-                                    ses_ops
-                                        .create_email_contact_without_verification(
-                                            &email,
-                                            Some(&list_name),
-                                        )
-                                        .await;
-                                }
-                                (false, false, true) => {
-                                    ses_ops
-                                        .create_email_contact_with_verification(
-                                            &email,
-                                            Some(&list_name),
-                                        )
-                                        .await;
-                                }
-                                (true, false, false) => {
-                                    ses_ops
-                                        .create_email_contact_without_verification(&email, None)
-                                        .await;
-                                }
-                                (true, false, true) => {
-                                    ses_ops
-                                        .create_email_contact_with_verification(&email, None)
-                                        .await;
-                                }
-                                _ => println!("{}\n", "No email is received".red().bold()),
+                                .await?;
                             }
+                            true => println!("{}\n", "Email/Contact can't be empty".red().bold()),
                         }
-                        "Create Email Identity\n" => {
-                            let email = Text::new("Enter the email\n")
-                                .with_placeholder(
-                                    "Emails should be without quotation marks around them\n",
-                                )
-                                .with_formatter(&|str| format!(".....{str}.....\n"))
-                                .prompt()
-                                .unwrap();
-                            match email.is_empty() {
-                                false => {
-                                    ses_ops.create_email_identity(&email).await;
-                                }
-                                true => println!("{}\n", "Email Can't be empty"),
-                            }
+                    } else {
+                        println!(
+                            "The provided Contact List Name '{}' doesn't exist",
+                            contact_list.red().bold()
+                        );
+                        println!(
+                            "{}\n",
+                            "The contact list name below is available in your credentials or region if any"
+                                .yellow()
+                                .bold()
+                        );
+                        let available = contact_lists::list_names(ctx).await?;
+                        for contact_list in available {
+                            println!("    {}", contact_list.green().bold());
                         }
+                        println!();
+                    }
+                }
+            }
+        }
+        "Delete Contacts\n" => {
+            let get_available_contact_lists = contact_lists::list_names(ctx).await?;
+            let format_it = format!(
+                "The contact list name below is available in your credentials or region if any:\n    {}\n",
+                get_available_contact_lists.join("")
+            );
+            let placeholder = format!("Default Contact List Name Is: {}", ses_ops.get_list_name());
+            let contact_list = Text::new("Please enter the name of the contact list from which you want to delete all the contacts or emails within it\n")
+                .with_placeholder(&format_it)
+                .with_formatter(&|input| format!("Received Contact List Name: {input}\n"))
+                .with_help_message(&placeholder)
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let contact_list = match contact_list.is_empty() {
+                true => ses_ops.get_list_name(),
+                false => contact_list.as_str(),
+            };
+            contact_lists::delete_all_contacts(ctx, contact_list).await?;
+        }
+        "Update Email Template\n" => {
+            let get_available_template_names = templates::list_names(ctx).await?;
+            let placeholder_info = format!(
+                "Available Template Names in Your Credentials\n{:#?}",
+                get_available_template_names
+            );
+            let template_name =
+                Text::new("Please provide the template name to update the associated template\n")
+                    .with_placeholder(&placeholder_info)
+                    .with_formatter(&|input| format!("Received Template Name Is: {input}\n"))
+                    .prompt()?;
+            match template_name.is_empty() {
+                false => {
+                    let is_template_exist = templates::exists(ctx, &template_name).await?;
+                    if is_template_exist {
+                        let (current_subject, current_template_html, current_text) =
+                            templates::parts(ctx, &template_name).await?;
+                        let current_subject = format!(
+                            "Your current email template subject is:\n {}",
+                            current_subject
+                        );
+                        let subject_path =Text::new("Please provide the path to the subject data in JSON or HTML format to update\n")
+                                .with_placeholder(&current_subject)
+                                .with_formatter(&|input| format!("Received Subject Path: {input}\n"))
+                                .prompt()?;
+                        let current_template_variables = render::variables(&current_template_html);
+                        let current_template_variables = format!("These are the current template variables in the template named '{}'\n{}",template_name,current_template_variables.join("\n"));
+                        let template_path = Text::new("Please provide the path for the template in JSON or HTML format to update it with the old one\n")
+                                      .with_formatter(&|input| format!("Received Template Path Is: {input}\n"))
+                                      .with_placeholder(&current_template_variables)
+                                      .with_help_message("Example template is available at this location: https://tinyurl.com/4ssuz7fy")
+                                      .prompt()?;
+                        let current_text = if current_text.len() > 50 {
+                            let format_text = current_text.lines().take(10).collect::<String>();
+                            format!("Your current email template text is:\n{}\n", format_text)
+                        } else {
+                            format!("Your current email template text is:\n{}\n", current_text)
+                        };
+                        let text_path =Text::new("Please provide the path to the text body for the email template\n")
+                                .with_placeholder(&current_text)
+                                .with_help_message("This section is optional, but it's essential to include for recipients who do not support HTML")
+                                .with_formatter(&|input| format!("Received Text Body Is: {input}\n"))
+                                .prompt_skippable()?
+                            .unwrap_or_default();
+                        let template_data = read_file(&template_path, "Template")?;
+                        let subject_data = read_file(&subject_path, "Subject")?;
 
-                        "Email Verification\n" => {
-                            let email_to_verify =
-                                Text::new("Enter the email to check the identity\n")
-                                    .with_placeholder("Only verified email can receive email\n")
-                                    .with_formatter(&|str| format!(".....{str}.....\n"))
-                                    .prompt()
-                                    .unwrap();
-                            match email_to_verify.is_empty() {
-                                false => {
-                                    let available_email_identies =
-                                        ses_ops.retrieve_emails_from_list_email_identities().await;
-                                    if available_email_identies.contains(&email_to_verify) {
-                                        match ses_ops.is_email_verfied(&email_to_verify).await {
-                                            Some(status) => {
-                                                if status {
-                                                    let email_to_verify =
-                                                        email_to_verify.green().bold();
-                                                    println!("The email address {email_to_verify} has been verified\n");
-                                                    println!(" You can use it to receive messages or as a 'from' address\n");
-                                                } else {
-                                                    let email_to_verify =
-                                                        email_to_verify.green().bold();
-                                                    println!("The email address {email_to_verify} is not verified\n");
-                                                    println!("Therefore, you can't use it to send emails ('from' address) or receive messages\n");
-                                                }
-                                            }
-                                            None => {}
-                                        }
-                                    } else {
-                                        println!(
-                                            "No identity was found for the email '{}'",
-                                            email_to_verify.red().bold()
-                                        );
-                                        println!("{}\n","Please execute the 'create email identity' option before verifying this email".yellow().bold());
-                                    }
-                                }
-                                true => {
-                                    println!("{}\n", "The email can't be empty".red().bold())
-                                }
-                            }
+                        let text = match text_path.is_empty() {
+                            false => Some(read_file(&text_path, "Text Body")?),
+                            true => None,
+                        };
+                        templates::update(ctx, &template_name, &subject_data, &template_data, text)
+                            .await?;
+                    } else {
+                        println!(
+                            "The template named '{}' doesn't exist",
+                            template_name.red().bold()
+                        );
+                        println!(
+                            "{}\n",
+                            "Here are the available template names in your credentials and region"
+                                .yellow()
+                                .bold()
+                        );
+                        let available = templates::list_names(ctx).await?;
+                        for template_name in available {
+                            println!("    {}", template_name.green().bold());
                         }
-
-                        "Retrieve emails from the provided list\n" => {
-                            let get_contact_list_name = ses_ops.get_list_name();
-                            let get_contact_list_name =
-                                format!("Default contact list name: {}\n", get_contact_list_name);
-                            let list_name = Text::new("Please enter the name of the list for which you'd like to receive these emails in PDF and text formats\n")
-                               .with_placeholder(&get_contact_list_name)
-                               .with_formatter(&|str| format!(".....{str}....."))
-                               .prompt_skippable()
-                               .unwrap()
-                               .unwrap();
-                            match list_name.is_empty() {
-                                false => {
-                                    ses_ops
-                                        .writing_email_addresses_from_provided_list_as_text_pdf(
-                                            Some(&list_name),
-                                        )
-                                        .await;
-                                }
-                                true => {
-                                    ses_ops
-                                        .writing_email_addresses_from_provided_list_as_text_pdf(
-                                            None,
-                                        )
-                                        .await;
-                                }
+                        println!();
+                    }
+                }
+                true => {
+                    println!("{}\n", "Template Name can't be empty".red().bold())
+                }
+            }
+        }
+        "Get Email Template\n" => {
+            let get_available_template_names = templates::list_names(ctx).await?;
+            let placeholder_info = format!(
+                "Available Template Names in Your Credentials\n{:#?}",
+                get_available_template_names
+            );
+            let template_name = Text::new("Please provide the template name for which you want to download the HTML, subject, and text parts\n")
+                .with_placeholder(&placeholder_info)
+                .with_formatter(&|input| {
+                    format!("Received Template Name: {input}\n")
+                })
+                    .prompt()?;
+            match template_name.is_empty() {
+                false => {
+                    templates::download(ctx, &template_name).await?;
+                }
+                true => {
+                    println!("{}\n", "Template Name can't be empty".red().bold())
+                }
+            }
+        }
+        "Get Email Template Variables\n" => {
+            let get_available_template_names = templates::list_names(ctx).await?;
+            let placeholder_info = format!(
+                "Available Template Names in Your Credentials\n{:#?}",
+                get_available_template_names
+            );
+            let template_name = Text::new(
+                "Please provide the template name for which you want to retrieve template variables\n",
+            )
+                    .with_placeholder(&placeholder_info)
+                    .with_help_message("Look for the placeholder to identify the available template names in your credentials")
+                    .with_formatter(&|input| {
+                format!("Received Template Name Is: {input}\n")
+            })
+                .prompt_skippable()?
+                .unwrap_or_default();
+            match template_name.is_empty() {
+                false => templates::print_variables(ctx, &template_name).await?,
+                true => {
+                    println!("{}\n", "Template Name can't be empty".red().bold())
+                }
+            }
+        }
+        "Delete Template\n" => {
+            let get_available_template_names = templates::list_names(ctx).await?;
+            let placeholder_info =
+                format!("Available Templates \n{:#?}", get_available_template_names);
+            let template_name = Text::new("Please provide the template name for deletion\n")
+                .with_placeholder(&placeholder_info)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            match template_name.is_empty() {
+                false => {
+                    let is_template_name_exist = templates::exists(ctx, &template_name).await?;
+                    match is_template_name_exist {
+                        true => {
+                            templates::download(ctx, &template_name).await?;
+                            templates::delete(ctx, &template_name).await?;
+                            println!("{}\n","The template has been downloaded to your current directory in case you need it".yellow().bold());
+                        }
+                        false => {
+                            println!(
+                                "The template named '{}' doesn't exist",
+                                template_name.red().bold()
+                            );
+                            println!(
+                                "{}\n",
+                                "Here are the available template names in your credentials or region"
+                                    .yellow()
+                                    .bold()
+                            );
+                            let available = templates::list_names(ctx).await?;
+                            for template_name in available {
+                                println!("    {}", template_name.green().bold());
                             }
+                            println!();
                         }
-                        "Default Values\n" => {
-                            let default_list_name = ses_ops.get_list_name().green().bold();
-                            let default_template_name = ses_ops.get_template_name().green().bold();
-                            let default_from_address = ses_ops.get_from_address().green().bold();
-                            println!("Default Contact List Name: {default_list_name}\n");
-                            println!("Default Template Name: {default_template_name}\n");
-                            println!("Default from_address is: {default_from_address}\n");
+                    }
+                }
+                true => {
+                    println!("{}\n", "Template Name can't be empty".red().bold())
+                }
+            }
+        }
 
-                            println!("{}\n","Be sure to check the placeholder for default values, allowing you to skip using the default value".bright_blue());
+        "Create a Contact List Name\n" => {
+            let lst_name =
+                Text::new("Enter the list name to add to the AWS Simple Email Service\n")
+                    .with_placeholder("The name should be unique\n")
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .with_help_message("This is where the emails are stored")
+                    .prompt()?;
+            let description = Text::new("Small Description about the list name\n")
+                .with_placeholder("Eg: A list named 'Zone Email Contacts' is used to add the emails\nof people in a specific area but can be skipped\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            match lst_name.is_empty() {
+                false => {
+                    let topics = prompt_topics()?;
+                    contact_lists::create(ctx, &lst_name, Some(&description), topics).await?;
+                }
+                true => println!(
+                    "{}\n",
+                    "Contact Name Can't be empty..try again".red().bold()
+                ),
+            }
+        }
+        "Update a Contact List\n" => {
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let list_name = Text::new("Please provide the name of the Contact List\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty())
+                .unwrap_or(ses_ops.get_list_name().into());
+            let existing = contact_lists::topic_names(ctx, &list_name).await?;
+            let removed = match existing.is_empty() {
+                true => Vec::new(),
+                false => MultiSelect::new("Please select the topics to remove, if any\n", existing)
+                    .prompt()?,
+            };
+            let description =
+                Text::new("Please enter the new description or skip it to keep the current one\n")
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt_skippable()?
+                    .filter(|description| !description.is_empty());
+            let update = ListUpdate {
+                description,
+                topics: prompt_topics()?,
+                removed_topics: removed,
+            };
+            contact_lists::update(ctx, &list_name, update).await?;
+        }
+        "Copy a Contact List\n" => {
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let from = Text::new("Please provide the name of the Contact List to copy\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty())
+                .unwrap_or(ses_ops.get_list_name().into());
+            let to = Text::new("Please provide the name of the new Contact List\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let region = Text::new(
+                "Please provide the region of the new list or skip it to keep the current region\n",
+            )
+            .with_placeholder("Eg: eu-west-1\n")
+            .with_help_message("SES allows a single contact list per region")
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt_skippable()?
+            .filter(|region| !region.is_empty());
+            let question = match region {
+                None => format!("SES allows one contact list per region, so '{from}' is deleted before '{to}' is created, its contacts are backed up to a JSON file first. Continue?\n"),
+                Some(_) => format!("Would you like to delete '{from}' once it is copied?\n"),
+            };
+            let delete_source = Confirm::new(&question).with_default(false).prompt()?;
+            let copy = ListCopy {
+                from: &from,
+                to: &to,
+                region: region.as_deref(),
+                delete_source,
+            };
+            contact_lists::copy(ctx, &copy).await?;
+        }
+        "Search Contacts\n" => {
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let list_name = Text::new("Please provide the name of the Contact List\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty())
+                .unwrap_or(ses_ops.get_list_name().into());
+            let filter = prompt_filter()?;
+            let emails = matching_contacts(ctx, &list_name, &filter).await?;
+            let page_size = 50;
+            let mut page = 1;
+            loop {
+                page = print_page(&emails, page, page_size);
+                let choice = Select::new(
+                    "What would you like to do with the matching contacts?\n",
+                    vec![
+                        "Next Page",
+                        "Previous Page",
+                        "Write the Emails to a File",
+                        "Delete the Matching Contacts",
+                        "Return to the SES Menu",
+                    ],
+                )
+                .prompt()?;
+                match choice {
+                    "Next Page" => page += 1,
+                    "Previous Page" => page = page.saturating_sub(1),
+                    "Write the Emails to a File" => {
+                        let path = Text::new("Please provide the path of the file\n")
+                            .with_placeholder("Eg: selection.txt\n")
+                            .with_formatter(&|str| format!(".....{str}.....\n"))
+                            .prompt()?;
+                        write_emails(&path, &emails)?;
+                    }
+                    "Delete the Matching Contacts" => {
+                        let confirmed = Confirm::new(&format!(
+                            "Delete the {} matching contacts from '{list_name}'?\n",
+                            emails.len()
+                        ))
+                        .with_default(false)
+                        .prompt()?;
+                        if confirmed {
+                            contact_lists::delete_contacts(ctx, &list_name, emails).await?;
                         }
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+        "Get a Contact\n" | "Update a Contact\n" => {
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let email = Text::new("Enter the email of the contact\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let list_name = Text::new("Please provide the name of the Contact List\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty())
+                .unwrap_or(ses_ops.get_list_name().into());
+            if email_choice == "Get a Contact\n" {
+                return contact_lists::show_contact(ctx, &list_name, &email).await;
+            }
+            contact_lists::show_contact(ctx, &list_name, &email).await?;
+            let attributes =
+                Text::new("Please enter the attributes to add as a JSON object or skip it\n")
+                    .with_placeholder("Eg: {\"first_name\": \"Ann\", \"plan\": null}\n")
+                    .with_help_message("A null value removes the attribute")
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt_skippable()?
+                    .unwrap_or_default();
+            let attributes = match attributes.trim().is_empty() {
+                true => Default::default(),
+                false => parse_attributes(&attributes)?,
+            };
+            let replace_attributes = !attributes.is_empty()
+                && Confirm::new("Would you like to replace every attribute with these?\n")
+                    .with_default(false)
+                    .prompt()?;
+            let unsubscribe_all = Select::new(
+                "Please select the unsubscribe-all flag\n",
+                vec!["Keep it", "Unsubscribe from every topic", "Subscribe again"],
+            )
+            .prompt()?;
+            let unsubscribe_all = match unsubscribe_all {
+                "Unsubscribe from every topic" => Some(true),
+                "Subscribe again" => Some(false),
+                _ => None,
+            };
+            let preferences = match unsubscribe_all {
+                Some(true) => BTreeMap::new(),
+                _ => prompt_preferences(ctx, &list_name).await?,
+            };
+            let update = ContactUpdate {
+                attributes,
+                replace_attributes,
+                preferences,
+                unsubscribe_all,
+            };
+            contact_lists::update_contact(ctx, &list_name, &email, &update).await?;
+        }
+        "Describe a Contact List\n" => {
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let list_name = Text::new("Please provide the name of the Contact List\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty())
+                .unwrap_or(ses_ops.get_list_name().into());
+            contact_lists::describe(ctx, &list_name).await?;
+        }
+        "Delete Contact List Name\n" => {
+            let get_available_contact_lists = contact_lists::list_names(ctx).await?;
+            let contact_list_names = format!(
+                "Available Contact List Names:\n{:#?}\n",
+                get_available_contact_lists
+            );
+            let lst_name =
+                Text::new("Enter the Contact List name to delete from AWS Simple Email Service\n")
+                    .with_placeholder(&contact_list_names)
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .with_help_message("This is where the emails are stored")
+                    .prompt()?;
+            match lst_name.is_empty() {
+                false => {
+                    contact_lists::delete(ctx, &lst_name).await?;
+                }
+                true => println!("{}\n", "Contact List Name can't be empty".red().bold()),
+            }
+        }
 
-                        "Send a Single Simple Email\n" => {
-                            let email = Text::new("Enter the email..\n")
-                        .with_formatter(&|str| format!(".....{str}....."))
-                        .with_placeholder(
-                            "The provided email should be verified through the 'Create Email Identity' option",
-                        )
-                        .prompt()
-                        .unwrap();
-                            let email_contacts =
-                                ses_ops.retrieve_emails_from_list_email_identities().await;
-                            match email.is_empty() {
-                                false => {
-                                    if email_contacts.contains(&email) {
-                                        let subject = Text::new("Enter the subject of Email\n")
-                                .with_placeholder(
-                                    "Eg: For testing purposes, we have launched a new product",
-                                )
-                                .with_formatter(&|str| format!(".....{str}....."))
-                                .prompt()
-                                .unwrap();
+        "Add an email to the list\n" => {
+            let get_contact_list_name = ses_ops.get_list_name();
+            let get_contact_list_name =
+                format!("Default contact list name: {}\n", get_contact_list_name);
+            let email = Text::new("Enter the email\n")
+                .with_placeholder("Emails should be without quotation marks around them\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let list_name = Text::new("Enter the list name you want the email add in it\n")
+                .with_placeholder(&get_contact_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let to_verified = Confirm::new("Would you like to send the verification email as well?\n")
+                   .with_formatter(&|str| format!(".....{str}.....\n"))
+                   .with_placeholder("Selecting 'Yes' means you want to receive a verification, while choosing 'No' means your email will be added to the list without verification\n")
+                   .prompt()?;
 
-                                        let defaul_from_address = ses_ops.get_from_address();
+            if email.is_empty() {
+                println!("{}\n", "No email is received".red().bold());
+                return Ok(());
+            }
+            let list_name = match list_name.is_empty() {
+                true => ses_ops.get_list_name().to_string(),
+                false => list_name,
+            };
+            let unsubscribe_all =
+                Confirm::new("Would you like to unsubscribe the contact from every topic?\n")
+                    .with_default(false)
+                    .prompt()?;
+            let preferences = match unsubscribe_all {
+                true => BTreeMap::new(),
+                false => prompt_preferences(ctx, &list_name).await?,
+            };
+            contact_lists::add_contact(ctx, &list_name, &email, &preferences, unsubscribe_all)
+                .await?;
+            if to_verified {
+                identities::create(ctx, &email).await?;
+            }
+        }
+        "Import Contacts from a CSV or JSON File\n" => {
+            let get_contact_list_name =
+                format!("Default contact list name: {}\n", ses_ops.get_list_name());
+            let file_path = Text::new("Please enter the path to the CSV or JSON file with the contacts\n")
+                .with_placeholder("A CSV file needs an 'email' column, the other columns become attributes unless they are named 'unsubscribe_all' or 'topic:<name>'\n")
+                .with_help_message("A JSON file is an array of objects with 'email' and the optional 'attributes', 'topics' and 'unsubscribe_all' fields")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let list_name = Text::new("Enter the list name you want the contacts imported into\n")
+                .with_placeholder(&get_contact_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let list_name = match list_name.is_empty() {
+                true => ses_ops.get_list_name().to_string(),
+                false => list_name,
+            };
+            match contact_lists::exists(ctx, &list_name).await? {
+                true => import::import_contacts(ctx, &list_name, &file_path, 10, 3).await?,
+                false => println!(
+                    "The provided Contact List Name '{}' doesn't exist\n",
+                    list_name.red().bold()
+                ),
+            }
+        }
+        "Create Email Identity\n" => {
+            let email = Text::new("Enter the email\n")
+                .with_placeholder("Emails should be without quotation marks around them\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            match email.is_empty() {
+                false => identities::create(ctx, &email).await?,
+                true => println!("Email Can't be empty\n"),
+            }
+        }
+
+        "Email Verification\n" => {
+            let email_to_verify = Text::new("Enter the email to check the identity\n")
+                .with_placeholder("Only verified email can receive email\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            match email_to_verify.is_empty() {
+                false => {
+                    let available_email_identies = identities::identity_names(ctx).await?;
+                    if available_email_identies.contains(&email_to_verify) {
+                        if identities::is_verified(ctx, &email_to_verify).await? {
+                            let email_to_verify = email_to_verify.green().bold();
+                            println!("The email address {email_to_verify} has been verified\n");
+                            println!(
+                                " You can use it to receive messages or as a 'from' address\n"
+                            );
+                        } else {
+                            let email_to_verify = email_to_verify.green().bold();
+                            println!("The email address {email_to_verify} is not verified\n");
+                            println!("Therefore, you can't use it to send emails ('from' address) or receive messages\n");
+                        }
+                    } else {
+                        println!(
+                            "No identity was found for the email '{}'",
+                            email_to_verify.red().bold()
+                        );
+                        println!("{}\n","Please execute the 'create email identity' option before verifying this email".yellow().bold());
+                    }
+                }
+                true => {
+                    println!("{}\n", "The email can't be empty".red().bold())
+                }
+            }
+        }
 
-                                        let default_from_address =format!("Your 'from_address' needs to be verified, which is typically your email\nand the default 'from_address' is {}",defaul_from_address);
+        "Retrieve emails from the provided list\n" => {
+            let get_contact_list_name = ses_ops.get_list_name();
+            let get_contact_list_name =
+                format!("Default contact list name: {}\n", get_contact_list_name);
+            let list_name = Text::new("Please enter the name of the list for which you'd like to receive these emails in PDF and text formats\n")
+                .with_placeholder(&get_contact_list_name)
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let list_name = match list_name.is_empty() {
+                true => ses_ops.get_list_name(),
+                false => list_name.as_str(),
+            };
+            contact_lists::write_emails_as_text_pdf(ses_ops, ctx, list_name).await?;
+        }
+        "Export Contacts as CSV or JSON\n" => {
+            let get_contact_list_name =
+                format!("Default contact list name: {}\n", ses_ops.get_list_name());
+            let list_name = Text::new("Please enter the name of the list you want to export\n")
+                .with_placeholder(&get_contact_list_name)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let list_name = match list_name.is_empty() {
+                true => ses_ops.get_list_name().to_string(),
+                false => list_name,
+            };
+            let format = Select::new("Select the export format\n", vec!["CSV", "JSON"])
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let (format, default_path) = match format {
+                "CSV" => (ExportFormat::Csv, format!("{list_name}.csv")),
+                _ => (ExportFormat::Json, format!("{list_name}.json")),
+            };
+            let output = Text::new("Please enter the path of the file to write\n")
+                .with_placeholder(&format!(
+                    "Defaults to '{default_path}', use '-' to print it here\n"
+                ))
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let output = match output.is_empty() {
+                true => default_path,
+                false => output,
+            };
+            match contact_lists::exists(ctx, &list_name).await? {
+                true => export::export_contacts(ctx, &list_name, format, Some(&output)).await?,
+                false => println!(
+                    "The provided Contact List Name '{}' doesn't exist\n",
+                    list_name.red().bold()
+                ),
+            }
+        }
+        "Default Values\n" => {
+            ctx.defaults.print();
 
-                                        let from_address = Text::new("Please enter the 'From' address, or press Enter to use the default 'From' address, if one is available in the placeholder\n")
-                    .with_placeholder(&default_from_address)
-                    .with_formatter(&|str| format!(".....{str}....."))
-                    .prompt_skippable()
-                    .unwrap()
-                    .unwrap();
-                                        let body_info = Confirm::new("You can either provide the email body from a local file path or any S3 object URLs can be passed, and they should be publicly accessible. Not all links provide the exact content we requested\n")
+            println!("{}\n","Be sure to check the placeholder for default values, allowing you to skip using the default value".bright_blue());
+        }
+
+        "Send a Single Simple Email\n" => {
+            let email = Text::new("Enter the email..\n")
                 .with_formatter(&|str| format!(".....{str}....."))
-                .with_placeholder("Please respond with 'Yes' to provide a local file or 'No' to provide a S3 Object Url link\n")
-                .prompt()
-                .unwrap();
-                                        let from_address = match from_address.is_empty() {
-                                            true => None,
-                                            false => Some(from_address.as_str()),
-                                        };
-                                        match (subject.is_empty(), body_info) {
-                                            (false, true) => {
-                                                let body_path = Text::new("Please provide the path to the body of a simple email content file\n")
-                    .with_formatter(&|str| format!(".....{str}....."))
-                    .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
-                    .with_help_message("You can download a example simple email content here https://tinyurl.com/mr22bh4f")
-                    .prompt()
-                    .unwrap();
-                                                let mut reading_simple_data = OpenOptions::new()
-                    .read(true)
-                    .write(true)
-                    .open(&body_path)
-                    .expect("Error opening the simple email file path you specified");
-                                                let mut body_data = String::new();
-                                                reading_simple_data
-                                                    .read_to_string(&mut body_data)
-                                                    .expect("Error while reading to string\n");
-                                                let simple_email =
-                                                    SimpleMail::builder(&body_data, &subject)
-                                                        .build();
+                .with_placeholder(
+            "The provided email should be verified through the 'Create Email Identity' option",
+        )
+                .with_help_message("Separate several To addresses with commas")
+                .prompt()?;
+            let to = parse_addresses(&email);
+            let envelope = prompt_envelope(&ctx.defaults)?;
+            let unverified = unreachable_recipients(ctx, to.iter().chain(envelope.copies()))
+                .await?
+                .into_iter()
+                .next();
+            match to.is_empty() {
+                false => {
+                    if unverified.is_none() {
+                        let subject = Text::new("Enter the subject of Email\n")
+                            .with_placeholder(
+                                "Eg: For testing purposes, we have launched a new product",
+                            )
+                            .with_formatter(&|str| format!(".....{str}....."))
+                            .prompt()?;
+
+                        let defaul_from_address = ses_ops.get_from_address();
 
-                                                match ses_ops
-                                                    .send_mono_email(
-                                                        &email,
-                                                        Simple_(simple_email),
-                                                        from_address,
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(email_builder) => {
-                                                        email_builder
-                                .send()
-                        .await
-                        .map(|_|{
-                            let colored_email = email.green().bold();
-                            println!("A simple email has been successfully sent to '{}'\n{}\n",colored_email,"Please check your inbox to view it".yellow().bold())
-                          })
-                        .expect("Error while Sending Simple Email\n");
-                                                    }
-                                                    Err(msg) => println!("{}\n", msg),
-                                                }
-                                            }
-                                            (false, false) => {
-                                                let body_link = Text::new("Please provide the link to the body of a simple email content file\n")
-                                           .with_formatter(&|str| format!(".....{str}.....\n"))
-                                           .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
-                                           .with_help_message("Visit this link https://tinyurl.com/4whs2vkt to obtain an S3 URL that contains the simple email content")
-                                           .prompt()
-                                         .unwrap();
-                                                match get(&body_link).await{
-                                 Ok(body) => {
-                        let body_data = body.text().await.expect("Error while getting text data\n");
-                        let x: &[_] = &['\n','\r',' ','\x1b','\u{20}','\u{7f}','\u{80}'];
-                        let body_data = body_data.trim_matches(x);
-                        let simple_email = SimpleMail::builder(
-                          &body_data,
-                          &subject
-                          )
-                          .build();
-                        match ses_ops
-                        .send_mono_email(&email, Simple_(simple_email),from_address)
-                        .await{
-                            Ok(send_email_builder) => {
-                                send_email_builder
-                                .send()
-                        .await
-                        .map(|_|{
-                            let colored_email = email.green().bold();
-                            println!("A simple email has been successfully sent to '{}'\n{}\n",colored_email,"Please check your inbox to view it".yellow().bold())
-                           })
-                        .expect("Error While Sending Simple Email\n");
+                        let default_from_address =format!("Your 'from_address' needs to be verified, which is typically your email\nand the default 'from_address' is {}",defaul_from_address);
+
+                        let from_address = Text::new("Please enter the 'From' address, or press Enter to use the default 'From' address, if one is available in the placeholder\n")
+                            .with_placeholder(&default_from_address)
+                            .with_formatter(&|str| format!(".....{str}....."))
+                            .prompt_skippable()?
+                            .unwrap_or_default();
+                        let body_info = Confirm::new("You can either provide the email body from a local file path or any S3 object URLs can be passed, and they should be publicly accessible. Not all links provide the exact content we requested\n")
+                            .with_formatter(&|str| format!(".....{str}....."))
+                            .with_placeholder("Please respond with 'Yes' to provide a local file or 'No' to provide a S3 Object Url link\n")
+                            .prompt()?;
+                        let from_address = match from_address.is_empty() {
+                            true => None,
+                            false => Some(from_address.as_str()),
+                        };
+                        match (subject.is_empty(), body_info) {
+                            (false, true) => {
+                                let body_path = Text::new("Please provide the path to the body of a simple email content file\n")
+                                    .with_formatter(&|str| format!(".....{str}....."))
+                                    .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
+                                    .with_help_message("You can download a example simple email content here https://tinyurl.com/mr22bh4f")
+                                    .prompt()?;
+                                let body_data =
+                                    with_text_part(read_file(&body_path, "simple email")?)?;
+                                send::simple(
+                                    ses_ops,
+                                    ctx,
+                                    &to,
+                                    &subject,
+                                    &body_data,
+                                    &envelope,
+                                    from_address,
+                                )
+                                .await?;
                             }
-                            Err(msg) => {
-                                println!("{}",msg);
-                                println!("The verification email has been sent to: {}\n",email.green().bold());
+                            (false, false) => {
+                                let body_link = Text::new("Please provide the link to the body of a simple email content file\n")
+                                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                                    .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
+                                    .with_help_message("Visit this link https://tinyurl.com/4whs2vkt to obtain an S3 URL that contains the simple email content")
+                                    .prompt()?;
+                                let body_data = with_text_part(fetch_body(&body_link).await?)?;
+                                send::simple(
+                                    ses_ops,
+                                    ctx,
+                                    &to,
+                                    &subject,
+                                    &body_data,
+                                    &envelope,
+                                    from_address,
+                                )
+                                .await?;
                             }
-                        };
-                         
-
+                            _ => println!("Subject can't be empty\n"),
+                        }
+                    } else {
+                        println!("The provided email '{}' has not been verified. Please execute the 'Create Email Identity' option to verify the email address, and then proceed with this one\n",unverified.unwrap_or_default().yellow().bold());
                     }
-                    Err(_) => println!("{}\n","The provided link doesn't seem to be working. Could you please check the link and try again?".red().bold())
                 }
-                                            }
-                                            _ => println!("{}\n", "Subject can't be empty"),
-                                        }
-                                    } else {
-                                        println!("The provided email '{}' has not been verified. Please execute the 'Create Email Identity' option to verify the email address, and then proceed with this one\n",email.yellow().bold());
-                                    }
-                                }
-                                true => {
-                                    println!("{}\n", "Email can't be empty".red().bold());
-                                }
-                            }
-                        }
-                        "Get Email Identities\n" => {
-                            ses_ops.writing_email_identies_details_as_text_pdf().await;
-                            println!("{}\n","This option only returns the emails that are created either via the 'Create Email Identity' option or\nby choosing 'yes' in the 'Add an Email to the list' option when asked to send a verification email".yellow().bold());
-                        }
-                        "Send a Single Templated Email\n" => {
-                            let get_from_address = ses_ops.get_from_address();
-                            let get_template_name = ses_ops.get_template_name();
-                            let default_template_name =
-                                format!("Default template name is: {}", get_template_name);
-                            let default_from_address =
-                                format!("Default from_address is: {}", get_from_address);
-                            let email = Text::new("Enter the email you wish to send\n")
-                                .with_placeholder("The email must be verified")
-                                .with_formatter(&|str| format!(".....{str}....."))
-                                .prompt()
-                                .unwrap();
-                            let email_contacts =
-                                ses_ops.retrieve_emails_from_list_email_identities().await;
+                true => {
+                    println!("{}\n", "Email can't be empty".red().bold());
+                }
+            }
+        }
+        "Get Email Identities\n" => {
+            identities::write_identities_as_text_pdf(ses_ops, ctx).await?;
+            println!("{}\n","This option only returns the emails that are created either via the 'Create Email Identity' option or\nby choosing 'yes' in the 'Add an Email to the list' option when asked to send a verification email".yellow().bold());
+        }
+        "Send a Single Templated Email\n" => {
+            let get_from_address = ses_ops.get_from_address();
+            let get_template_name = ses_ops.get_template_name();
+            let default_template_name = format!("Default template name is: {}", get_template_name);
+            let default_from_address = format!("Default from_address is: {}", get_from_address);
+            let email = Text::new("Enter the email you wish to send\n")
+                .with_placeholder("The email must be verified")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .with_help_message("Separate several To addresses with commas")
+                .prompt()?;
+            let to = parse_addresses(&email);
+            let envelope = prompt_envelope(&ctx.defaults)?;
+            let unverified = unreachable_recipients(ctx, to.iter().chain(envelope.copies()))
+                .await?
+                .into_iter()
+                .next();
 
-                            match email.is_empty() {
-                                false => {
-                                    if email_contacts.contains(&email) {
-                                        let template_name = Text::new(
-                                    "Please enter the template name you want to use for the email\n",)
-                                .with_placeholder(&default_template_name)
+            match to.is_empty() {
+                false => {
+                    if unverified.is_none() {
+                        let template_name = Text::new(
+                    "Please enter the template name you want to use for the email\n",)
+                        .with_placeholder(&default_template_name)
+                        .with_formatter(&|str| format!(".....{str}.....\n"))
+                        .with_help_message(
+                    "The template name must exist, and the variables should be specified as key-value pairs according to the template\n",
+                )
+                        .prompt()?;
+                        let from_address = Text::new("Enter the from address\n")
+                            .with_placeholder(&default_from_address)
+                            .with_formatter(&|str| format!(".....{str}.....\n"))
+                            .prompt_skippable()?
+                            .unwrap_or_default();
+                        let placeholder_info = format!(
+                            "The template variables should reflect the '{}' template",
+                            template_name
+                        );
+                        let template_path = Text::new(
+                            "You can provide the path to the template data in JSON format\n",
+                        )
+                        .with_formatter(&|str| format!(".....{str}.....\n"))
+                        .with_placeholder(&placeholder_info)
+                        .prompt()?;
+                        let contact_list = match to.len() {
+                            1 => Text::new("Please provide the contact list of the recipient to use its attributes as template data, or skip it\n")
+                                .with_help_message("The values of the template data file override the attributes")
                                 .with_formatter(&|str| format!(".....{str}.....\n"))
-                                .with_help_message(
-                                    "The template name must exist, and the variables should be specified as key-value pairs according to the template\n",
-                                )
-                                .prompt()
-                                .unwrap();
-                                        let from_address = Text::new("Enter the from address\n")
-                                            .with_placeholder(&default_from_address)
-                                            .with_formatter(&|str| format!(".....{str}.....\n"))
-                                            .prompt_skippable()
-                                            .unwrap()
-                                            .unwrap();
-                                        let placeholder_info = format!(
-                                "The template variables should reflect the '{}' template",
-                                template_name
-                            );
-                                        let template_path = Text::new(
-                        "You can provide the path to the template data in JSON format\n",
-                    )
-                    .with_formatter(&|str| format!(".....{str}.....\n"))
-                    .with_placeholder(&placeholder_info)
-                    .prompt()
-                    .unwrap();
-
-                                        match (
-                                            template_name.is_empty(),
-                                            from_address.is_empty(),
-                                            template_path.is_empty(),
-                                        ) {
-                                            (false, false, false) => {
-                                                let mut reading_template_data = OpenOptions::new()
-                                                    .read(true)
-                                                    .write(true)
-                                                    .open(&template_path)
-                                                    .expect(
-                                                        "Error opening the file path you specified",
-                                                    );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading data\n");
+                                .prompt_skippable()?
+                                .unwrap_or_default(),
+                            _ => String::new(),
+                        };
 
-                                                let email_content = TemplateMail::builder(
-                                                    &template_name,
-                                                    &template_data,
-                                                )
-                                                .build();
-                                                match ses_ops
-                                                    .send_mono_email(
-                                                        &email,
-                                                        Template_(email_content),
-                                                        Some(&from_address),
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(email_builder) => {
-                                                        email_builder
-                                                            .send()
-                                                    .await
-                                                    .map(|_| {
-                                                        let colored_email = email.green().bold();
-                                                        println!(
-                                                            "The template email is send to: {}\n",
-                                                            colored_email
-                                                        )
-                                                    })
-                                                    .expect("Error while sending template mail\n");
-                                                    }
-                                                    Err(msg) => {
-                                                        println!("{}", msg);
-                                                        println!("The verification email has been sent to: {}\n",email.green().bold());
-                                                    }
-                                                }
-                                            }
-                                            (false, true, false) => {
-                                                let mut reading_template_data = OpenOptions::new()
-                            .read(true)
-                            .write(true)
-                            .open(&template_path)
-                            .expect(
-                                "Error opening the Template file path you specified",
-                            );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading data\n");
-                                                let email_content = TemplateMail::builder(
-                                                    &template_name,
-                                                    &template_data,
-                                                )
-                                                .build();
-                                                match ses_ops
-                                                    .send_mono_email(
-                                                        &email,
-                                                        Template_(email_content),
-                                                        None,
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(email_builder) => {
-                                                        email_builder
-                                                            .send()
-                                                        .await
-                                                        .map(|_| {
-                                                            let colored_email =
-                                                                email.green().bold();
-                                                            println!(
-                                                    "The template email is send to: {}\n",
-                                                    colored_email
-                                                )
-                                                        })
-                                                        .expect(
-                                                            "Error while sending template mail\n",
-                                                        );
-                                                    }
-                                                    Err(msg) => {
-                                                        println!("{}", msg);
-                                                        println!("The verification email has been sent to: {}\n",email.green().bold());
-                                                    }
-                                                }
-                                            }
-                                            (true, true, false) => {
-                                                let mut reading_template_data = OpenOptions::new()
-                                                    .read(true)
-                                                    .write(true)
-                                                    .open(&template_path)
-                                                    .expect(
-                                                        "Error opening the file path you specified",
-                                                    );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading data\n");
-                                                let email_content = TemplateMail::builder(
-                                                    &get_template_name,
-                                                    &template_data,
-                                                )
-                                                .build();
-                                                match ses_ops
-                                                    .send_mono_email(
-                                                        &email,
-                                                        Template_(email_content),
-                                                        None,
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(email_builder) => {
-                                                        email_builder
-                                                        .send()
-                                                    .await
-                                                    .map(|_| {
-                                                        let colored_email = email.green().bold();
-                                                        println!(
-                                                            "The template email is send to: {}\n",
-                                                            colored_email
-                                                        )
-                                                    })
-                                                    .expect("Error while sending template mail\n");
-                                                    }
-                                                    Err(msg) => {
-                                                        println!("{}", msg);
-                                                        println!("The verification email has been sent to: {}\n",email.green().bold());
-                                                    }
-                                                }
-                                            }
-                                            (true, false, false) => {
-                                                let mut reading_template_data = OpenOptions::new()
-                                                    .read(true)
-                                                    .write(true)
-                                                    .open(&template_path)
-                                                    .expect(
-                                                        "Error opening the file path you specified",
-                                                    );
-                                                let mut template_data = String::new();
-                                                reading_template_data
-                                                    .read_to_string(&mut template_data)
-                                                    .expect("Error while reading data\n");
-                                                let email_content = TemplateMail::builder(
-                                                    &get_template_name,
-                                                    &template_data,
-                                                )
-                                                .build();
-                                                match ses_ops
-                                                    .send_mono_email(
-                                                        &email,
-                                                        Template_(email_content),
-                                                        Some(&from_address),
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(email_builder) => {
-                                                        email_builder
-                                                            .send()
-                                                    .await
-                                                    .map(|_| {
-                                                        let colored_email = email.green().bold();
-                                                        println!(
-                                                            "The template email is send to: {}\n",
-                                                            colored_email
-                                                        )
-                                                    })
-                                                    .expect("Error while sending template mail\n");
-                                                    }
-                                                    Err(msg) => {
-                                                        println!("{}", msg);
-                                                        println!("The verification email has been sent to: {}\n",email.green().bold());
-                                                    }
-                                                }
-                                            }
-                                            _ => {
-                                                println!("{}\n","Please ensure that the fields are not empty, and then try again.".red().bold());
-                                            }
-                                        }
-                                    } else {
-                                        println!("The provided email '{}' has not been verified. Please execute the 'Create Email Identity' option to verify the email address, and then proceed with this one\n",email.yellow().bold());
+                        match template_path.is_empty() && contact_list.is_empty() {
+                            false => {
+                                let template_data = Some(template_path.as_str())
+                                    .filter(|path| !path.is_empty())
+                                    .map(|path| read_file(path, "Template"))
+                                    .transpose()?;
+                                let template_data = match contact_list.is_empty() {
+                                    true => template_data.unwrap_or_default(),
+                                    false => {
+                                        contact_template_data(
+                                            ctx,
+                                            &contact_list,
+                                            &to[0],
+                                            template_data.as_deref(),
+                                        )
+                                        .await?
                                     }
-                                }
-                                true => println!("{}\n", "Email can't be empty".red().bold()),
+                                };
+                                let template_name =
+                                    Some(template_name.as_str()).filter(|name| !name.is_empty());
+                                let from_address =
+                                    Some(from_address.as_str()).filter(|from| !from.is_empty());
+                                send::templated(
+                                    ses_ops,
+                                    ctx,
+                                    &to,
+                                    template_name,
+                                    &template_data,
+                                    &envelope,
+                                    from_address,
+                                )
+                                .await?;
+                            }
+                            true => {
+                                println!("{}\n","Please ensure that the fields are not empty, and then try again.".red().bold());
                             }
                         }
-                        "Send a Bulk of Simple Emails\n" => {
-                            let get_from_address = ses_ops.get_from_address();
-                            let get_list_name = ses_ops.get_list_name();
+                    } else {
+                        println!("The provided email '{}' has not been verified. Please execute the 'Create Email Identity' option to verify the email address, and then proceed with this one\n",unverified.unwrap_or_default().yellow().bold());
+                    }
+                }
+                true => println!("{}\n", "Email can't be empty".red().bold()),
+            }
+        }
+        "Send a Bulk of Simple Emails\n" => {
+            let get_from_address = ses_ops.get_from_address();
+            let get_list_name = ses_ops.get_list_name();
 
-                            let default_from_address =
-                                format!("Default from_address is: {}\n", get_from_address);
-                            let default_list_name =
-                                format!("Default list name is: {}\n", get_list_name);
+            let default_from_address = format!("Default from_address is: {}\n", get_from_address);
+            let default_list_name = format!("Default list name is: {}\n", get_list_name);
 
-                            let list_name = Text::new("Please provide the name of the Contact List where all your verified emails are stored\n")
-            .with_placeholder(&default_list_name)
-            .with_formatter(&|input| format!("The Simple Email Content will be sent to each email address in the: {input} Contact List\n"))
-            .prompt_skippable()
-            .unwrap()
-            .unwrap_or(ses_ops.get_list_name().into());
-                            let body_info = Confirm::new("You can either provide the email body from a local file path or any S3 object URLs can be passed, and they should be publicly accessible. Not all links provide the exact content we requested\n")
-        .with_formatter(&|str| format!(".....{str}....."))
-        .with_placeholder("Please respond with 'Yes' to provide a local file or 'No' to provide a S3 Object Url link\n")
-        .with_help_message("The body data is the same for all emails in the list")
-        .prompt()
-        .unwrap();
-                            //println!("The emails in the provided list should be verified; otherwise, the operation may fail. You can choose to skip this step by providing empty input and then proceed with the 'Get Email Identities' option\n".yellow().bold());
-                            let subject = Text::new("Please enter the subject content that all your subscribers should be aware of\n")
+            let list_name = Text::new("Please provide the name of the Contact List where all your verified emails are stored\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|input| format!("The Simple Email Content will be sent to each email address in the: {input} Contact List\n"))
+                .prompt_skippable()?
+                .unwrap_or(ses_ops.get_list_name().into());
+            let body_info = Confirm::new("You can either provide the email body from a local file path or any S3 object URLs can be passed, and they should be publicly accessible. Not all links provide the exact content we requested\n")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .with_placeholder("Please respond with 'Yes' to provide a local file or 'No' to provide a S3 Object Url link\n")
+                .with_help_message("The body data is the same for all emails in the list")
+                .prompt()?;
+            //println!("The emails in the provided list should be verified; otherwise, the operation may fail. You can choose to skip this step by providing empty input and then proceed with the 'Get Email Identities' option\n".yellow().bold());
+            let subject = Text::new(
+                "Please enter the subject content that all your subscribers should be aware of\n",
+            )
             .with_placeholder("The subject is the same for all emails\n")
             .with_formatter(&|str| format!(".....{str}.....\n"))
-            .prompt()
-            .unwrap();
-                            let from_address = Text::new("Enter the from address\n")
-                                .with_placeholder(&default_from_address)
-                                .with_formatter(&|str| format!(".....{str}.....\n"))
-                                .prompt_skippable()
-                                .unwrap()
-                                .unwrap();
-
-                            match (subject.is_empty(), body_info) {
-                                (false, true) => {
-                                    let body_path = Text::new("Please provide the path to the body of a simple email content file\n")
-                .with_formatter(&|str| format!(".....{str}....."))
-                .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
-                .with_help_message("You can download a example simple email content here https://tinyurl.com/mr22bh4f")
-                .prompt()
-                .unwrap();
-                                    let body_data = std::fs::read_to_string(&body_path).expect(
-                                        "Error Opening the simple email file path you specified\n",
-                                    );
-                                    let simple_data = SimpleMail::builder(&body_data, &subject);
-
-                                    match (list_name.is_empty(), from_address.is_empty()) {
-                                        (false, false) => {
-                                            ses_ops
-                                                .send_multi_email_with_simple(
-                                                    simple_data,
-                                                    Some(&from_address),
-                                                    Some(&list_name),
-                                                )
-                                                .await;
-                                        }
-                                        (true, true) => {
-                                            ses_ops
-                                                .send_multi_email_with_simple(
-                                                    simple_data,
-                                                    None,
-                                                    None,
-                                                )
-                                                .await;
-                                        }
-                                        (false, true) => {
-                                            ses_ops
-                                                .send_multi_email_with_simple(
-                                                    simple_data,
-                                                    None,
-                                                    Some(&list_name),
-                                                )
-                                                .await;
-                                        }
-                                        (true, false) => {
-                                            ses_ops
-                                                .send_multi_email_with_simple(
-                                                    simple_data,
-                                                    Some(&from_address),
-                                                    None,
-                                                )
-                                                .await;
-                                        }
-                                    }
-                                }
-                                (false, false) => {
-                                    let body_link = Text::new("Please provide the link to the body of a simple email content file\n")
-                .with_formatter(&|str| format!(".....{str}....."))
-                .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
-                .with_help_message("Visit this link https://tinyurl.com/4whs2vkt to obtain an S3 URL that contains the simple email content")
-                .prompt()
-                .unwrap();
-                                    match get(&body_link).await{
-                    Ok(body) => {
-                        let body_data = body.text().await.expect("Error while getting text data\n");
-                        let x: &[_] = &['\n','\r',' ','\x1b','\u{20}','\u{7f}','\u{80}'];
-                        let body_data = body_data.trim_matches(x);
-                        let simple_data = SimpleMail::builder(&body_data, &subject);
-                        match (list_name.is_empty(),from_address.is_empty()){
-                            (false,false) => {
-                                 ses_ops
-                                .send_multi_email_with_simple(simple_data, Some(&from_address), Some(&list_name))
-                                .await; 
-                            }
-                            (true,true) =>{
-                                ses_ops
-                                .send_multi_email_with_simple(simple_data, None, None)
-                                .await;
-                            }
-                            (false,true) =>{
-                                ses_ops
-                                .send_multi_email_with_simple(simple_data, None, Some(&list_name))
-                                .await;
-                            }
-                            (true,false) =>{
-                               ses_ops
-                                .send_multi_email_with_simple(simple_data, Some(&from_address), None)
-                                .await;
-                            }
-                          }
+            .prompt()?;
+            let from_address = Text::new("Enter the from address\n")
+                .with_placeholder(&default_from_address)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let list_name = Some(list_name.as_str()).filter(|name| !name.is_empty());
+            let envelope = Envelope {
+                topic: prompt_topic_scope(ctx, list_name.unwrap_or(ses_ops.get_list_name()))
+                    .await?,
+                selection: prompt_selection()?,
+                ..prompt_envelope(&ctx.defaults)?
+            };
+            let from_address = Some(from_address.as_str()).filter(|from| !from.is_empty());
 
-                    }
-                    Err(_) => println!("{}\n","The provided link doesn't seem to be working. Could you please check the link and try again?".red().bold())
+            match (subject.is_empty(), body_info) {
+                (false, true) => {
+                    let body_path = Text::new("Please provide the path to the body of a simple email content file\n")
+                        .with_formatter(&|str| format!(".....{str}....."))
+                        .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
+                        .with_help_message("You can download a example simple email content here https://tinyurl.com/mr22bh4f")
+                        .prompt()?;
+                    let body_data = with_text_part(read_file(&body_path, "simple email")?)?;
+                    send::bulk_simple(
+                        ses_ops,
+                        ctx,
+                        list_name,
+                        &subject,
+                        &body_data,
+                        &envelope,
+                        from_address,
+                    )
+                    .await?;
                 }
-                                }
-                                _ => {
-                                    println!(
-                                        "{}\n",
-                                        "Email,subject or body can't be empty".red().bold()
-                                    );
-                                }
-                            }
-                        }
+                (false, false) => {
+                    let body_link = Text::new("Please provide the link to the body of a simple email content file\n")
+                        .with_formatter(&|str| format!(".....{str}....."))
+                        .with_placeholder("Any file extension is acceptable as long as it can be read and contains only text content or an HTML body, without any template variables\n")
+                        .with_help_message("Visit this link https://tinyurl.com/4whs2vkt to obtain an S3 URL that contains the simple email content")
+                        .prompt()?;
+                    let body_data = with_text_part(fetch_body(&body_link).await?)?;
+                    send::bulk_simple(
+                        ses_ops,
+                        ctx,
+                        list_name,
+                        &subject,
+                        &body_data,
+                        &envelope,
+                        from_address,
+                    )
+                    .await?;
+                }
+                _ => {
+                    println!("{}\n", "Email,subject or body can't be empty".red().bold());
+                }
+            }
+        }
 
-                        "Send a Bulk of Templated Emails\n" => {
-                            let get_from_address = ses_ops.get_from_address();
-                            let get_template_name = ses_ops.get_template_name();
-                            let get_list_name = ses_ops.get_list_name();
+        "Send a Bulk of Templated Emails\n" => {
+            let default_template = format!(
+                "Default template name is: {}\n",
+                ses_ops.get_template_name()
+            );
+            let default_list_name = format!("Default list name is: {}\n", ses_ops.get_list_name());
+            let template_name = Text::new("Please provide the template name\n")
+                .with_placeholder(&default_template)
+                .with_formatter(&|input| format!("Received Template Name: {input}\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let list_name = Text::new("Please provide the name of the Contact List where all your verified emails are stored\n")
+                .with_placeholder(&default_list_name)
+                .with_formatter(&|input| format!("The templated email will be sent to each email address in the: {input} Contact List\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let data_file = Text::new("Please provide the path to the CSV or JSON file with the template data of each recipient\n")
+                .with_placeholder("A CSV file needs an 'email' column, a JSON file is an array of objects with an 'email' field or an object keyed by email\n")
+                .with_help_message("Skip it to use the attributes stored with each contact")
+                .with_formatter(&|input| format!("Received Template Data Path: {input}\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let default_data = Text::new(
+                "Please provide the path to the JSON template data shared by every recipient\n",
+            )
+            .with_placeholder("The per-recipient data overrides these values\n")
+            .with_help_message("This is optional")
+            .with_formatter(&|input| format!("Received Default Template Data Path: {input}\n"))
+            .prompt_skippable()?
+            .unwrap_or_default();
+            let non_empty = |input: &str| Some(input.to_string()).filter(|input| !input.is_empty());
+            let (template_name, list_name, data_file, default_data) = (
+                non_empty(&template_name),
+                non_empty(&list_name),
+                non_empty(&data_file),
+                non_empty(&default_data),
+            );
+            let topic =
+                prompt_topic_scope(ctx, list_name.as_deref().unwrap_or(ses_ops.get_list_name()))
+                    .await?;
+            let bulk = send::BulkTemplated {
+                template_name: template_name.as_deref(),
+                list_name: list_name.as_deref(),
+                from_address: None,
+                data_file: data_file.as_deref(),
+                default_data: default_data.as_deref(),
+                envelope: Envelope {
+                    topic,
+                    selection: prompt_selection()?,
+                    ..prompt_envelope(&ctx.defaults)?
+                },
+            };
+            send::bulk_templated(ses_ops, ctx, &bulk).await?;
+        }
+        "Send an Email with Attachments\n" => {
+            let to_list = Confirm::new("Would you like to send it to every address in a contact list?\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .with_placeholder("Selecting 'Yes' sends it to a contact list, while choosing 'No' sends it to a single verified email\n")
+                .prompt()?;
+            let recipient = match to_list {
+                true => Text::new("Please provide the name of the Contact List\n")
+                    .with_placeholder(&format!(
+                        "Default list name is: {}\n",
+                        ses_ops.get_list_name()
+                    ))
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt_skippable()?
+                    .unwrap_or_default(),
+                false => Text::new("Enter the email..\n")
+                    .with_placeholder("The email must be verified\n")
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt()?,
+            };
+            let subject = Text::new("Please enter the subject\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt()?;
+            let html = Text::new("Please provide the path to the HTML body\n")
+                .with_placeholder("Inline images are referenced as <img src=\"cid:logo.png\">\n")
+                .with_help_message("Skip it to send only the text body")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let text = Text::new("Please provide the path to the text body\n")
+                .with_placeholder("Shown by email clients that don't support HTML\n")
+                .with_help_message("Skip it to send only the HTML body")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let attachments =
+                Text::new("Please provide the paths of the files to attach, separated by commas\n")
+                    .with_placeholder("e.g. invoice.pdf,terms.pdf\n")
+                    .with_help_message("The whole message can be at most 40 MB after encoding")
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt_skippable()?
+                    .unwrap_or_default();
+            let inline =
+                Text::new("Please provide the paths of the inline images, separated by commas\n")
+                    .with_placeholder(
+                        "e.g. logo.png, referenced from the HTML body as cid:logo.png\n",
+                    )
+                    .with_formatter(&|str| format!(".....{str}.....\n"))
+                    .prompt_skippable()?
+                    .unwrap_or_default();
+            let paths = |input: &str| {
+                input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|path| !path.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            };
+            let non_empty = |input: String| Some(input).filter(|input| !input.is_empty());
+            let files = mime::RawFiles {
+                subject,
+                html: non_empty(html),
+                text: non_empty(text),
+                attachments: paths(&attachments),
+                inline: paths(&inline),
+            };
+            match to_list {
+                true => {
+                    let list_name = Some(recipient.as_str()).filter(|name| !name.is_empty());
+                    let envelope = Envelope {
+                        topic: prompt_topic_scope(
+                            ctx,
+                            list_name.unwrap_or(ses_ops.get_list_name()),
+                        )
+                        .await?,
+                        selection: prompt_selection()?,
+                        ..prompt_envelope(&ctx.defaults)?
+                    };
+                    send::bulk_raw(ses_ops, ctx, list_name, &files, &envelope, None).await?;
+                }
+                false => {
+                    let envelope = prompt_envelope(&ctx.defaults)?;
+                    send::raw(ses_ops, ctx, &recipient, &files, &envelope, None).await?
+                }
+            }
+        }
+        "Resume a Bulk Send\n" => {
+            let journal_path = Text::new(
+                "Please provide the path to the journal of the bulk send you want to continue\n",
+            )
+            .with_placeholder(
+                "Every bulk send writes a campaign_<time>.jsonl journal in the current directory\n",
+            )
+            .with_help_message(
+                "Only the recipients the journal doesn't have as delivered are sent to",
+            )
+            .with_formatter(&|input| format!("Received Journal Path: {input}\n"))
+            .prompt()?;
+            send::resume(ses_ops, ctx, &journal_path).await?;
+        }
+        "Configuration Set Operations\n" => configuration_set_menu(ctx).await?,
+        "Identity Operations\n" => identity_menu(ctx).await?,
+        "Common Errors\n" => {
+            let possible_errors = include_str!("./assets/possible_errors.txt")
+                .yellow()
+                .italic()
+                .bold();
+            println!("{}\n", possible_errors);
+        }
+        _ => {}
+    }
+    Ok(())
+}
 
-                            use std::env::var;
-                            match (var("TEMPLATE_NAME"), var("FROM_ADDRESS"), var("LIST_NAME")) {
-                                (Ok(_), Ok(_), Ok(_)) => {
-                                    println!(
-                                        "Template Name: {}\nFrom Address: {}\nList Name: {}\n",
-                                        get_template_name.green().bold(),
-                                        get_list_name.green().bold(),
-                                        get_from_address.green().bold()
-                                    );
-                                    ses_ops.send_bulk_templated_emails().await;
-                                }
-                                _ => {
-                                    println!(
-                                        "{}\n",
-                                        "This information is for Debugging Purposes"
-                                            .yellow()
-                                            .bold()
-                                    );
-                                    println!(
-                                        "Template Name: {}\nFrom Address: {}\nList Name: {}\n",
-                                        get_template_name.green().bold(),
-                                        get_list_name.green().bold(),
-                                        get_from_address.green().bold()
-                                    );
-                                }
-                            }
-                        }
-                        "Common Errors\n" => {
-                            let possible_errors = include_str!("./assets/possible_errors.txt")
-                                .yellow()
-                                .italic()
-                                .bold();
-                            println!("{}\n", possible_errors);
-                        }
-                        "Return to the Main Menu\n" => continue 'main,
+/// The configuration set operations, shown until 'Return to the SES Menu' is chosen.
+async fn configuration_set_menu(ctx: &Context) -> Result<()> {
+    let operations = vec![
+        "Create a Configuration Set\n",
+        "List Configuration Sets\n",
+        "Describe a Configuration Set\n",
+        "Toggle Reputation Metrics\n",
+        "Toggle Sending\n",
+        "Set the TLS Policy\n",
+        "Set the Custom Tracking Domain\n",
+        "Add an Event Destination\n",
+        "Delete an Event Destination\n",
+        "Delete a Configuration Set\n",
+        "Return to the SES Menu\n",
+    ];
+    loop {
+        let choice = Select::new(
+            "Select the configuration set operation\n",
+            operations.clone(),
+        )
+        .with_vim_mode(true)
+        .with_page_size(11)
+        .prompt()?;
+        if choice == "Return to the SES Menu\n" {
+            return Ok(());
+        }
+        if let Err(err) = configuration_set_operation(choice, ctx).await {
+            err.report();
+        }
+    }
+}
 
-                        _ => {}
+async fn configuration_set_operation(choice: &str, ctx: &Context) -> Result<()> {
+    if choice == "List Configuration Sets\n" {
+        let names = configuration_sets::list(ctx).await?;
+        match names.is_empty() {
+            true => println!(
+                "{}\n",
+                "There are no configuration sets in the account"
+                    .yellow()
+                    .bold()
+            ),
+            false => {
+                for name in names {
+                    println!("    {}", name.green().bold());
+                }
+                println!();
+            }
+        }
+        return Ok(());
+    }
+    let default_set = format!(
+        "Default configuration set is: {}",
+        ctx.defaults
+            .configuration_set
+            .value
+            .as_deref()
+            .unwrap_or_default()
+    );
+    let name = Text::new("Please enter the name of the configuration set\n")
+        .with_placeholder(&default_set)
+        .with_formatter(&|str| format!(".....{str}....."))
+        .prompt_skippable()?
+        .filter(|name| !name.is_empty())
+        .or_else(|| ctx.defaults.configuration_set.value.clone())
+        .ok_or_else(|| SesClientError::validation("The configuration set name can't be empty"))?;
+    match choice {
+        "Create a Configuration Set\n" => {
+            let reputation_metrics = Confirm::new(
+                "Would you like to collect the reputation metrics of the set?\n",
+            )
+            .with_placeholder(
+                "The bounce and complaint rates of the messages sent with the set are tracked",
+            )
+            .with_default(true)
+            .prompt()?;
+            let sending_enabled = Confirm::new("Should sending be enabled right away?\n")
+                .with_default(true)
+                .prompt()?;
+            let require_tls = Confirm::new("Should the messages only be delivered over TLS?\n")
+                .with_placeholder("Otherwise TLS is used whenever the receiving server supports it")
+                .with_default(false)
+                .prompt()?;
+            let tracking_domain = Text::new(
+                "Please enter the custom domain of the open and click tracking links or skip it\n",
+            )
+            .with_help_message(
+                "The domain needs a CNAME record pointing at the SES tracking domain of the region",
+            )
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default();
+            let set = NewConfigurationSet {
+                name: &name,
+                reputation_metrics,
+                sending_enabled,
+                tls_policy: require_tls.then_some(TlsRequirement::Require),
+                tracking_domain: Some(tracking_domain.as_str()).filter(|domain| !domain.is_empty()),
+            };
+            configuration_sets::create(ctx, &set).await
+        }
+        "Describe a Configuration Set\n" => configuration_sets::describe(ctx, &name).await,
+        "Toggle Reputation Metrics\n" => {
+            let enabled = Confirm::new("Should the reputation metrics be collected?\n")
+                .with_placeholder("Respond with 'Yes' to turn them on or 'No' to turn them off")
+                .prompt()?;
+            configuration_sets::set_reputation_metrics(ctx, &name, enabled).await
+        }
+        "Toggle Sending\n" => {
+            let enabled = Confirm::new("Should the messages of the set be sent?\n")
+                .with_placeholder("Respond with 'Yes' to resume sending or 'No' to pause it")
+                .prompt()?;
+            configuration_sets::set_sending(ctx, &name, enabled).await
+        }
+        "Set the TLS Policy\n" => {
+            let policy = match Select::new("Select the TLS policy\n", vec!["REQUIRE", "OPTIONAL"])
+                .with_help_message("REQUIRE only delivers over TLS, OPTIONAL uses it whenever the receiving server supports it")
+                .prompt()?
+            {
+                "REQUIRE" => TlsRequirement::Require,
+                _ => TlsRequirement::Optional,
+            };
+            configuration_sets::set_tls_policy(ctx, &name, policy).await
+        }
+        "Set the Custom Tracking Domain\n" => {
+            let domain = Text::new("Please enter the custom tracking domain\n")
+                .with_help_message("Skip it to go back to the domain SES provides")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .unwrap_or_default();
+            let domain = Some(domain.as_str()).filter(|domain| !domain.is_empty());
+            configuration_sets::set_tracking_domain(ctx, &name, domain).await
+        }
+        "Add an Event Destination\n" => {
+            let destination_name = Text::new("Please enter the name of the event destination\n")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt()?;
+            let events = [
+                ("SEND", EventKind::Send),
+                ("REJECT", EventKind::Reject),
+                ("BOUNCE", EventKind::Bounce),
+                ("COMPLAINT", EventKind::Complaint),
+                ("DELIVERY", EventKind::Delivery),
+                ("OPEN", EventKind::Open),
+                ("CLICK", EventKind::Click),
+                ("RENDERING_FAILURE", EventKind::RenderingFailure),
+                ("DELIVERY_DELAY", EventKind::DeliveryDelay),
+                ("SUBSCRIPTION", EventKind::Subscription),
+            ];
+            let chosen = MultiSelect::new(
+                "Select the events sent to the destination\n",
+                events.iter().map(|(label, _)| *label).collect(),
+            )
+            .with_help_message("Space selects an event, Enter confirms")
+            .prompt()?;
+            if chosen.is_empty() {
+                return Err(SesClientError::validation("Select at least one event"));
+            }
+            let events = events
+                .iter()
+                .filter(|(label, _)| chosen.contains(label))
+                .map(|(_, kind)| *kind)
+                .collect();
+            let target = match Select::new(
+                "Select where the events are sent\n",
+                vec!["SNS", "CloudWatch", "Kinesis Data Firehose", "EventBridge"],
+            )
+            .prompt()?
+            {
+                "SNS" => EventTarget::Sns {
+                    topic_arn: Text::new("Please enter the ARN of the SNS topic\n").prompt()?,
+                },
+                "CloudWatch" => {
+                    let dimensions = Text::new(
+                        "Please enter the dimensions as name:source:default separated by commas\n",
+                    )
+                    .with_placeholder("Eg: campaign:message-tag:none")
+                    .with_help_message("The source is message-tag, email-header or link-tag")
+                    .prompt()?;
+                    EventTarget::CloudWatch {
+                        dimensions: dimensions
+                            .split(',')
+                            .map(|dimension| dimension.trim().to_string())
+                            .filter(|dimension| !dimension.is_empty())
+                            .collect(),
                     }
                 }
+                "Kinesis Data Firehose" => EventTarget::Firehose {
+                    delivery_stream_arn: Text::new("Please enter the ARN of the delivery stream\n")
+                        .prompt()?,
+                    iam_role_arn: Text::new(
+                        "Please enter the ARN of the IAM role SES assumes to write to the stream\n",
+                    )
+                    .prompt()?,
+                },
+                _ => EventTarget::EventBridge {
+                    event_bus_arn: Text::new("Please enter the ARN of the event bus\n")
+                        .with_placeholder(
+                            "Eg: arn:aws:events:us-east-1:123456789012:event-bus/default",
+                        )
+                        .prompt()?,
+                },
+            };
+            let args = EventDestinationArgs {
+                set: name,
+                name: destination_name,
+                events,
+                disabled: false,
+                target,
+            };
+            configuration_sets::add_event_destination(ctx, &args).await
+        }
+        "Delete an Event Destination\n" => {
+            let destination_name =
+                Text::new("Please enter the name of the event destination to delete\n")
+                    .with_formatter(&|str| format!(".....{str}....."))
+                    .prompt()?;
+            configuration_sets::delete_event_destination(ctx, &name, &destination_name).await
+        }
+        "Delete a Configuration Set\n" => {
+            let confirmed = Confirm::new(&format!("Are you sure you want to delete the configuration set '{name}' and its event destinations?\n"))
+                .with_default(false)
+                .prompt()?;
+            match confirmed {
+                true => configuration_sets::delete(ctx, &name).await,
+                false => Ok(()),
             }
-            "Quit the application\n" => {
-                credential.empty();
-                break 'main;
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The email address and domain identity operations, shown until 'Return to the SES Menu' is chosen.
+async fn identity_menu(ctx: &Context) -> Result<()> {
+    let operations = vec![
+        "Identity Status Dashboard\n",
+        "Create a Domain Identity\n",
+        "Set the MAIL FROM Domain\n",
+        "Show the DNS Records\n",
+        "Check the DNS Records\n",
+        "Put an Identity Policy\n",
+        "Get the Identity Policies\n",
+        "Delete an Identity Policy\n",
+        "Toggle Feedback Forwarding\n",
+        "Delete an Identity\n",
+        "Return to the SES Menu\n",
+    ];
+    loop {
+        let choice = Select::new("Select the identity operation\n", operations.clone())
+            .with_vim_mode(true)
+            .with_page_size(11)
+            .prompt()?;
+        if choice == "Return to the SES Menu\n" {
+            return Ok(());
+        }
+        if let Err(err) = identity_operation(choice, ctx).await {
+            err.report();
+        }
+    }
+}
+
+async fn identity_operation(choice: &str, ctx: &Context) -> Result<()> {
+    if choice == "Identity Status Dashboard\n" {
+        return identity_dashboard(ctx).await;
+    }
+    let domain_only = matches!(
+        choice,
+        "Create a Domain Identity\n"
+            | "Set the MAIL FROM Domain\n"
+            | "Show the DNS Records\n"
+            | "Check the DNS Records\n"
+    );
+    let (kind, placeholder) = match domain_only {
+        true => ("domain", "Eg: example.com"),
+        false => (
+            "email address or domain of the identity",
+            "Eg: someone@example.com or example.com",
+        ),
+    };
+    let identity = Text::new(&format!("Please enter the {kind}\n"))
+        .with_placeholder(placeholder)
+        .with_formatter(&|str| format!(".....{str}....."))
+        .prompt()?;
+    let prompt_mail_from = |help: &str| -> Result<(Option<String>, bool)> {
+        let mail_from = Text::new("Please enter the MAIL FROM domain or skip it\n")
+            .with_placeholder(&format!("Eg: mail.{identity}"))
+            .with_help_message(help)
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .filter(|mail_from| !mail_from.is_empty());
+        let reject = match mail_from {
+            Some(_) => {
+                Confirm::new("Should messages be rejected while the MX record is missing?\n")
+                    .with_placeholder("Otherwise SES falls back to its own MAIL FROM domain")
+                    .with_default(false)
+                    .prompt()?
             }
-            _other => {
-                println!("This branch never reach..");
+            None => false,
+        };
+        Ok((mail_from, reject))
+    };
+    let prompt_zone_file = || -> Result<Option<String>> {
+        Ok(
+            Text::new("Please enter the path of the zone-file snippet or skip it\n")
+                .with_help_message("The records are printed when it is skipped")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .filter(|path| !path.is_empty()),
+        )
+    };
+    match choice {
+        "Create a Domain Identity\n" => {
+            let byodkim = Confirm::new("Would you like to bring your own DKIM key?\n")
+                .with_placeholder("Otherwise Easy DKIM generates the keys and three CNAME records")
+                .with_default(false)
+                .prompt()?;
+            let (selector, private_key, public_key) = match byodkim {
+                true => (
+                    Text::new("Please enter the DKIM selector\n")
+                        .with_placeholder("Eg: ses")
+                        .prompt()?,
+                    Text::new("Please enter the path of the PEM private key\n").prompt()?,
+                    Text::new("Please enter the path of the PEM public key or skip it\n")
+                        .with_help_message("It is only used to print the DKIM TXT record")
+                        .prompt_skippable()?
+                        .filter(|path| !path.is_empty()),
+                ),
+                false => (String::new(), String::new(), None),
+            };
+            let (mail_from, reject_on_mx_failure) =
+                prompt_mail_from("SES uses its own MAIL FROM domain when it is skipped")?;
+            let setup = DomainSetup {
+                domain: &identity,
+                byodkim: byodkim.then(|| Byodkim {
+                    selector: &selector,
+                    private_key: &private_key,
+                    public_key: public_key.as_deref(),
+                }),
+                mail_from: mail_from.as_deref(),
+                reject_on_mx_failure,
+            };
+            let records = domains::create_domain(ctx, &setup).await?;
+            if !records.is_empty() {
+                domains::print_records(&records);
+                domains::write_zone(&records, prompt_zone_file()?.as_deref())?;
             }
+            Ok(())
+        }
+        "Set the MAIL FROM Domain\n" => {
+            let (mail_from, reject_on_mx_failure) =
+                prompt_mail_from("Skip it to remove the custom MAIL FROM domain")?;
+            domains::set_mail_from(ctx, &identity, mail_from.as_deref(), reject_on_mx_failure).await
         }
+        "Show the DNS Records\n" => {
+            let records = domains::domain_records(ctx, &identity).await?;
+            domains::print_records(&records);
+            match prompt_zone_file()? {
+                Some(path) => domains::write_zone(&records, Some(&path)),
+                None => Ok(()),
+            }
+        }
+        "Check the DNS Records\n" => {
+            let default_resolver = std::env::var("DNS_RESOLVER").ok();
+            let resolver = Text::new("Please enter the address of the DNS server or skip it\n")
+                .with_placeholder(default_resolver.as_deref().unwrap_or("Eg: 127.0.0.1:5353"))
+                .with_help_message(
+                    "Skip it to use DNS_RESOLVER if it is set, or else the system resolver",
+                )
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .filter(|address| !address.is_empty())
+                .or(default_resolver)
+                .as_deref()
+                .map(parse_resolver)
+                .transpose()?;
+            dns_check::check_domain(ctx, &identity, resolver).await
+        }
+        "Put an Identity Policy\n" => {
+            let name = Text::new("Please enter the name of the policy\n")
+                .with_help_message("A policy of the same name is replaced")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt()?;
+            let path = Text::new("Please enter the path of the policy document\n")
+                .with_placeholder("Eg: all_policy.json")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt()?;
+            let exists = identities::policies(ctx, &identity)
+                .await?
+                .contains_key(&name);
+            let confirmed = !exists
+                || Confirm::new(&format!(
+                    "The policy '{name}' already exists, would you like to replace it?\n"
+                ))
+                .with_default(false)
+                .prompt()?;
+            match confirmed {
+                true => identities::put_policy(ctx, &identity, &name, &path).await,
+                false => Ok(()),
+            }
+        }
+        "Get the Identity Policies\n" => {
+            let name = Text::new("Please enter the name of the policy or skip it\n")
+                .with_help_message("Every policy of the identity is shown when it is skipped")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .filter(|name| !name.is_empty());
+            let output = Text::new("Please enter the path to write the policies to or skip it\n")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt_skippable()?
+                .filter(|path| !path.is_empty());
+            identities::show_policies(ctx, &identity, name.as_deref(), output.as_deref()).await
+        }
+        "Delete an Identity Policy\n" => {
+            let name = Text::new("Please enter the name of the policy to delete\n")
+                .with_formatter(&|str| format!(".....{str}....."))
+                .prompt()?;
+            let confirmed = Confirm::new(&format!(
+                "Are you sure you want to delete the policy '{name}' of '{identity}'?\n"
+            ))
+            .with_placeholder("The senders it authorizes can no longer send from the identity")
+            .with_default(false)
+            .prompt()?;
+            match confirmed {
+                true => identities::delete_policy(ctx, &identity, &name).await,
+                false => Ok(()),
+            }
+        }
+        "Toggle Feedback Forwarding\n" => {
+            let enabled = Confirm::new("Should bounces and complaints be forwarded by email?\n")
+                .with_placeholder("Turning it off needs bounce and complaint notifications on SNS")
+                .prompt()?;
+            identities::set_feedback_forwarding(ctx, &identity, enabled).await
+        }
+        "Delete an Identity\n" => {
+            let confirmed = Confirm::new(&format!(
+                "Are you sure you want to delete the identity '{identity}' and its policies?\n"
+            ))
+            .with_placeholder("Messages can no longer be sent from it until it is verified again")
+            .with_default(false)
+            .prompt()?;
+            match confirmed {
+                true => identities::delete(ctx, &identity).await,
+                false => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Shows every identity sorted by the chosen column, then offers the CSV export and
+/// sending the verification emails again.
+async fn identity_dashboard(ctx: &Context) -> Result<()> {
+    let mut statuses = identity_status::identity_statuses(ctx).await?;
+    let columns = [
+        ("Identity", IdentityColumn::Identity),
+        ("Type", IdentityColumn::Type),
+        ("Verification", IdentityColumn::Verification),
+        ("DKIM", IdentityColumn::Dkim),
+        ("Sending", IdentityColumn::Sending),
+        ("MAIL FROM", IdentityColumn::MailFrom),
+    ];
+    let column = Select::new(
+        "Select the column to sort by\n",
+        columns.iter().map(|(label, _)| *label).collect(),
+    )
+    .prompt()?;
+    let column = columns
+        .iter()
+        .find(|(label, _)| *label == column)
+        .map(|(_, column)| *column)
+        .unwrap_or(IdentityColumn::Identity);
+    let descending = Confirm::new("Would you like to sort in descending order?\n")
+        .with_default(false)
+        .prompt()?;
+    identity_status::sort(&mut statuses, column, descending);
+    identity_status::print_table(&statuses);
+    if statuses.is_empty() {
+        return Ok(());
+    }
+    let csv = Text::new("Please enter the path to export the table as CSV or skip it\n")
+        .with_placeholder("Eg: identities.csv")
+        .with_formatter(&|str| format!(".....{str}....."))
+        .prompt_skippable()?
+        .filter(|path| !path.is_empty());
+    if let Some(path) = csv {
+        identity_status::write_csv(&path, &statuses)?;
+    }
+    let pending = statuses
+        .iter()
+        .filter(|status| status.is_pending_email())
+        .count();
+    if pending > 0
+        && Confirm::new(&format!(
+            "Would you like to send the verification email again to the {pending} pending addresses?\n"
+        ))
+        .with_help_message("Each identity is deleted and created again, which drops its policies and settings")
+        .with_default(false)
+        .prompt()?
+    {
+        identity_status::resend_verification(ctx, &statuses).await?;
+    }
+    Ok(())
+}
+
+/// Asks for the CC, BCC and Reply-To addresses, the configuration set and the
+/// message tags, each one can be skipped.
+fn prompt_envelope(defaults: &Defaults) -> Result<Envelope> {
+    let help_message = format!(
+        "Separate several addresses with commas, a message can have at most {MAX_RECIPIENTS} To, CC and BCC addresses together"
+    );
+    let prompt = |kind: &str| -> Result<Vec<String>> {
+        let addresses = Text::new(&format!("Please enter the {kind} addresses or skip it\n"))
+            .with_help_message(&help_message)
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default();
+        Ok(parse_addresses(&addresses))
+    };
+    let (cc, bcc, reply_to) = (prompt("CC")?, prompt("BCC")?, prompt("Reply-To")?);
+    let default_configuration_set = format!(
+        "Default configuration set is: {}",
+        defaults
+            .configuration_set
+            .value
+            .as_deref()
+            .unwrap_or_default()
+    );
+    let configuration_set =
+        Text::new("Please enter the configuration set or skip it to use the default\n")
+            .with_placeholder(&default_configuration_set)
+            .with_help_message(
+                "The events of the messages are routed to the event destinations of the set",
+            )
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default();
+    let default_tags = format!(
+        "Default tags are: {}",
+        defaults.email_tags.value.as_deref().unwrap_or_default()
+    );
+    let tags = Text::new("Please enter the message tags as name=value pairs separated by commas or skip it\n")
+        .with_placeholder(&default_tags)
+        .with_help_message("Names and values may only hold ASCII letters, numbers, underscores and dashes, they are added to the default tags")
+        .with_formatter(&|str| format!(".....{str}....."))
+        .prompt_skippable()?
+        .unwrap_or_default();
+    Ok(Envelope {
+        cc,
+        bcc,
+        reply_to,
+        configuration_set: Some(configuration_set).filter(|name| !name.is_empty()),
+        tags: parse_tags(&tags)?,
+        topic: None,
+        selection: ContactFilter::default(),
+    })
+}
+
+/// Asks for the conditions of a contact filter, each one can be skipped.
+fn prompt_filter() -> Result<ContactFilter> {
+    let prompt = |message: &str, help_message: &str| -> Result<String> {
+        Ok(Text::new(message)
+            .with_help_message(help_message)
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default())
+    };
+    let topics = prompt(
+        "Please enter the topic statuses as topic=OPT_IN or topic=OPT_OUT separated by commas or skip it\n",
+        "Contacts without a preference for a topic use its default",
+    )?;
+    let unsubscribe_all = Select::new(
+        "Please select the unsubscribe-all status of the contacts\n",
+        vec![
+            "Any",
+            "Only unsubscribed from every topic",
+            "Only the others",
+        ],
+    )
+    .prompt()?;
+    let attributes = prompt(
+        "Please enter the attribute values as name=value separated by commas or skip it\n",
+        "Each contact with an attribute condition is fetched on its own, which is slower on large lists",
+    )?;
+    let domain = prompt(
+        "Please enter the domain of the email addresses or skip it\n",
+        "Eg: example.com",
+    )?;
+    let pattern = prompt(
+        "Please enter a regular expression the email addresses must match or skip it\n",
+        "Eg: ^sales\\.",
+    )?;
+    let non_empty =
+        |input: String| Some(input.trim().to_string()).filter(|input| !input.is_empty());
+    let filter = ContactFilter {
+        topics: parse_preferences(&topics)?,
+        unsubscribe_all: match unsubscribe_all {
+            "Only unsubscribed from every topic" => Some(true),
+            "Only the others" => Some(false),
+            _ => None,
+        },
+        attributes: parse_conditions(&attributes)?,
+        domain: non_empty(domain),
+        pattern: non_empty(pattern),
+    };
+    filter.validate()?;
+    Ok(filter)
+}
+
+/// Asks whether to narrow the recipients of a bulk send with a contact filter.
+fn prompt_selection() -> Result<ContactFilter> {
+    match Confirm::new("Would you like to send only to the contacts that match a filter?\n")
+        .with_default(false)
+        .prompt()?
+    {
+        true => prompt_filter(),
+        false => Ok(ContactFilter::default()),
+    }
+}
+
+/// Asks for topics one at a time until the user declines to add another.
+fn prompt_topics() -> Result<Vec<Topic>> {
+    let mut topics = Vec::new();
+    while Confirm::new("Would you like to add a topic to the list?\n")
+        .with_help_message(
+            "Contacts can opt in or out of each topic and bulk sends can be scoped to one",
+        )
+        .with_default(false)
+        .prompt()?
+    {
+        let name = Text::new("Please enter the topic name\n")
+            .with_placeholder("Eg: newsletter\n")
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt()?;
+        let display_name = Text::new("Please enter the display name shown to contacts\n")
+            .with_placeholder("Eg: Monthly Newsletter\n")
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt()?;
+        let status = Select::new(
+            "Please select the preference of contacts who didn't choose one\n",
+            vec!["OPT_IN", "OPT_OUT"],
+        )
+        .prompt()?;
+        let description = Text::new("Please enter a description of the topic or skip it\n")
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default();
+        topics.push(parse_topic(&format!(
+            "{name}:{display_name}:{status}:{description}"
+        ))?);
+    }
+    Ok(topics)
+}
+
+/// Asks for the preference of the contact for every topic of the list, keeping
+/// only the topics that weren't skipped.
+async fn prompt_preferences(ctx: &Context, list_name: &str) -> Result<BTreeMap<String, String>> {
+    let mut preferences = BTreeMap::new();
+    for topic in contact_lists::topic_names(ctx, list_name).await? {
+        let choice = Select::new(
+            &format!("Please select the preference for the topic '{topic}'\n"),
+            vec!["Skip", "OPT_IN", "OPT_OUT"],
+        )
+        .with_help_message(
+            "A skipped topic keeps the current preference of the contact or the topic default",
+        )
+        .prompt()?;
+        if choice != "Skip" {
+            preferences.insert(topic, choice.to_string());
+        }
+    }
+    Ok(preferences)
+}
+
+/// Asks for the topic a bulk send is scoped to when the list has topics.
+async fn prompt_topic_scope(ctx: &Context, list_name: &str) -> Result<Option<String>> {
+    let topics = contact_lists::topic_names(ctx, list_name).await?;
+    if topics.is_empty() {
+        return Ok(None);
+    }
+    let every_contact = "Every contact of the list".to_string();
+    let mut options = vec![every_contact.clone()];
+    options.extend(topics);
+    let choice = Select::new("Please select the topic the emails are about\n", options)
+        .with_help_message(
+            "Contacts who opted out of the topic or unsubscribed from every topic are skipped",
+        )
+        .prompt()?;
+    Ok(Some(choice).filter(|choice| *choice != every_contact))
+}
+
+/// Asks for the plain text part of an HTML body, skipping it generates the text from the HTML.
+fn with_text_part(body: String) -> Result<SimpleBody> {
+    if !is_html(&body) {
+        return SimpleBody::new(body, None);
     }
+    let text_path =
+        Text::new("Please provide the path to the plain text version of the HTML body\n")
+            .with_placeholder(
+                "Recipients whose email clients can't display HTML see this part instead\n",
+            )
+            .with_help_message(
+                "Skip it to generate the text from the HTML, links are kept as 'text (url)'",
+            )
+            .with_formatter(&|str| format!(".....{str}....."))
+            .prompt_skippable()?
+            .unwrap_or_default();
+    let text = match text_path.is_empty() {
+        true => None,
+        false => Some(read_file(&text_path, "Text Body")?),
+    };
+    SimpleBody::new(body, text)
 }
 fn global_render_config() -> RenderConfig {
     let mut config = RenderConfig::default()
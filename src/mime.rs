@@ -0,0 +1,455 @@
+use crate::content::{absolute_path, read_file};
+use crate::envelope::Envelope;
+use crate::error::{Result, SesClientError};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// SES rejects raw messages larger than this, base64 encoding included.
+pub const MAX_RAW_MESSAGE_SIZE: usize = 40 * 1024 * 1024;
+
+/// A file attached to the message, inline parts are referenced from the HTML
+/// body as `cid:<file name>`.
+#[derive(Clone)]
+pub struct Part {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    pub fn read(path: &str) -> Result<Self> {
+        let data = std::fs::read(path).map_err(|source| SesClientError::File {
+            what: "Attachment",
+            path: path.into(),
+            source,
+        })?;
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string());
+        Ok(Part {
+            content_type: content_type(&file_name),
+            file_name,
+            data,
+        })
+    }
+}
+
+/// The local files a raw message is built from, kept in the journal of a bulk send.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RawFiles {
+    pub subject: String,
+    /// Path to the HTML body
+    pub html: Option<String>,
+    /// Path to the text body
+    pub text: Option<String>,
+    pub attachments: Vec<String>,
+    /// Images referenced from the HTML body as `cid:<file name>`
+    pub inline: Vec<String>,
+}
+
+impl RawFiles {
+    /// Reads the bodies, attachments and inline images.
+    pub fn read(&self) -> Result<RawEmail> {
+        if self.html.is_none() && self.text.is_none() {
+            return Err(SesClientError::validation(
+                "An HTML body, a text body or both are required",
+            ));
+        }
+        let read_all = |paths: &[String]| {
+            paths
+                .iter()
+                .map(|path| Part::read(path))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(RawEmail {
+            subject: self.subject.clone(),
+            html: self
+                .html
+                .as_deref()
+                .map(|path| read_file(path, "HTML Body"))
+                .transpose()?,
+            text: self
+                .text
+                .as_deref()
+                .map(|path| read_file(path, "Text Body"))
+                .transpose()?,
+            attachments: read_all(&self.attachments)?,
+            inline: read_all(&self.inline)?,
+        })
+    }
+
+    /// The journal may be resumed from another directory, so every path is made absolute.
+    pub fn absolute(&self) -> Self {
+        let absolute = |path: &String| absolute_path(path);
+        RawFiles {
+            subject: self.subject.clone(),
+            html: self.html.as_ref().map(absolute),
+            text: self.text.as_ref().map(absolute),
+            attachments: self.attachments.iter().map(absolute).collect(),
+            inline: self.inline.iter().map(absolute).collect(),
+        }
+    }
+}
+
+/// The parts of a multipart message, at least one of the HTML and text bodies is needed.
+#[derive(Clone)]
+pub struct RawEmail {
+    pub subject: String,
+    pub html: Option<String>,
+    pub text: Option<String>,
+    pub attachments: Vec<Part>,
+    pub inline: Vec<Part>,
+}
+
+impl RawEmail {
+    /// The names of the attached and inline files, shown in dry run reports.
+    pub fn file_names(&self) -> Vec<String> {
+        self.attachments
+            .iter()
+            .chain(&self.inline)
+            .map(|part| part.file_name.clone())
+            .collect()
+    }
+
+    /// Builds the MIME message addressed to `to`, failing when it exceeds the SES size limit.
+    ///
+    /// The CC and Reply-To addresses of the envelope become headers, the BCC
+    /// addresses only go in the destination of the request.
+    ///
+    /// The layout is multipart/mixed around multipart/related around
+    /// multipart/alternative, each level is only added when it has parts.
+    pub fn to_mime(&self, from: &str, to: &str, envelope: &Envelope) -> Result<Vec<u8>> {
+        let id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let mut message = String::new();
+        message.push_str(&format!("From: {from}\r\n"));
+        message.push_str(&format!("To: {to}\r\n"));
+        if !envelope.cc.is_empty() {
+            message.push_str(&format!("Cc: {}\r\n", envelope.cc.join(", ")));
+        }
+        if !envelope.reply_to.is_empty() {
+            message.push_str(&format!("Reply-To: {}\r\n", envelope.reply_to.join(", ")));
+        }
+        message.push_str(&format!("Subject: {}\r\n", encode_word(&self.subject)));
+        message.push_str("MIME-Version: 1.0\r\n");
+
+        let content_ids = (0..self.inline.len())
+            .map(|index| format!("part{index}@ses-client"))
+            .collect::<Vec<_>>();
+        // The HTML body can only tell inline files apart by name, a name used twice
+        // references the first of them
+        let mut references = HashMap::new();
+        for (part, content_id) in self.inline.iter().zip(&content_ids) {
+            references
+                .entry(part.file_name.as_str())
+                .or_insert_with(|| content_id.clone());
+        }
+        let mut body = Vec::new();
+        if let Some(text) = &self.text {
+            body.push(text_part("text/plain", text));
+        }
+        if let Some(html) = &self.html {
+            body.push(text_part("text/html", &link_content_ids(html, &references)));
+        }
+        let body = multipart("alternative", &format!("alt_{id}"), body);
+        let mut related = vec![body];
+        related.extend(
+            self.inline
+                .iter()
+                .zip(&content_ids)
+                .map(|(part, content_id)| file_part(part, Some(content_id))),
+        );
+        let related = multipart("related", &format!("related_{id}"), related);
+        let mut mixed = vec![related];
+        mixed.extend(self.attachments.iter().map(|part| file_part(part, None)));
+        message.push_str(&multipart("mixed", &format!("mixed_{id}"), mixed));
+
+        if message.len() > MAX_RAW_MESSAGE_SIZE {
+            return Err(SesClientError::validation(format!(
+                "The message is {} MB after encoding, SES accepts at most 40 MB",
+                message.len() / (1024 * 1024)
+            )));
+        }
+        Ok(message.into_bytes())
+    }
+}
+
+/// Wraps the parts in a multipart entity, a single part is returned as it is.
+fn multipart(subtype: &str, boundary: &str, mut parts: Vec<String>) -> String {
+    if parts.len() == 1 {
+        return parts.remove(0);
+    }
+    let mut entity = format!("Content-Type: multipart/{subtype}; boundary=\"{boundary}\"\r\n\r\n");
+    for part in parts {
+        entity.push_str(&format!("--{boundary}\r\n{part}\r\n"));
+    }
+    entity.push_str(&format!("--{boundary}--\r\n"));
+    entity
+}
+
+fn text_part(content_type: &str, content: &str) -> String {
+    format!(
+        "Content-Type: {content_type}; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}",
+        wrapped_base64(content.as_bytes())
+    )
+}
+
+/// An inline part has a content ID, any other part is an attachment.
+fn file_part(part: &Part, content_id: Option<&String>) -> String {
+    let file_name = parameter("filename", &part.file_name);
+    let disposition = match content_id {
+        Some(content_id) => {
+            format!("Content-Disposition: inline; {file_name}\r\nContent-ID: <{content_id}>\r\n")
+        }
+        None => format!("Content-Disposition: attachment; {file_name}\r\n"),
+    };
+    format!(
+        "Content-Type: {}; {}\r\n{disposition}Content-Transfer-Encoding: base64\r\n\r\n{}",
+        part.content_type,
+        parameter("name", &part.file_name),
+        wrapped_base64(&part.data)
+    )
+}
+
+/// A quoted header parameter with its quotes and backslashes escaped, a value
+/// outside printable ASCII is percent-encoded as RFC 2231 asks.
+fn parameter(name: &str, value: &str) -> String {
+    if value
+        .chars()
+        .all(|char| char.is_ascii() && !char.is_ascii_control())
+    {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        return format!("{name}=\"{escaped}\"");
+    }
+    let plain = |byte: u8| byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte);
+    let encoded = value
+        .bytes()
+        .map(|byte| match plain(byte) {
+            true => (byte as char).to_string(),
+            false => format!("%{byte:02X}"),
+        })
+        .collect::<String>();
+    format!("{name}*=UTF-8''{encoded}")
+}
+
+/// A file name isn't a valid Content-ID, so the `cid:<file name>` references of
+/// the HTML body are pointed at the generated IDs of the inline parts.
+fn link_content_ids(html: &str, content_ids: &HashMap<&str, String>) -> String {
+    if content_ids.is_empty() {
+        return html.to_string();
+    }
+    let reference = Regex::new(r#"cid:([^"'\s>)]+)"#).expect("the pattern is valid");
+    reference
+        .replace_all(html, |captures: &Captures| {
+            match content_ids.get(&captures[1]) {
+                Some(content_id) => format!("cid:{content_id}"),
+                None => captures[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Base64 in lines of 76 characters as RFC 2045 asks.
+fn wrapped_base64(data: &[u8]) -> String {
+    let encoded = STANDARD.encode(data);
+    let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / 38);
+    for line in encoded.as_bytes().chunks(76) {
+        wrapped.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        wrapped.push_str("\r\n");
+    }
+    wrapped
+}
+
+/// Header values outside printable ASCII are sent as RFC 2047 encoded words.
+fn encode_word(value: &str) -> String {
+    match value
+        .chars()
+        .all(|char| char.is_ascii() && !char.is_ascii_control())
+    {
+        true => value.to_string(),
+        false => format!("=?UTF-8?B?{}?=", STANDARD.encode(value)),
+    }
+}
+
+fn content_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ics" => "text/calendar",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(attachments: Vec<Part>) -> RawEmail {
+        RawEmail {
+            subject: "Prices".into(),
+            html: None,
+            text: Some("Hello".into()),
+            attachments,
+            inline: Vec::new(),
+        }
+    }
+
+    fn part(file_name: &str, data: Vec<u8>) -> Part {
+        Part {
+            file_name: file_name.into(),
+            content_type: content_type(file_name),
+            data,
+        }
+    }
+
+    #[test]
+    fn sends_a_single_body_without_multipart() {
+        let envelope = Envelope {
+            cc: vec!["c@example.com".into()],
+            bcc: vec!["b@example.com".into()],
+            ..Envelope::default()
+        };
+        let message = email(Vec::new())
+            .to_mime("a@example.com", "d@example.com", &envelope)
+            .unwrap();
+        let message = String::from_utf8(message).unwrap();
+        assert!(message.starts_with(
+            "From: a@example.com\r\nTo: d@example.com\r\nCc: c@example.com\r\nSubject: Prices\r\nMIME-Version: 1.0\r\n\
+            Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\nSGVsbG8=\r\n"
+        ));
+        assert!(!message.contains("multipart"));
+        assert!(!message.contains("b@example.com"));
+    }
+
+    #[test]
+    fn nests_bodies_and_attachments() {
+        let mut raw = email(vec![part("prices.pdf", b"%PDF".to_vec())]);
+        raw.html = Some("<p>Hello</p>".into());
+        let message = raw
+            .to_mime("a@example.com", "d@example.com", &Envelope::default())
+            .unwrap();
+        let message = String::from_utf8(message).unwrap();
+        let mixed = message.find("multipart/mixed").unwrap();
+        let alternative = message.find("multipart/alternative").unwrap();
+        assert!(mixed < alternative);
+        assert!(!message.contains("multipart/related"));
+        assert!(message.contains(
+            "Content-Type: application/pdf; name=\"prices.pdf\"\r\nContent-Disposition: attachment; filename=\"prices.pdf\"\r\n"
+        ));
+    }
+
+    #[test]
+    fn references_inline_parts_by_generated_content_ids() {
+        let mut raw = email(Vec::new());
+        raw.html = Some(
+            "<img src=\"cid:my logo.png\"><img src='cid:chart.png'><img src=\"cid:other.png\">"
+                .into(),
+        );
+        raw.inline = vec![
+            part("my logo.png", b"png".to_vec()),
+            part("chart.png", b"png".to_vec()),
+        ];
+        let message = raw
+            .to_mime("a@example.com", "d@example.com", &Envelope::default())
+            .unwrap();
+        let message = String::from_utf8(message).unwrap();
+        assert!(message.contains(
+            "Content-Disposition: inline; filename=\"my logo.png\"\r\nContent-ID: <part0@ses-client>\r\n"
+        ));
+        assert!(message.contains("Content-ID: <part1@ses-client>\r\n"));
+        assert!(
+            message.find("multipart/related").unwrap()
+                < message.find("multipart/alternative").unwrap()
+        );
+
+        let content_ids = HashMap::from([("chart.png", "part1@ses-client".to_string())]);
+        assert_eq!(
+            link_content_ids(
+                "<img src='cid:chart.png'><img src=cid:other.png>",
+                &content_ids
+            ),
+            "<img src='cid:part1@ses-client'><img src=cid:other.png>"
+        );
+    }
+
+    #[test]
+    fn gives_inline_files_of_the_same_name_their_own_content_ids() {
+        let mut raw = email(Vec::new());
+        raw.html = Some("<img src=\"cid:logo.png\">".into());
+        raw.inline = vec![
+            part("logo.png", b"first".to_vec()),
+            part("logo.png", b"second".to_vec()),
+        ];
+        let message = raw
+            .to_mime("a@example.com", "d@example.com", &Envelope::default())
+            .unwrap();
+        let message = String::from_utf8(message).unwrap();
+        assert!(message.contains("Content-ID: <part0@ses-client>\r\n"));
+        assert!(message.contains("Content-ID: <part1@ses-client>\r\n"));
+    }
+
+    #[test]
+    fn escapes_and_encodes_file_name_parameters() {
+        assert_eq!(parameter("name", "prices.pdf"), "name=\"prices.pdf\"");
+        assert_eq!(
+            parameter("filename", r#"a "quoted" \name.pdf"#),
+            r#"filename="a \"quoted\" \\name.pdf""#
+        );
+        assert_eq!(
+            parameter("filename", "Préstamo 1.pdf"),
+            "filename*=UTF-8''Pr%C3%A9stamo%201.pdf"
+        );
+    }
+
+    #[test]
+    fn rejects_messages_over_the_size_limit() {
+        let raw = email(vec![part("big.zip", vec![0; MAX_RAW_MESSAGE_SIZE * 3 / 4])]);
+        assert!(raw
+            .to_mime("a@example.com", "d@example.com", &Envelope::default())
+            .is_err());
+    }
+
+    #[test]
+    fn wraps_base64_lines() {
+        let encoded = wrapped_base64(&[7; 100]);
+        let lines = encoded.split_terminator("\r\n").collect::<Vec<_>>();
+        assert_eq!(
+            lines.iter().map(|line| line.len()).collect::<Vec<_>>(),
+            [76, 60]
+        );
+        assert_eq!(wrapped_base64(b""), "");
+    }
+
+    #[test]
+    fn encodes_non_ascii_headers() {
+        assert_eq!(encode_word("Prices"), "Prices");
+        assert_eq!(encode_word("Préstamo"), "=?UTF-8?B?UHLDqXN0YW1v?=");
+        assert_eq!(content_type("Report.PDF"), "application/pdf");
+        assert_eq!(content_type("notes"), "application/octet-stream");
+    }
+}
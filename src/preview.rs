@@ -0,0 +1,50 @@
+use crate::content::{read_file, write_file};
+use crate::error::Result;
+use crate::render::{missing_variables, render};
+use colored::Colorize;
+use serde_json::Value;
+use std::path::Path;
+
+/// Reads `subject.html`, `template.html` and the optional `text_body.txt` from a
+/// directory, the layout 'Get Email Template' downloads a template in.
+pub fn template_parts_from_dir(dir: &str) -> Result<(String, String, String)> {
+    let dir = Path::new(dir);
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let subject = read_file(&path("subject.html"), "Subject")?;
+    let html = read_file(&path("template.html"), "Template")?;
+    let text_path = path("text_body.txt");
+    let text = match Path::new(&text_path).exists() {
+        true => read_file(&text_path, "Text Body")?,
+        false => String::new(),
+    };
+    Ok((subject, html, text))
+}
+
+/// Renders the template parts with the data, see `render` for the supported syntax, writes
+/// the HTML to `output` so it can be opened in a browser and prints the subject and text.
+pub fn preview(
+    (subject, html, text): &(String, String, String),
+    data: &Value,
+    output: &str,
+) -> Result<()> {
+    let missing = missing_variables(&[subject, html, text], data);
+    write_file(output, render(html, data))?;
+    println!("{} {}", "Subject:".yellow().bold(), render(subject, data));
+    if !text.is_empty() {
+        println!("{}\n{}", "Text Body:".yellow().bold(), render(text, data));
+    }
+    if !missing.is_empty() {
+        println!(
+            "{} {}",
+            "These variables are missing from the template data, SES fails to render the message without them:"
+                .red()
+                .bold(),
+            missing.join(", ")
+        );
+    }
+    println!(
+        "The rendered HTML is written to '{}', open it in a browser to preview it\n",
+        output.green().bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::contact_filter::ContactFilter;
+use crate::contacts::{get_contact_record, list_recipient_emails};
+use crate::content::read_file;
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::send::parse_template_data;
+use futures::stream::{self, StreamExt};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A recipient with its template data, or why the data couldn't be read.
+pub type RecipientData = (String, std::result::Result<Value, String>);
+
+/// Every contact of the list, or of its `topic`, that matches the `selection`
+/// with its own template data.
+///
+/// The data comes from the row of the data file with the same email, contacts
+/// without a row fall back to the attributes stored with the contact. A contact
+/// that can't be read even after the throttling retries keeps the reason, so it
+/// fails on its own instead of the whole send.
+pub async fn recipient_data(
+    ctx: &Context,
+    list_name: &str,
+    topic: Option<&str>,
+    selection: &ContactFilter,
+    data_file: Option<&str>,
+) -> Result<Vec<RecipientData>> {
+    let mut file_data = match data_file {
+        Some(path) => read_data_file(path)?,
+        None => HashMap::new(),
+    };
+    let emails = list_recipient_emails(&ctx.client, list_name, topic, selection).await?;
+    let recipients = emails
+        .into_iter()
+        .map(|email| {
+            let data = file_data.remove(&email.to_lowercase());
+            (email, data)
+        })
+        .collect::<Vec<_>>();
+    Ok(stream::iter(recipients)
+        .map(|(email, data)| async move {
+            let data = match data {
+                Some(data) => Ok(data),
+                None => get_contact_record(&ctx.client, list_name, &email)
+                    .await
+                    .map(|record| Value::Object(record.attributes))
+                    .map_err(|err| err.to_string()),
+            };
+            (email, data)
+        })
+        .buffered(10)
+        .collect()
+        .await)
+}
+
+/// The attributes of the contact as template data, overlaid with the JSON
+/// `template_data` when it is given.
+pub async fn contact_template_data(
+    ctx: &Context,
+    list_name: &str,
+    email: &str,
+    template_data: Option<&str>,
+) -> Result<String> {
+    let attributes = Value::Object(
+        get_contact_record(&ctx.client, list_name, email)
+            .await?
+            .attributes,
+    );
+    let data = match template_data {
+        Some(template_data) => merge(&attributes, &parse_template_data(template_data)?),
+        None => attributes,
+    };
+    Ok(data.to_string())
+}
+
+/// Overlays the recipient data on the shared default data, the way SES combines
+/// `ReplacementTemplateData` with the default template data.
+pub fn merge(default_data: &Value, data: &Value) -> Value {
+    match (default_data, data) {
+        (Value::Object(default_data), Value::Object(data)) => {
+            let mut merged = default_data.clone();
+            merged.extend(data.clone());
+            Value::Object(merged)
+        }
+        _ => data.clone(),
+    }
+}
+
+/// Reads the template data of each recipient keyed by the lowercase email.
+///
+/// A CSV file needs an 'email' column and every other column becomes a variable,
+/// a JSON file is either an array of objects with an 'email' field or an object
+/// mapping each email to its data.
+fn read_data_file(path: &str) -> Result<HashMap<String, Value>> {
+    let content = read_file(path, "Template Data")?;
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let invalid = |reason: String| {
+        SesClientError::validation(format!("The template data file '{path}' {reason}"))
+    };
+    match extension.as_deref() {
+        Some("json") => {
+            let value: Value = serde_json::from_str(&content)
+                .map_err(|err| invalid(format!("isn't valid JSON: {err}")))?;
+            match value {
+                Value::Array(rows) => rows
+                    .into_iter()
+                    .map(|row| match row {
+                        Value::Object(mut data) => match data.remove("email") {
+                            Some(Value::String(email)) => {
+                                Ok((email.trim().to_lowercase(), Value::Object(data)))
+                            }
+                            _ => Err(invalid("has an object without an 'email' string".into())),
+                        },
+                        _ => Err(invalid("has an array item that isn't an object".into())),
+                    })
+                    .collect(),
+                Value::Object(by_email) => Ok(by_email
+                    .into_iter()
+                    .map(|(email, data)| (email.trim().to_lowercase(), data))
+                    .collect()),
+                _ => Err(invalid("must be an array or an object".into())),
+            }
+        }
+        Some("csv") => {
+            let mut reader = csv::Reader::from_reader(content.as_bytes());
+            let headers = reader
+                .headers()
+                .map_err(|err| invalid(format!("couldn't be read: {err}")))?
+                .clone();
+            let mut by_email = HashMap::new();
+            for record in reader.records() {
+                let record = record.map_err(|err| invalid(format!("couldn't be read: {err}")))?;
+                let mut data = Map::new();
+                let mut email = None;
+                for (header, value) in headers.iter().zip(record.iter()) {
+                    match header.trim() {
+                        "email" => email = Some(value.trim().to_lowercase()),
+                        header => {
+                            data.insert(header.to_string(), Value::String(value.to_string()));
+                        }
+                    }
+                }
+                match email {
+                    Some(email) => by_email.insert(email, Value::Object(data)),
+                    None => return Err(invalid("needs an 'email' column".into())),
+                };
+            }
+            Ok(by_email)
+        }
+        _ => Err(invalid("must have a .csv or .json extension".into())),
+    }
+}
@@ -0,0 +1,447 @@
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Renders a template part with the JSON template data, covering the subset of
+/// Handlebars that SES templates commonly use.
+///
+/// - `{{name}}` is replaced with the HTML-escaped value and `{{{name}}}` with the
+///   raw value. Dotted names such as `{{user.name}}` look up nested objects, and
+///   a variable missing from the data renders as an empty string.
+/// - `{{#if name}}`, `{{#unless name}}` and `{{#with name}}` blocks, each with an
+///   optional `{{else}}`, follow the Handlebars truthiness rules.
+/// - `{{#each name}}` repeats its block for the items of an array or the values of
+///   an object, with `this`, `@index`, `@key`, `@first`, `@last` and `../name`.
+/// - `{{! comments }}` render as nothing.
+///
+/// Other helpers, partials and subexpressions aren't supported. Their tags render
+/// as nothing, so the preview of a template using them differs from what SES sends.
+pub fn render(part: &str, data: &Value) -> String {
+    let mut rendered = String::with_capacity(part.len());
+    let scope = Scope {
+        value: data,
+        parent: None,
+        index: None,
+        key: None,
+        count: 0,
+    };
+    render_nodes(&parse(part), &scope, &mut rendered);
+    rendered
+}
+
+/// The names of the variables a template part reads from the top level of the
+/// data, in order of appearance.
+///
+/// Helper tags aren't variables. The conditions of `#if` and `#unless` are left
+/// out since a missing value only makes them false, and the names inside `#each`
+/// and `#with` blocks are left out since they refer to the items.
+pub fn variables(part: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_variables(&parse(part), &mut names);
+    names
+}
+
+/// The variables of the template parts that the data doesn't provide, SES fails
+/// to render a message with any of them.
+pub fn missing_variables(parts: &[&str], data: &Value) -> Vec<String> {
+    let mut missing = Vec::new();
+    for name in parts.iter().flat_map(|part| variables(part)) {
+        if lookup(data, &name).is_none() && !missing.contains(&name) {
+            missing.push(name);
+        }
+    }
+    missing
+}
+
+enum Node {
+    Text(String),
+    Variable {
+        name: String,
+        escape: bool,
+    },
+    Block {
+        helper: String,
+        argument: String,
+        body: Vec<Node>,
+        inverse: Vec<Node>,
+    },
+}
+
+/// A block being parsed, its nodes go to `inverse` once its `{{else}}` is seen.
+struct OpenBlock {
+    helper: String,
+    argument: String,
+    body: Vec<Node>,
+    inverse: Option<Vec<Node>>,
+}
+
+impl OpenBlock {
+    fn nodes(&mut self) -> &mut Vec<Node> {
+        match &mut self.inverse {
+            Some(inverse) => inverse,
+            None => &mut self.body,
+        }
+    }
+
+    fn close(self) -> Node {
+        Node::Block {
+            helper: self.helper,
+            argument: self.argument,
+            body: self.body,
+            inverse: self.inverse.unwrap_or_default(),
+        }
+    }
+}
+
+/// Parses the tags leniently the way a preview should, an unmatched `{{/block}}`
+/// is ignored and blocks still open at the end are closed there.
+fn parse(part: &str) -> Vec<Node> {
+    let mut stack = vec![OpenBlock {
+        helper: String::new(),
+        argument: String::new(),
+        body: Vec::new(),
+        inverse: None,
+    }];
+    let mut rest = part;
+    while let Some(start) = rest.find("{{") {
+        let raw = rest[start..].starts_with("{{{");
+        let (open, close) = if raw { (3, "}}}") } else { (2, "}}") };
+        let Some(end) = rest[start + open..].find(close) else {
+            break;
+        };
+        let current = stack.last_mut().expect("the root block is never closed");
+        if start > 0 {
+            current.nodes().push(Node::Text(rest[..start].to_string()));
+        }
+        // `~` strips whitespace in Handlebars, it is only dropped here
+        let tag = rest[start + open..start + open + end]
+            .trim_matches('~')
+            .trim();
+        rest = &rest[start + open + end + close.len()..];
+        if raw {
+            current.nodes().push(Node::Variable {
+                name: tag.to_string(),
+                escape: false,
+            });
+            continue;
+        }
+        match tag.chars().next() {
+            Some('!' | '>') => {}
+            Some('#') => {
+                let (helper, argument) = tag[1..]
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((&tag[1..], ""));
+                stack.push(OpenBlock {
+                    helper: helper.to_string(),
+                    argument: argument.trim().to_string(),
+                    body: Vec::new(),
+                    inverse: None,
+                });
+            }
+            Some('/') => {
+                if stack.len() > 1 {
+                    let block = stack.pop().expect("checked above").close();
+                    stack
+                        .last_mut()
+                        .expect("the root block is never closed")
+                        .nodes()
+                        .push(block);
+                }
+            }
+            // The root block can't have an `{{else}}`, its helper is empty
+            _ if tag == "else" || tag == "^" => {
+                if !current.helper.is_empty() && current.inverse.is_none() {
+                    current.inverse = Some(Vec::new());
+                }
+            }
+            _ => current.nodes().push(Node::Variable {
+                name: tag.to_string(),
+                escape: true,
+            }),
+        }
+    }
+    let mut current = stack.pop().expect("the root block is never closed");
+    current.nodes().push(Node::Text(rest.to_string()));
+    while let Some(mut parent) = stack.pop() {
+        parent.nodes().push(current.close());
+        current = parent;
+    }
+    current.body
+}
+
+/// The value `this` refers to, with the loop variables of an `#each` item.
+struct Scope<'a> {
+    value: &'a Value,
+    parent: Option<&'a Scope<'a>>,
+    index: Option<usize>,
+    key: Option<&'a str>,
+    count: usize,
+}
+
+impl<'a> Scope<'a> {
+    fn child(&'a self, value: &'a Value) -> Scope<'a> {
+        Scope {
+            value,
+            parent: Some(self),
+            index: None,
+            key: None,
+            count: 0,
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Cow<'a, Value>> {
+        if let Some(name) = name.strip_prefix("../") {
+            return self.parent.and_then(|parent| parent.lookup(name));
+        }
+        let value = match name {
+            "@index" => self.index.map(Value::from),
+            "@key" => self.key.map(Value::from),
+            "@first" => self.index.map(|index| Value::Bool(index == 0)),
+            "@last" => self.index.map(|index| Value::Bool(index + 1 == self.count)),
+            _ => None,
+        };
+        if value.is_some() {
+            return value.map(Cow::Owned);
+        }
+        let name = ["this.", "this/", "./"]
+            .iter()
+            .find_map(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name);
+        match name {
+            "this" | "." => Some(Cow::Borrowed(self.value)).filter(|value| !value.is_null()),
+            name => lookup(self.value, name).map(Cow::Borrowed),
+        }
+    }
+}
+
+fn render_nodes(nodes: &[Node], scope: &Scope, rendered: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => rendered.push_str(text),
+            Node::Variable { name, escape } => {
+                if let Some(value) = scope.lookup(name) {
+                    let value = value_to_string(&value);
+                    match escape {
+                        true => rendered.push_str(&escape_html(&value)),
+                        false => rendered.push_str(&value),
+                    }
+                }
+            }
+            Node::Block {
+                helper,
+                argument,
+                body,
+                inverse,
+            } => {
+                let value = scope.lookup(argument);
+                let truthy = value.as_deref().is_some_and(is_truthy);
+                match helper.as_str() {
+                    "if" if truthy => render_nodes(body, scope, rendered),
+                    "unless" if !truthy => render_nodes(body, scope, rendered),
+                    "if" | "unless" => render_nodes(inverse, scope, rendered),
+                    "with" => match value.as_deref() {
+                        Some(value) if truthy => render_nodes(body, &scope.child(value), rendered),
+                        _ => render_nodes(inverse, scope, rendered),
+                    },
+                    "each" => {
+                        let items = match value.as_deref() {
+                            Some(Value::Array(items)) => {
+                                items.iter().map(|item| (None, item)).collect::<Vec<_>>()
+                            }
+                            Some(Value::Object(items)) => items
+                                .iter()
+                                .map(|(key, item)| (Some(key.as_str()), item))
+                                .collect(),
+                            _ => Vec::new(),
+                        };
+                        if items.is_empty() {
+                            render_nodes(inverse, scope, rendered);
+                        }
+                        for (index, (key, item)) in items.iter().enumerate() {
+                            let item_scope = Scope {
+                                index: Some(index),
+                                key: *key,
+                                count: items.len(),
+                                ..scope.child(item)
+                            };
+                            render_nodes(body, &item_scope, rendered);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn collect_variables(nodes: &[Node], names: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Variable { name, .. } => {
+                let local = name.starts_with('@')
+                    || name.starts_with("../")
+                    || name == "this"
+                    || name.starts_with("this.")
+                    || name.starts_with("this/")
+                    || name == ".";
+                if !local && !name.contains(char::is_whitespace) && !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+            Node::Block {
+                helper,
+                body,
+                inverse,
+                ..
+            } => {
+                if matches!(helper.as_str(), "if" | "unless") {
+                    collect_variables(body, names);
+                }
+                collect_variables(inverse, names);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(data: &'a Value, name: &str) -> Option<&'a Value> {
+    name.split(['.', '/'])
+        .try_fold(data, |value, key| match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            value => value.get(key),
+        })
+        .filter(|value| !value.is_null())
+}
+
+/// `false`, `null`, `0`, an empty string and an empty array are false like in Handlebars.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64() != Some(0.0),
+        Value::String(string) => !string.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Escapes the same characters as Handlebars.
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            '`' => escaped.push_str("&#x60;"),
+            '=' => escaped.push_str("&#x3D;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn substitutes_nested_variables() {
+        let data = json!({"name": "Ann", "user": {"plan": "pro"}, "count": 3});
+        assert_eq!(
+            render("Hi {{ name }}, {{user.plan}} x{{count}}{{missing}}", &data),
+            "Hi Ann, pro x3"
+        );
+    }
+
+    #[test]
+    fn escapes_html_unless_triple_stashed() {
+        let data = json!({"link": "<a href=\"x\">Tom & Jerry</a>"});
+        assert_eq!(
+            render("{{link}}", &data),
+            "&lt;a href&#x3D;&quot;x&quot;&gt;Tom &amp; Jerry&lt;/a&gt;"
+        );
+        assert_eq!(render("{{{link}}}", &data), "<a href=\"x\">Tom & Jerry</a>");
+    }
+
+    #[test]
+    fn renders_conditionals() {
+        let template = "{{#if vip}}VIP{{else}}Regular{{/if}} {{#unless items}}empty{{/unless}}";
+        assert_eq!(render(template, &json!({"vip": true})), "VIP empty");
+        assert_eq!(
+            render(template, &json!({"vip": 0, "items": [1]})),
+            "Regular "
+        );
+    }
+
+    #[test]
+    fn renders_each_with_loop_variables() {
+        let data = json!({"title": "Order", "items": [{"name": "a"}, {"name": "b"}]});
+        assert_eq!(
+            render(
+                "{{#each items}}{{@index}}:{{name}}/{{../title}}{{#unless @last}}, {{/unless}}{{/each}}",
+                &data
+            ),
+            "0:a/Order, 1:b/Order"
+        );
+        assert_eq!(
+            render(
+                "{{#each tags}}{{this}}{{else}}none{{/each}}",
+                &json!({"tags": []})
+            ),
+            "none"
+        );
+        assert_eq!(
+            render(
+                "{{#each prices}}{{@key}}={{this}};{{/each}}",
+                &json!({"prices": {"a": 1}})
+            ),
+            "a=1;"
+        );
+    }
+
+    #[test]
+    fn renders_with_and_skips_comments() {
+        let data = json!({"user": {"name": "Ann"}});
+        assert_eq!(
+            render("{{! hidden }}{{#with user}}{{name}}{{/with}}", &data),
+            "Ann"
+        );
+    }
+
+    #[test]
+    fn keeps_unclosed_tags_as_text() {
+        assert_eq!(render("Hi {{name", &json!({"name": "Ann"})), "Hi {{name");
+    }
+
+    #[test]
+    fn variables_skip_helpers_and_block_scopes() {
+        let template = "{{name}} {{#if vip}}{{discount}}{{else}}{{price}}{{/if}} \
+            {{#each items}}{{title}} {{@index}}{{/each}} {{{raw}}} {{! note }} {{name}}";
+        assert_eq!(
+            variables(template),
+            vec!["name", "discount", "price", "raw"]
+        );
+    }
+
+    #[test]
+    fn missing_variables_checks_the_data() {
+        let parts = [
+            "Hi {{name}}",
+            "{{#each items}}{{sku}}{{/each}} {{user.plan}}",
+        ];
+        assert_eq!(
+            missing_variables(&parts, &json!({"name": "Ann", "user": {}})),
+            vec!["user.plan"]
+        );
+    }
+}
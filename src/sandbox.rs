@@ -0,0 +1,205 @@
+use crate::cli::UnverifiedAction;
+use crate::contact_filter::write_emails;
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::identities::{is_verified, list_identities, send_verification};
+use colored::Colorize;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use inquire::Select;
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether the account can only send to verified identities.
+pub async fn is_sandboxed(ctx: &Context) -> Result<bool> {
+    let output = ctx
+        .client
+        .get_account()
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("GetAccount", err))?;
+    Ok(!output.production_access_enabled())
+}
+
+/// Keeps the recipients a sandboxed account can send to, a production account
+/// keeps every one of them.
+///
+/// An address can receive when it is verified itself or its domain is. The others
+/// are skipped, sent a verification email and skipped, or abort the send, as
+/// `ctx.unverified` says or else as the user chooses. Only addresses without an
+/// identity get a verification email, existing identities are left alone.
+///
+/// The skipped recipients are written to a report file. They aren't recorded in
+/// the journal, so resuming the send once they are verified reaches them.
+pub async fn screen_recipients<T>(
+    ctx: &Context,
+    recipients: Vec<T>,
+    email_of: fn(&T) -> &str,
+) -> Result<Vec<T>> {
+    if recipients.is_empty() || !is_sandboxed(ctx).await? {
+        return Ok(recipients);
+    }
+    println!(
+        "{}\n",
+        "The account is in the SES sandbox, checking that every recipient is verified..."
+            .yellow()
+            .bold()
+    );
+    let unverified = unverified_emails(ctx, recipients.iter().map(email_of)).await?;
+    if unverified.is_empty() {
+        println!("{}\n", "Every recipient is verified".green().bold());
+        return Ok(recipients);
+    }
+    println!(
+        "{} of {} recipients aren't verified and can't receive emails from a sandboxed account:",
+        unverified.len().to_string().red().bold(),
+        recipients.len()
+    );
+    for email in &unverified {
+        println!("    {email}");
+    }
+    println!();
+    let action = match ctx.unverified {
+        Some(action) => action,
+        None => prompt_action()?,
+    };
+    match action {
+        UnverifiedAction::Abort => {
+            return Err(SesClientError::validation(format!(
+                "The send was aborted since {} recipients aren't verified, request production access to send to any address",
+                unverified.len()
+            )))
+        }
+        UnverifiedAction::Verify => {
+            let mut pending = 0;
+            for email in &unverified {
+                match send_verification(ctx, email).await {
+                    Ok(true) => {}
+                    Ok(false) => pending += 1,
+                    Err(err) => err.report(),
+                }
+            }
+            println!();
+            if pending > 0 {
+                println!(
+                    "{}\n",
+                    format!(
+                        "{pending} recipients already have an identity waiting for verification, use 'identity status --resend-pending' to send their emails again"
+                    )
+                    .yellow()
+                    .bold()
+                );
+            }
+        }
+        UnverifiedAction::Skip => {}
+    }
+    let skipped = unverified
+        .iter()
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+    let recipients = recipients
+        .into_iter()
+        .filter(|recipient| !skipped.contains(email_of(recipient)))
+        .collect::<Vec<_>>();
+    println!(
+        "{} unverified recipients are skipped, {} remain",
+        unverified.len().to_string().yellow().bold(),
+        recipients.len().to_string().green().bold()
+    );
+    write_emails(&report_path(), &unverified)?;
+    Ok(recipients)
+}
+
+/// The recipients of a single send a sandboxed account can't send to, in the order
+/// they were given. A production account can send to any address, so nothing is
+/// checked for it.
+pub async fn unreachable_recipients<'a>(
+    ctx: &Context,
+    emails: impl IntoIterator<Item = &'a String>,
+) -> Result<Vec<String>> {
+    if !is_sandboxed(ctx).await? {
+        return Ok(Vec::new());
+    }
+    unverified_emails(ctx, emails.into_iter().map(String::as_str)).await
+}
+
+/// The emails that are neither verified themselves nor on a verified domain, in
+/// the order they were given.
+///
+/// The identities are listed once and only the ones that exist are asked about,
+/// an email or domain without an identity is unverified. Throttled checks are
+/// retried and any other failure aborts the screening.
+async fn unverified_emails<'a>(
+    ctx: &Context,
+    emails: impl Iterator<Item = &'a str>,
+) -> Result<Vec<String>> {
+    let mut unique = Vec::new();
+    let mut seen = HashSet::new();
+    for email in emails {
+        if seen.insert(email) {
+            unique.push(email.to_string());
+        }
+    }
+    let identities = list_identities(ctx)
+        .await?
+        .into_iter()
+        .map(|(identity, _)| (identity.to_lowercase(), identity))
+        .collect::<HashMap<_, _>>();
+    let domain_of = |email: &str| {
+        email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.to_lowercase())
+    };
+    let candidates = unique
+        .iter()
+        .flat_map(|email| [Some(email.to_lowercase()), domain_of(email)])
+        .flatten()
+        .filter_map(|identity| identities.get(&identity).cloned())
+        .collect::<HashSet<_>>();
+    let verified = stream::iter(candidates)
+        .map(|identity| async move {
+            is_verified(ctx, &identity)
+                .await
+                .map(|verified| verified.then(|| identity.to_lowercase()))
+        })
+        .buffer_unordered(10)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<HashSet<_>>();
+    Ok(unique
+        .into_iter()
+        .filter(|email| {
+            !verified.contains(&email.to_lowercase())
+                && !domain_of(email).is_some_and(|domain| verified.contains(&domain))
+        })
+        .collect())
+}
+
+/// `skipped_recipients_<unix time in ms>.txt` in the current directory.
+fn report_path() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    format!("skipped_recipients_{millis}.txt")
+}
+
+fn prompt_action() -> Result<UnverifiedAction> {
+    let choices = vec![
+        "Skip the unverified recipients",
+        "Send them verification emails and skip them",
+        "Abort the send",
+    ];
+    let choice = Select::new(
+        "What should happen to the unverified recipients?\n",
+        choices,
+    )
+    .with_help_message("Verified recipients can be reached by resuming the journal later")
+    .prompt()?;
+    Ok(match choice {
+        "Skip the unverified recipients" => UnverifiedAction::Skip,
+        "Send them verification emails and skip them" => UnverifiedAction::Verify,
+        _ => UnverifiedAction::Abort,
+    })
+}
@@ -0,0 +1,749 @@
+use crate::body::SimpleBody;
+use crate::bulk::{self, BulkSender, Delivery};
+use crate::contacts::list_recipient_emails;
+use crate::content::{absolute_path, read_file};
+use crate::context::Context;
+use crate::dry_run::{self, RenderedEmail};
+use crate::envelope::Envelope;
+use crate::error::{Result, SesClientError};
+use crate::journal::{Campaign, Journal};
+use crate::mime::{RawEmail, RawFiles};
+use crate::recipients::{merge, recipient_data, RecipientData};
+use crate::sandbox::screen_recipients;
+use crate::templates;
+use aws_apis::SesOps;
+use aws_sdk_sesv2::primitives::Blob;
+use aws_sdk_sesv2::types::{
+    BulkEmailContent, BulkEmailEntry, BulkEmailEntryResult, BulkEmailStatus, Content, EmailContent,
+    Message, RawMessage, ReplacementEmailContent, ReplacementTemplate, Template,
+};
+use colored::Colorize;
+use serde_json::Value;
+
+/// Sends a simple email to verified addresses.
+pub async fn simple(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    to: &[String],
+    subject: &str,
+    body: &SimpleBody,
+    envelope: &Envelope,
+    from_address: Option<&str>,
+) -> Result<()> {
+    let envelope = &envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate(to)?;
+    let from = from_address.unwrap_or(ses_ops.get_from_address());
+    let recipients = to.join(", ");
+    if ctx.dry_run {
+        return dry_run::report(&[
+            RenderedEmail::simple(&recipients, from, subject, body).with_envelope(envelope)
+        ]);
+    }
+    ctx.client
+        .send_email()
+        .from_email_address(from)
+        .destination(envelope.destination(to.to_vec()))
+        .set_reply_to_addresses(envelope.reply_to_addresses())
+        .set_configuration_set_name(envelope.configuration_set.clone())
+        .set_email_tags(envelope.email_tags())
+        .content(simple_content(subject, body))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("SendEmail", err))?;
+    println!(
+        "A simple email has been successfully sent to '{}'\n{}\n",
+        recipients.green().bold(),
+        "Please check your inbox to view it".yellow().bold()
+    );
+    Ok(())
+}
+
+/// Sends the same simple email to every address in the contact list, `None` uses the defaults.
+pub async fn bulk_simple(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    list_name: Option<&str>,
+    subject: &str,
+    body: &SimpleBody,
+    envelope: &Envelope,
+    from_address: Option<&str>,
+) -> Result<()> {
+    let list_name = list_name.unwrap_or(ses_ops.get_list_name());
+    let from = from_address.unwrap_or(ses_ops.get_from_address());
+    let envelope = &envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate_copies(1)?;
+    if ctx.dry_run {
+        let emails = list_recipient_emails(
+            &ctx.client,
+            list_name,
+            envelope.topic.as_deref(),
+            &envelope.selection,
+        )
+        .await?
+        .iter()
+        .map(|email| RenderedEmail::simple(email, from, subject, body).with_envelope(envelope))
+        .collect::<Vec<_>>();
+        return dry_run::report(&emails);
+    }
+    let emails = list_recipient_emails(
+        &ctx.client,
+        list_name,
+        envelope.topic.as_deref(),
+        &envelope.selection,
+    )
+    .await?;
+    let emails = screen_recipients(ctx, emails, String::as_str).await?;
+    let campaign = Campaign::Simple {
+        list_name: list_name.into(),
+        from_address: from.into(),
+        subject: subject.into(),
+        html: body.html.clone(),
+        text: body.text.clone(),
+        envelope: envelope.clone(),
+    };
+    let content = simple_content(subject, body);
+    let journal = || Journal::create(&campaign);
+    simple_campaign(ctx, list_name, from, &content, emails, envelope, journal).await
+}
+
+/// Sends to every recipient, the journal is only started once the quota of the
+/// account covers the send so a refused send leaves none behind.
+async fn simple_campaign(
+    ctx: &Context,
+    list_name: &str,
+    from: &str,
+    content: &EmailContent,
+    emails: Vec<String>,
+    envelope: &Envelope,
+    journal: impl FnOnce() -> Result<Journal>,
+) -> Result<()> {
+    let sender = BulkSender::for_account(ctx, emails.len(), envelope).await?;
+    let sender = sender.with_journal(journal()?);
+    let list_management = envelope.list_management(list_name);
+    let deliveries = sender
+        .run(emails, |email: &String| {
+            let request = ctx
+                .client
+                .send_email()
+                .from_email_address(from)
+                .destination(envelope.destination(vec![email.clone()]))
+                .set_reply_to_addresses(envelope.reply_to_addresses())
+                .set_configuration_set_name(envelope.configuration_set.clone())
+                .set_email_tags(envelope.email_tags())
+                .set_list_management_options(list_management.clone())
+                .content(content.clone());
+            let recipient = email.clone();
+            async move {
+                request.send().await.map(|output| {
+                    vec![Delivery {
+                        recipient,
+                        outcome: Ok(output.message_id().unwrap_or_default().to_string()),
+                    }]
+                })
+            }
+        })
+        .await;
+    bulk::report(&deliveries, "simple emails")
+}
+
+fn simple_content(subject: &str, body: &SimpleBody) -> EmailContent {
+    EmailContent::builder()
+        .simple(
+            Message::builder()
+                .subject(Content::builder().data(subject).charset("UTF-8").build())
+                .body(body.to_body())
+                .build(),
+        )
+        .build()
+}
+
+/// Sends a templated email to verified addresses, `None` uses the default template.
+pub async fn templated(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    to: &[String],
+    template_name: Option<&str>,
+    template_data: &str,
+    envelope: &Envelope,
+    from_address: Option<&str>,
+) -> Result<()> {
+    let envelope = &envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate(to)?;
+    let template_name = template_name.unwrap_or(ses_ops.get_template_name());
+    let from = from_address.unwrap_or(ses_ops.get_from_address());
+    let recipients = to.join(", ");
+    if ctx.dry_run {
+        let data = parse_template_data(template_data)?;
+        let parts = templates::parts(ctx, template_name).await?;
+        return dry_run::report(&[RenderedEmail::templated(
+            &recipients,
+            from,
+            template_name,
+            &parts,
+            &data,
+        )
+        .with_envelope(envelope)]);
+    }
+    ctx.client
+        .send_email()
+        .from_email_address(from)
+        .destination(envelope.destination(to.to_vec()))
+        .set_reply_to_addresses(envelope.reply_to_addresses())
+        .set_configuration_set_name(envelope.configuration_set.clone())
+        .set_email_tags(envelope.email_tags())
+        .content(
+            EmailContent::builder()
+                .template(
+                    Template::builder()
+                        .template_name(template_name)
+                        .template_data(template_data)
+                        .build(),
+                )
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("SendEmail", err))?;
+    println!(
+        "The template email is send to: {}\n",
+        recipients.green().bold()
+    );
+    Ok(())
+}
+
+/// A bulk templated send, `None` uses the default values.
+#[derive(Default)]
+pub struct BulkTemplated<'a> {
+    pub template_name: Option<&'a str>,
+    pub list_name: Option<&'a str>,
+    pub from_address: Option<&'a str>,
+    /// CSV or JSON file with the template data of each recipient, keyed by email
+    pub data_file: Option<&'a str>,
+    /// JSON file with the template data shared by every recipient
+    pub default_data: Option<&'a str>,
+    pub envelope: Envelope,
+}
+
+/// SendBulkEmail accepts at most this many destinations per request.
+const BULK_ENTRIES_PER_REQUEST: usize = 50;
+
+/// Sends the template to every address in the contact list, each one with its own
+/// `ReplacementTemplateData` from the data file or the attributes of the contact.
+pub async fn bulk_templated(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    bulk: &BulkTemplated<'_>,
+) -> Result<()> {
+    let template_name = bulk.template_name.unwrap_or(ses_ops.get_template_name());
+    let list_name = bulk.list_name.unwrap_or(ses_ops.get_list_name());
+    let from_address = bulk.from_address.unwrap_or(ses_ops.get_from_address());
+    println!(
+        "Template Name: {}\nFrom Address: {}\nList Name: {}\n",
+        template_name.green().bold(),
+        from_address.green().bold(),
+        list_name.green().bold()
+    );
+    if [template_name, list_name, from_address]
+        .iter()
+        .any(|value| value.is_empty())
+    {
+        return Err(SesClientError::validation("Provide the template name, from address and list name or set the 'TEMPLATE_NAME', 'FROM_ADDRESS' and 'LIST_NAME' environment variables to send a bulk of templated emails"));
+    }
+    let envelope = &bulk.envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate_copies(1)?;
+    let default_data = read_default_data(bulk.default_data)?;
+    let recipients = recipient_data(
+        ctx,
+        list_name,
+        envelope.topic.as_deref(),
+        &envelope.selection,
+        bulk.data_file,
+    )
+    .await?;
+    if ctx.dry_run {
+        let parts = templates::parts(ctx, template_name).await?;
+        let emails = recipients
+            .iter()
+            .filter_map(|(email, data)| match data {
+                Ok(data) => Some(
+                    RenderedEmail::templated(
+                        email,
+                        from_address,
+                        template_name,
+                        &parts,
+                        &merge(&default_data, data),
+                    )
+                    .with_envelope(envelope),
+                ),
+                Err(reason) => {
+                    println!("    {} '{email}': {reason}", "Failed".red().bold());
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        return dry_run::report(&emails);
+    }
+    let recipients = screen_recipients(ctx, recipients, |(email, _)| email.as_str()).await?;
+    let campaign = Campaign::Templated {
+        list_name: list_name.into(),
+        from_address: from_address.into(),
+        template_name: template_name.into(),
+        data_file: bulk.data_file.map(absolute_path),
+        default_data: bulk.default_data.map(absolute_path),
+        envelope: envelope.clone(),
+    };
+    let journal = || Journal::create(&campaign);
+    templated_campaign(
+        ctx,
+        list_name,
+        from_address,
+        &template_content(template_name, &default_data),
+        recipients,
+        envelope,
+        journal,
+    )
+    .await
+}
+
+/// Sends to every recipient the journal doesn't already have as delivered.
+async fn templated_campaign(
+    ctx: &Context,
+    list_name: &str,
+    from_address: &str,
+    default_content: &BulkEmailContent,
+    recipients: Vec<RecipientData>,
+    envelope: &Envelope,
+    journal: impl FnOnce() -> Result<Journal>,
+) -> Result<()> {
+    let mut unreadable = Vec::new();
+    let recipients = recipients
+        .into_iter()
+        .filter_map(|(recipient, data)| match data {
+            Ok(data) => Some((recipient, data)),
+            Err(reason) => {
+                unreadable.push(Delivery {
+                    recipient,
+                    outcome: Err(format!("The template data couldn't be read: {reason}")),
+                });
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let sender = BulkSender::for_account(ctx, recipients.len(), envelope).await?;
+    let journal = journal()?;
+    for delivery in &unreadable {
+        if let Err(err) = journal.record(delivery) {
+            err.report();
+        }
+    }
+    let sender = sender.with_journal(journal);
+    let request = ctx
+        .client
+        .send_bulk_email()
+        .from_email_address(from_address)
+        .default_content(default_content.clone())
+        .set_reply_to_addresses(envelope.reply_to_addresses())
+        .set_configuration_set_name(envelope.configuration_set.clone())
+        .set_default_email_tags(envelope.email_tags())
+        .set_list_management_options(envelope.list_management(list_name));
+    let jobs = recipients
+        .chunks(BULK_ENTRIES_PER_REQUEST)
+        .map(<[_]>::to_vec)
+        .collect::<Vec<_>>();
+    let deliveries = sender
+        .run(jobs, |chunk: &Vec<(String, Value)>| {
+            let entries = chunk
+                .iter()
+                .map(|(email, data)| (email.clone(), bulk_entry(envelope, email, data)))
+                .collect::<Vec<_>>();
+            let request = request.clone();
+            async move {
+                let (mut pending, mut deliveries, mut attempt) = (entries, Vec::new(), 0);
+                // Entries refused for a reason that can clear up are sent again with the
+                // bulk backoff, the others are done
+                while !pending.is_empty() {
+                    let request = request.clone().set_bulk_email_entries(Some(
+                        pending.iter().map(|(_, entry)| entry.clone()).collect(),
+                    ));
+                    match request.send().await {
+                        // Nothing has been sent yet, the sender retries the whole chunk
+                        Err(err) if attempt == 0 => return Err(err),
+                        Err(err)
+                            if attempt < bulk::THROTTLE_RETRIES && bulk::is_retryable(&err) => {}
+                        Err(err) => {
+                            let reason = SesClientError::ses("SendBulkEmail", err).to_string();
+                            deliveries.extend(pending.drain(..).map(|(recipient, _)| Delivery {
+                                recipient,
+                                outcome: Err(reason.clone()),
+                            }));
+                        }
+                        Ok(output) => {
+                            let mut results =
+                                output.bulk_email_entry_results().unwrap_or_default().iter();
+                            let mut transient = Vec::new();
+                            for (recipient, entry) in pending {
+                                match results.next() {
+                                    Some(result)
+                                        if attempt < bulk::THROTTLE_RETRIES
+                                            && is_transient(result.status()) =>
+                                    {
+                                        transient.push((recipient, entry))
+                                    }
+                                    result => deliveries.push(Delivery {
+                                        recipient,
+                                        outcome: entry_outcome(result),
+                                    }),
+                                }
+                            }
+                            pending = transient;
+                        }
+                    }
+                    if !pending.is_empty() {
+                        tokio::time::sleep(bulk::backoff(attempt)).await;
+                        attempt += 1;
+                    }
+                }
+                Ok(deliveries)
+            }
+        })
+        .await;
+    let deliveries = unreadable.into_iter().chain(deliveries).collect::<Vec<_>>();
+    bulk::report(&deliveries, "templated emails")
+}
+
+fn bulk_entry(envelope: &Envelope, email: &str, data: &Value) -> BulkEmailEntry {
+    BulkEmailEntry::builder()
+        .destination(envelope.destination(vec![email.to_string()]))
+        .replacement_email_content(
+            ReplacementEmailContent::builder()
+                .replacement_template(
+                    ReplacementTemplate::builder()
+                        .replacement_template_data(data.to_string())
+                        .build(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+/// Throttling and transient failures of a single entry can clear up, any other
+/// status will be refused again.
+fn is_transient(status: Option<&BulkEmailStatus>) -> bool {
+    matches!(
+        status,
+        Some(
+            BulkEmailStatus::AccountThrottled
+                | BulkEmailStatus::AccountSendingPaused
+                | BulkEmailStatus::TransientFailure
+        )
+    )
+}
+
+/// The message ID of a sent entry or why it wasn't sent, a recipient without a result
+/// of its own is counted as failed.
+fn entry_outcome(result: Option<&BulkEmailEntryResult>) -> std::result::Result<String, String> {
+    match result {
+        Some(result) => match result.status() {
+            Some(BulkEmailStatus::Success) => {
+                Ok(result.message_id().unwrap_or_default().to_string())
+            }
+            status => Err(result
+                .error()
+                .map(String::from)
+                .or_else(|| status.map(|status| status.as_str().to_string()))
+                .unwrap_or_default()),
+        },
+        None => Err("SES returned no result for the recipient".into()),
+    }
+}
+
+/// The template and the data shared by every recipient of a bulk templated send.
+fn template_content(template_name: &str, default_data: &Value) -> BulkEmailContent {
+    BulkEmailContent::builder()
+        .template(
+            Template::builder()
+                .template_name(template_name)
+                .template_data(default_data.to_string())
+                .build(),
+        )
+        .build()
+}
+
+/// Sends a multipart MIME message with attachments to a single verified address.
+pub async fn raw(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    email: &str,
+    files: &RawFiles,
+    envelope: &Envelope,
+    from_address: Option<&str>,
+) -> Result<()> {
+    let envelope = &envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate(&[email.to_string()])?;
+    let from = from_address.unwrap_or(ses_ops.get_from_address());
+    let raw_email = files.read()?;
+    if ctx.dry_run {
+        return dry_run::report(&[
+            RenderedEmail::raw(email, from, &raw_email).with_envelope(envelope)
+        ]);
+    }
+    ctx.client
+        .send_email()
+        .from_email_address(from)
+        .destination(envelope.destination(vec![email.to_string()]))
+        .set_configuration_set_name(envelope.configuration_set.clone())
+        .set_email_tags(envelope.email_tags())
+        .content(raw_content(&raw_email, from, email, envelope)?)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("SendEmail", err))?;
+    println!(
+        "The email with {} attachment(s) has been sent to '{}'\n",
+        raw_email.file_names().len(),
+        email.green().bold()
+    );
+    Ok(())
+}
+
+/// Sends a multipart MIME message with attachments to every address in the contact list,
+/// or to the contacts subscribed to the topic of the envelope.
+pub async fn bulk_raw(
+    ses_ops: &SesOps,
+    ctx: &Context,
+    list_name: Option<&str>,
+    files: &RawFiles,
+    envelope: &Envelope,
+    from_address: Option<&str>,
+) -> Result<()> {
+    let list_name = list_name.unwrap_or(ses_ops.get_list_name());
+    let from = from_address.unwrap_or(ses_ops.get_from_address());
+    let envelope = &envelope.clone().with_defaults(&ctx.defaults)?;
+    envelope.validate_copies(1)?;
+    let emails = list_recipient_emails(
+        &ctx.client,
+        list_name,
+        envelope.topic.as_deref(),
+        &envelope.selection,
+    )
+    .await?;
+    if ctx.dry_run {
+        let raw_email = files.read()?;
+        let emails = emails
+            .iter()
+            .map(|email| RenderedEmail::raw(email, from, &raw_email).with_envelope(envelope))
+            .collect::<Vec<_>>();
+        return dry_run::report(&emails);
+    }
+    let emails = screen_recipients(ctx, emails, String::as_str).await?;
+    let campaign = Campaign::Raw {
+        list_name: list_name.into(),
+        from_address: from.into(),
+        files: files.absolute(),
+        envelope: envelope.clone(),
+    };
+    let journal = || Journal::create(&campaign);
+    raw_campaign(ctx, list_name, from, files, emails, envelope, journal).await
+}
+
+/// Sends to every recipient, each message is built with the recipient in its `To`
+/// header. The journal is only started once the message and the quota are checked.
+async fn raw_campaign(
+    ctx: &Context,
+    list_name: &str,
+    from: &str,
+    files: &RawFiles,
+    emails: Vec<String>,
+    envelope: &Envelope,
+    journal: impl FnOnce() -> Result<Journal>,
+) -> Result<()> {
+    let raw_email = files.read()?;
+    // Building one message up front reports an oversized message before anything is sent
+    raw_email.to_mime(from, from, envelope)?;
+    let sender = BulkSender::for_account(ctx, emails.len(), envelope).await?;
+    let sender = sender.with_journal(journal()?);
+    let list_management = envelope.list_management(list_name);
+    let deliveries = sender
+        .run(emails, |email: &String| {
+            let request = raw_content(&raw_email, from, email, envelope).map(|content| {
+                ctx.client
+                    .send_email()
+                    .from_email_address(from)
+                    .destination(envelope.destination(vec![email.clone()]))
+                    .set_configuration_set_name(envelope.configuration_set.clone())
+                    .set_email_tags(envelope.email_tags())
+                    .set_list_management_options(list_management.clone())
+                    .content(content)
+            });
+            let recipient = email.clone();
+            async move {
+                match request {
+                    Ok(request) => request.send().await.map(|output| {
+                        vec![Delivery {
+                            recipient,
+                            outcome: Ok(output.message_id().unwrap_or_default().to_string()),
+                        }]
+                    }),
+                    // A message that can't be built fails without a request
+                    Err(err) => Ok(vec![Delivery {
+                        recipient,
+                        outcome: Err(err.to_string()),
+                    }]),
+                }
+            }
+        })
+        .await;
+    bulk::report(&deliveries, "emails with attachments")
+}
+
+fn raw_content(
+    raw_email: &RawEmail,
+    from: &str,
+    to: &str,
+    envelope: &Envelope,
+) -> Result<EmailContent> {
+    Ok(EmailContent::builder()
+        .raw(
+            RawMessage::builder()
+                .data(Blob::new(raw_email.to_mime(from, to, envelope)?))
+                .build(),
+        )
+        .build())
+}
+
+/// Sends a bulk campaign again to the recipients its journal doesn't have as delivered.
+pub async fn resume(ses_ops: &SesOps, ctx: &Context, journal_path: &str) -> Result<()> {
+    let (campaign, journal) = Journal::open(journal_path)?;
+    match campaign {
+        Campaign::Simple {
+            list_name,
+            from_address,
+            subject,
+            html,
+            text,
+            envelope,
+        } => {
+            let emails = list_recipient_emails(
+                &ctx.client,
+                &list_name,
+                envelope.topic.as_deref(),
+                &envelope.selection,
+            )
+            .await?
+            .into_iter()
+            .filter(|email| !journal.is_delivered(email))
+            .collect::<Vec<_>>();
+            if skipped_retries(ctx, "SendEmail", emails.iter()) {
+                return Ok(());
+            }
+            let emails = screen_recipients(ctx, emails, String::as_str).await?;
+            let content = simple_content(&subject, &SimpleBody { html, text });
+            simple_campaign(
+                ctx,
+                &list_name,
+                &from_address,
+                &content,
+                emails,
+                &envelope,
+                || Ok(journal),
+            )
+            .await
+        }
+        Campaign::Templated {
+            list_name,
+            from_address,
+            template_name,
+            data_file,
+            default_data,
+            envelope,
+        } => {
+            let default_data = read_default_data(default_data.as_deref())?;
+            let recipients = recipient_data(
+                ctx,
+                &list_name,
+                envelope.topic.as_deref(),
+                &envelope.selection,
+                data_file.as_deref(),
+            )
+            .await?
+            .into_iter()
+            .filter(|(email, _)| !journal.is_delivered(email))
+            .collect::<Vec<_>>();
+            if skipped_retries(
+                ctx,
+                "SendBulkEmail",
+                recipients.iter().map(|(email, _)| email),
+            ) {
+                return Ok(());
+            }
+            let recipients =
+                screen_recipients(ctx, recipients, |(email, _)| email.as_str()).await?;
+            templated_campaign(
+                ctx,
+                &list_name,
+                &from_address,
+                &template_content(&template_name, &default_data),
+                recipients,
+                &envelope,
+                || Ok(journal),
+            )
+            .await
+        }
+        Campaign::Raw {
+            list_name,
+            from_address,
+            files,
+            envelope,
+        } => {
+            let emails = list_recipient_emails(
+                &ctx.client,
+                &list_name,
+                envelope.topic.as_deref(),
+                &envelope.selection,
+            )
+            .await?
+            .into_iter()
+            .filter(|email| !journal.is_delivered(email))
+            .collect::<Vec<_>>();
+            if skipped_retries(ctx, "SendEmail", emails.iter()) {
+                return Ok(());
+            }
+            let emails = screen_recipients(ctx, emails, String::as_str).await?;
+            raw_campaign(
+                ctx,
+                &list_name,
+                &from_address,
+                &files,
+                emails,
+                &envelope,
+                || Ok(journal),
+            )
+            .await
+        }
+    }
+}
+
+/// In a dry run announces the send to each recipient a resume would retry and returns true
+/// so the caller can skip the send.
+fn skipped_retries<'a>(
+    ctx: &Context,
+    operation: &str,
+    emails: impl Iterator<Item = &'a String>,
+) -> bool {
+    for email in emails {
+        ctx.skipped(operation, email);
+    }
+    ctx.dry_run
+}
+
+fn read_default_data(path: Option<&str>) -> Result<Value> {
+    match path {
+        Some(path) => parse_template_data(&read_file(path, "Default Template Data")?),
+        None => Ok(Value::Object(Default::default())),
+    }
+}
+
+pub fn parse_template_data(template_data: &str) -> Result<Value> {
+    serde_json::from_str(template_data).map_err(|err| {
+        SesClientError::validation(format!("The template data isn't valid JSON: {err}"))
+    })
+}
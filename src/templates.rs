@@ -0,0 +1,198 @@
+use crate::content::{read_file, write_file};
+use crate::context::Context;
+use crate::error::{Result, SesClientError};
+use crate::render::{missing_variables, variables};
+use crate::send::parse_template_data;
+use aws_sdk_sesv2::error::ProvideErrorMetadata;
+use aws_sdk_sesv2::types::EmailTemplateContent;
+use colored::Colorize;
+
+/// The names of every email template in the account.
+pub async fn list_names(ctx: &Context) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = ctx
+            .client
+            .list_email_templates()
+            .page_size(100)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|err| SesClientError::ses("ListEmailTemplates", err))?;
+        names.extend(
+            output
+                .templates_metadata()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|template| template.template_name().map(String::from)),
+        );
+        match output.next_token() {
+            Some(token) => next_token = Some(token.to_string()),
+            None => break,
+        }
+    }
+    Ok(names)
+}
+
+/// Whether the template exists, any failure other than `NotFoundException` is an error.
+pub async fn exists(ctx: &Context, name: &str) -> Result<bool> {
+    match ctx
+        .client
+        .get_email_template()
+        .template_name(name)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(err) if err.code() == Some("NotFoundException") => Ok(false),
+        Err(err) => Err(SesClientError::ses("GetEmailTemplate", err)),
+    }
+}
+
+/// The subject, HTML and text parts of a template, a missing text part is empty.
+pub async fn parts(ctx: &Context, name: &str) -> Result<(String, String, String)> {
+    let output = match ctx
+        .client
+        .get_email_template()
+        .template_name(name)
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        Err(err) if err.code() == Some("NotFoundException") => {
+            return Err(SesClientError::validation(format!(
+                "The template named '{name}' doesn't exist"
+            )))
+        }
+        Err(err) => return Err(SesClientError::ses("GetEmailTemplate", err)),
+    };
+    let content = output.template_content();
+    let part = |part: Option<&str>| part.unwrap_or_default().to_string();
+    Ok((
+        part(content.and_then(|content| content.subject())),
+        part(content.and_then(|content| content.html())),
+        part(content.and_then(|content| content.text())),
+    ))
+}
+
+fn content(subject: &str, html: &str, text: Option<String>) -> EmailTemplateContent {
+    EmailTemplateContent::builder()
+        .subject(subject)
+        .html(html)
+        .set_text(text.filter(|text| !text.is_empty()))
+        .build()
+}
+
+pub async fn create(
+    ctx: &Context,
+    name: &str,
+    subject: &str,
+    html: &str,
+    text: Option<String>,
+) -> Result<()> {
+    if ctx.skipped("CreateEmailTemplate", name) {
+        return Ok(());
+    }
+    ctx.client
+        .create_email_template()
+        .template_name(name)
+        .template_content(content(subject, html, text))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("CreateEmailTemplate", err))?;
+    println!("The template '{}' has been created\n", name.green().bold());
+    Ok(())
+}
+
+/// Replaces every part of the template, a missing text part removes the current one.
+pub async fn update(
+    ctx: &Context,
+    name: &str,
+    subject: &str,
+    html: &str,
+    text: Option<String>,
+) -> Result<()> {
+    if ctx.skipped("UpdateEmailTemplate", name) {
+        return Ok(());
+    }
+    ctx.client
+        .update_email_template()
+        .template_name(name)
+        .template_content(content(subject, html, text))
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("UpdateEmailTemplate", err))?;
+    println!("The template '{}' has been updated\n", name.green().bold());
+    Ok(())
+}
+
+/// Writes the parts of the template to `subject.html`, `template.html` and, when the
+/// template has one, `text_body.txt` in the current directory.
+pub async fn download(ctx: &Context, name: &str) -> Result<()> {
+    let (subject, html, text) = parts(ctx, name).await?;
+    write_file("subject.html", &subject)?;
+    write_file("template.html", &html)?;
+    if !text.is_empty() {
+        write_file("text_body.txt", &text)?;
+    }
+    println!(
+        "The parts of the template '{}' have been written to subject.html, template.html{}\n",
+        name.green().bold(),
+        match text.is_empty() {
+            true => "",
+            false => " and text_body.txt",
+        }
+    );
+    Ok(())
+}
+
+pub async fn delete(ctx: &Context, name: &str) -> Result<()> {
+    if ctx.skipped("DeleteEmailTemplate", name) {
+        return Ok(());
+    }
+    ctx.client
+        .delete_email_template()
+        .template_name(name)
+        .send()
+        .await
+        .map_err(|err| SesClientError::ses("DeleteEmailTemplate", err))?;
+    println!("The template '{}' has been deleted\n", name.green().bold());
+    Ok(())
+}
+
+/// Prints the variables of the subject and the HTML body of the template.
+pub async fn print_variables(ctx: &Context, name: &str) -> Result<()> {
+    let (subject, html, _) = parts(ctx, name).await?;
+    println!("{}\n", "Subject Template Variables if any".yellow().bold());
+    for variable in variables(&subject) {
+        println!("    {}", variable.green().bold());
+    }
+    println!();
+    println!("{}\n", "HTML Template Variables if any".yellow().bold());
+    for variable in variables(&html) {
+        println!("    {}", variable.green().bold());
+    }
+    println!();
+    Ok(())
+}
+
+/// Checks that the JSON template data at `data_path` has a value for every variable
+/// of the template and names the ones it lacks.
+pub async fn match_data(ctx: &Context, name: &str, data_path: &str) -> Result<()> {
+    let (subject, html, text) = parts(ctx, name).await?;
+    let data = parse_template_data(&read_file(data_path, "Template Data")?)?;
+    let missing = missing_variables(&[subject.as_str(), html.as_str(), text.as_str()], &data);
+    if !missing.is_empty() {
+        return Err(SesClientError::validation(format!(
+            "The template data lacks the variables {missing:?} of the template '{name}'"
+        )));
+    }
+    println!(
+        "{}\n",
+        format!("The template data has every variable of the template '{name}'")
+            .green()
+            .bold()
+    );
+    Ok(())
+}